@@ -40,6 +40,8 @@ impl Change {
         db: &mut dyn SourceDatabase,
     ) {
         if let Some(roots) = self.roots {
+            let root_ids = (0..roots.len() as u32).map(SourceRootId).collect();
+            db.set_source_root_ids_with_durability(Arc::new(root_ids), Durability::LOW);
             for (root_id, root) in roots.into_iter().enumerate() {
                 let root_id = SourceRootId(root_id as u32);
                 for file_id in root.iter() {
@@ -12,7 +12,7 @@ use vfs::{AnchoredPath, VfsPath};
 use std::sync::Arc;
 
 use rustc_hash::{FxHashMap, FxHashSet};
-use syntax::{Parse, ParseEntryPoint};
+use syntax::{AstNode as _, Edition, Parse, ParseEntryPoint, ast};
 pub use vfs::FileId;
 
 pub trait Upcast<T: ?Sized> {
@@ -52,6 +52,17 @@ pub trait SourceDatabase: FileLoader {
     #[salsa::input]
     fn shader_defs(&self) -> Arc<FxHashSet<String>>;
 
+    /// The edition of files whose extension does not determine one.
+    #[salsa::input]
+    fn default_edition(&self) -> Edition;
+
+    /// The edition a file is parsed with: `.wesl` files are WESL, `.wgsl` files are strict WGSL.
+    #[salsa::invoke(file_edition_query)]
+    fn file_edition(
+        &self,
+        file_id: FileId,
+    ) -> Edition;
+
     /// Path to a file, relative to the root of its source root.
     /// Source root of the file.
     #[salsa::input]
@@ -66,6 +77,29 @@ pub trait SourceDatabase: FileLoader {
         id: SourceRootId,
     ) -> Arc<SourceRoot>;
 
+    /// All source roots known to the workspace.
+    #[salsa::input]
+    fn source_root_ids(&self) -> Arc<Vec<SourceRootId>>;
+
+    /// The module path declared with `#define_import_path` in a file.
+    #[salsa::invoke(define_import_path_query)]
+    fn define_import_path(
+        &self,
+        file_id: FileId,
+    ) -> Option<Arc<str>>;
+
+    /// Maps every `#define_import_path` in the workspace to the file declaring it.
+    #[salsa::invoke(import_path_index_query)]
+    fn import_path_index(&self) -> Arc<FxHashMap<Arc<str>, FileId>>;
+
+    /// The directory WESL `package::` paths are resolved against,
+    /// i.e. the deepest directory containing all shader files of the source root.
+    #[salsa::invoke(package_root_query)]
+    fn package_root(
+        &self,
+        id: SourceRootId,
+    ) -> Option<VfsPath>;
+
     #[salsa::invoke(parse_no_preprocessor_query)]
     fn parse_no_preprocessor(
         &self,
@@ -111,12 +145,69 @@ fn line_index(
     Arc::new(LineIndex::new(&text))
 }
 
+fn define_import_path_query(
+    db: &dyn SourceDatabase,
+    file_id: FileId,
+) -> Option<Arc<str>> {
+    let source = db.file_text(file_id);
+    shader_processor::get_shader_processor()
+        .define_import_path(&source)
+        .map(Arc::from)
+}
+
+fn import_path_index_query(db: &dyn SourceDatabase) -> Arc<FxHashMap<Arc<str>, FileId>> {
+    let mut index = FxHashMap::default();
+    for &source_root_id in db.source_root_ids().iter() {
+        let source_root = db.source_root(source_root_id);
+        for file_id in source_root.iter() {
+            if let Some(path) = db.define_import_path(file_id) {
+                index.entry(path).or_insert(file_id);
+            }
+        }
+    }
+    Arc::new(index)
+}
+
+fn file_edition_query(
+    db: &dyn SourceDatabase,
+    file_id: FileId,
+) -> Edition {
+    match db.file_path(file_id).name_and_extension() {
+        Some((_, Some("wesl"))) => Edition::Wesl0_0_1,
+        Some((_, Some("wgsl"))) => Edition::Wgsl,
+        _ => db.default_edition(),
+    }
+}
+
+fn is_shader_file(path: &VfsPath) -> bool {
+    matches!(path.name_and_extension(), Some((_, Some("wgsl" | "wesl"))))
+}
+
+fn package_root_query(
+    db: &dyn SourceDatabase,
+    id: SourceRootId,
+) -> Option<VfsPath> {
+    let source_root = db.source_root(id);
+    let mut paths = source_root
+        .iter()
+        .filter_map(|file_id| source_root.path_for_file(&file_id))
+        .filter(|path| is_shader_file(path));
+
+    let mut root = paths.next()?.parent()?;
+    for path in paths {
+        while !path.starts_with(&root) {
+            root = root.parent()?;
+        }
+    }
+    Some(root)
+}
+
 fn parse_no_preprocessor_query(
     db: &dyn SourceDatabase,
     file_id: FileId,
 ) -> syntax::Parse {
     let source = db.file_text(file_id);
-    syntax::parse(&source)
+    syntax::parse_with_edition(&source, db.file_edition(file_id))
 }
 
 fn parse_import_no_preprocessor_query(
@@ -125,13 +216,22 @@ fn parse_import_no_preprocessor_query(
 ) -> Result<syntax::Parse, ()> {
     let imports = db.custom_imports();
     let source = imports.get(&key).ok_or(())?;
-    Ok(syntax::parse(source))
+    Ok(syntax::parse_with_edition(source, db.default_edition()))
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct UnconfiguredCode {
     pub range: TextRange,
     pub def: String,
+    pub kind: UnconfiguredCodeKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnconfiguredCodeKind {
+    /// Code between `#ifdef def` and `#endif`
+    Ifdef,
+    /// An item or statement with a WESL `@if(def)` attribute
+    IfAttribute,
 }
 
 fn parse_with_unconfigured_query(
@@ -152,12 +252,60 @@ fn parse_with_unconfigured_query(
             unconfigured.push(UnconfiguredCode {
                 range,
                 def: def.to_string(),
+                kind: UnconfiguredCodeKind::Ifdef,
             })
         });
-    let parse = syntax::parse(&processed_source);
+    let parse = syntax::parse_with_edition(&processed_source, db.file_edition(file_id));
+    unconfigured.extend(unconfigured_if_attributes(&parse, &shader_defs));
     (parse, Arc::new(unconfigured))
 }
 
+/// Finds the outermost items, statements and struct members disabled by an `@if` attribute.
+fn unconfigured_if_attributes(
+    parse: &Parse,
+    shader_defs: &FxHashSet<String>,
+) -> Vec<UnconfiguredCode> {
+    let is_enabled = |def: &str| shader_defs.contains(def);
+    let mut unconfigured: Vec<UnconfiguredCode> = Vec::new();
+    for attribute in parse
+        .syntax()
+        .descendants()
+        .filter_map(ast::IfAttribute::cast)
+    {
+        let Some(owner) = attribute
+            .syntax()
+            .parent()
+            .and_then(|attribute_list| attribute_list.parent())
+        else {
+            continue;
+        };
+        let range = owner.text_range();
+        let is_nested = unconfigured
+            .last()
+            .is_some_and(|outer| outer.range.contains_range(range));
+        if is_nested || attribute.evaluate(&is_enabled) != Some(false) {
+            continue;
+        }
+        unconfigured.push(UnconfiguredCode {
+            range,
+            def: attribute
+                .condition()
+                .map(|condition| condition.syntax().text().to_string())
+                .unwrap_or_default(),
+            kind: UnconfiguredCodeKind::IfAttribute,
+        });
+    }
+    unconfigured
+}
+
+/// Whether `node` is enabled by its `@if` attributes, given the shader defs.
+pub fn is_configured(
+    node: &impl syntax::HasAttributes,
+    shader_defs: &FxHashSet<String>,
+) -> bool {
+    node.is_configured(&|def| shader_defs.contains(def))
+}
+
 fn parse_query(
     db: &dyn SourceDatabase,
     file_id: FileId,
@@ -176,9 +324,10 @@ fn parse_import_query(
 
     let processed_source =
         shader_processor::get_shader_processor().process(source, &shader_defs, |_, _| {});
-    Ok(syntax::parse_entrypoint(
+    Ok(syntax::parse_entrypoint_with_edition(
         &processed_source,
         parse_entrypoint,
+        db.default_edition(),
     ))
 }
 
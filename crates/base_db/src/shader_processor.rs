@@ -23,7 +23,7 @@ impl Default for ShaderProcessor {
             ifndef_regex: Regex::new(r"^\s*#\s*ifndef\s*([\w|\d|_]+)").unwrap(),
             else_regex: Regex::new(r"^\s*#\s*else").unwrap(),
             endif_regex: Regex::new(r"^\s*#\s*endif").unwrap(),
-            define_import_path_regex: Regex::new(r"^\s*#\s*define_import_path").unwrap(),
+            define_import_path_regex: Regex::new(r"^\s*#\s*define_import_path\s*([\w:]*)").unwrap(),
         }
     }
 }
@@ -38,6 +38,21 @@ impl ShaderProcessor {
         self.process_inner(shader_str, shader_defs, &mut emit_unconfigured)
     }
 
+    /// Returns the module path declared by a `#define_import_path` line, if any.
+    pub(crate) fn define_import_path<'a>(
+        &self,
+        shader_str: &'a str,
+    ) -> Option<&'a str> {
+        shader_str.lines().find_map(|line| {
+            let path = self
+                .define_import_path_regex
+                .captures(line)?
+                .get(1)?
+                .as_str();
+            (!path.is_empty()).then_some(path)
+        })
+    }
+
     fn process_inner(
         &self,
         shader_str: &str,
@@ -429,4 +444,17 @@ var<storage> cluster_offsets_and_counts: ClusterOffsetsAndCounts;
 "#,
         )
     }
+
+    #[test]
+    fn test_define_import_path() {
+        let processor = ShaderProcessor::default();
+        assert_eq!(
+            processor.define_import_path(
+                "// comment\n#define_import_path bevy_pbr::mesh_functions\nfn main() {}"
+            ),
+            Some("bevy_pbr::mesh_functions")
+        );
+        assert_eq!(processor.define_import_path("#define_import_path\n"), None);
+        assert_eq!(processor.define_import_path("fn main() {}"), None);
+    }
 }
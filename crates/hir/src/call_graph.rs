@@ -0,0 +1,456 @@
+//! The items of a module which are used by its entry points.
+//!
+//! Every item of the module and of its imports is a node, with edges to the items it
+//! refers to: the functions it calls, the globals it reads or writes, and the structs and
+//! type aliases its types are made of.
+
+use std::{collections::VecDeque, sync::Arc};
+
+use hir_def::{
+    InFile,
+    body::scope::{ExprScopes, ScopeId},
+    db::{DefinitionWithBodyId, FunctionId},
+    expression::{Callee, Expression, ExpressionId, Statement, StatementId},
+    module_data::Name,
+    resolver::{ResolveCallable, ResolveType, ResolveValue, Resolver},
+    type_ref::{ArraySize, TextureKind, TypeReference},
+};
+use hir_ty::{db::HirDatabase, infer::ResolvedCall};
+use rustc_hash::{FxHashMap, FxHashSet};
+use syntax::{ast, pointer::AstPointer};
+
+use crate::{
+    Function, GlobalConstant, GlobalVariable, Module, ModuleDef, Override, Struct, TypeAlias,
+};
+
+#[derive(Debug, Default)]
+pub struct CallGraph {
+    entry_points: Vec<Function>,
+    /// The items in the order they were discovered, starting with the items of the module.
+    items: Vec<ModuleDef>,
+    references: FxHashMap<ModuleDef, Vec<ModuleDef>>,
+    reachable: FxHashSet<ModuleDef>,
+}
+
+impl CallGraph {
+    pub fn new(
+        db: &dyn HirDatabase,
+        module: &Module,
+    ) -> Self {
+        let items = module.items(db);
+        let entry_points = items
+            .iter()
+            .filter_map(|item| match *item {
+                ModuleDef::Function(function) if function.is_entry_point(db) => Some(function),
+                _ => None,
+            })
+            .collect();
+
+        let mut references = FxHashMap::default();
+        let mut order = Vec::new();
+        let mut queue = VecDeque::from(items);
+        while let Some(item) = queue.pop_front() {
+            if references.contains_key(&item) {
+                continue;
+            }
+            let item_references = item_references(db, item);
+            queue.extend(item_references.iter().copied());
+            references.insert(item, item_references);
+            order.push(item);
+        }
+
+        let mut graph = CallGraph {
+            entry_points,
+            items: order,
+            references,
+            reachable: FxHashSet::default(),
+        };
+        graph.compute_reachability();
+        graph
+    }
+
+    /// The `@vertex`, `@fragment` and `@compute` functions of the module.
+    pub fn entry_points(&self) -> &[Function] {
+        &self.entry_points
+    }
+
+    /// The items of the module and the items of its imports which they refer to.
+    pub fn items(&self) -> &[ModuleDef] {
+        &self.items
+    }
+
+    /// Whether the item is used, directly or indirectly, by an entry point.
+    pub fn is_reachable(
+        &self,
+        item: ModuleDef,
+    ) -> bool {
+        self.reachable.contains(&item)
+    }
+
+    /// The items the item refers to, in the order they first appear.
+    pub fn references(
+        &self,
+        item: ModuleDef,
+    ) -> &[ModuleDef] {
+        self.references.get(&item).map_or(&[], Vec::as_slice)
+    }
+
+    /// The functions called by the function.
+    pub fn callees(
+        &self,
+        function: Function,
+    ) -> impl Iterator<Item = Function> + '_ {
+        self.references(ModuleDef::Function(function))
+            .iter()
+            .filter_map(|reference| match *reference {
+                ModuleDef::Function(callee) => Some(callee),
+                _ => None,
+            })
+    }
+
+    /// The functions calling the function.
+    pub fn callers(
+        &self,
+        function: Function,
+    ) -> impl Iterator<Item = Function> + '_ {
+        self.items.iter().filter_map(move |&item| match item {
+            ModuleDef::Function(caller)
+                if self
+                    .references(item)
+                    .contains(&ModuleDef::Function(function)) =>
+            {
+                Some(caller)
+            },
+            _ => None,
+        })
+    }
+
+    fn compute_reachability(&mut self) {
+        let mut stack: Vec<ModuleDef> = self
+            .entry_points
+            .iter()
+            .map(|&function| ModuleDef::Function(function))
+            .collect();
+        while let Some(item) = stack.pop() {
+            if self.reachable.insert(item) {
+                stack.extend(self.references(item).iter().copied());
+            }
+        }
+    }
+}
+
+/// A call in the body of a function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Call {
+    pub target: CallTarget,
+    /// The call expression.
+    pub expression: InFile<AstPointer<ast::Expression>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CallTarget {
+    /// A function declared in the module or in its imports.
+    Function(Function),
+    /// A builtin function such as `textureSample`.
+    Builtin(Name),
+}
+
+pub(crate) fn calls(
+    db: &dyn HirDatabase,
+    function: Function,
+) -> Vec<Call> {
+    let def = DefinitionWithBodyId::Function(function.id);
+    let file_id = def.file_id(db.upcast());
+    let (body, source_map) = db.body_with_source_map(def);
+    let infer = db.infer(def);
+
+    body.exprs
+        .iter()
+        .filter_map(|(id, expression)| {
+            let Expression::Call { callee, .. } = expression else {
+                return None;
+            };
+            let target = match (infer.call_resolution(id)?, callee) {
+                (ResolvedCall::UserFunction(id, _), _) => CallTarget::Function(Function { id }),
+                (ResolvedCall::Function(_), Callee::Name(name)) => {
+                    CallTarget::Builtin(name.clone())
+                },
+                // Type initializers are not calls of functions.
+                (ResolvedCall::Function(_) | ResolvedCall::OtherTypeInitializer(_), _) => {
+                    return None;
+                },
+            };
+            let pointer = source_map.expression_to_source(id).ok()?.clone();
+            Some(Call {
+                target,
+                expression: InFile::new(file_id, pointer),
+            })
+        })
+        .collect()
+}
+
+/// Resolves the names used in a body, including the locals in scope where they are used.
+pub(crate) struct BodyResolver {
+    /// Resolves the names of the module scope of the body.
+    resolver: Resolver,
+    scopes: Option<(FunctionId, Arc<ExprScopes>)>,
+}
+
+impl BodyResolver {
+    pub(crate) fn new(
+        db: &dyn HirDatabase,
+        def: DefinitionWithBodyId,
+    ) -> Self {
+        let scopes = match def {
+            DefinitionWithBodyId::Function(function) => Some((function, db.expression_scopes(def))),
+            DefinitionWithBodyId::GlobalVariable(_)
+            | DefinitionWithBodyId::GlobalConstant(_)
+            | DefinitionWithBodyId::Override(_) => None,
+        };
+        Self {
+            resolver: def.resolver(db.upcast()),
+            scopes,
+        }
+    }
+
+    /// The resolver for the names used by the expression.
+    pub(crate) fn for_expression(
+        &self,
+        expression: ExpressionId,
+    ) -> Resolver {
+        self.with_scope(|scopes| scopes.scope_for_expression(expression))
+    }
+
+    /// The resolver for the names used by the types of the statement.
+    pub(crate) fn for_statement(
+        &self,
+        statement: StatementId,
+    ) -> Resolver {
+        self.with_scope(|scopes| scopes.scope_for_statement(statement))
+    }
+
+    fn with_scope(
+        &self,
+        scope: impl FnOnce(&ExprScopes) -> Option<ScopeId>,
+    ) -> Resolver {
+        let resolver = self.resolver.clone();
+        match &self.scopes {
+            Some((function, scopes)) => match scope(scopes) {
+                Some(scope_id) => {
+                    resolver.push_expression_scope(*function, Arc::clone(scopes), scope_id)
+                },
+                None => resolver,
+            },
+            None => resolver,
+        }
+    }
+}
+
+fn item_references(
+    db: &dyn HirDatabase,
+    item: ModuleDef,
+) -> Vec<ModuleDef> {
+    let mut collector = ReferenceCollector {
+        db,
+        resolver: match item.as_def_with_body_id() {
+            Some(def) => def.resolver(db.upcast()),
+            None => {
+                let file_id = item.file_id(db.upcast());
+                Resolver::default().push_module_scope(db.upcast(), file_id, db.module_info(file_id))
+            },
+        },
+        references: Vec::new(),
+    };
+
+    match item {
+        ModuleDef::Function(function) => {
+            let data = db.fn_data(function.id);
+            for (r#type, _) in &data.parameters {
+                collector.type_reference(&db.lookup_intern_type_ref(*r#type));
+            }
+            if let Some(return_type) = data.return_type {
+                collector.type_reference(&db.lookup_intern_type_ref(return_type));
+            }
+        },
+        ModuleDef::GlobalVariable(var) => {
+            if let Some(r#type) = db.global_var_data(var.id).r#type {
+                collector.type_reference(&db.lookup_intern_type_ref(r#type));
+            }
+        },
+        ModuleDef::GlobalConstant(constant) => {
+            if let Some(r#type) = db.global_constant_data(constant.id).r#type {
+                collector.type_reference(&db.lookup_intern_type_ref(r#type));
+            }
+        },
+        ModuleDef::Override(override_declaration) => {
+            if let Some(r#type) = db.override_data(override_declaration.id).r#type {
+                collector.type_reference(&db.lookup_intern_type_ref(r#type));
+            }
+        },
+        ModuleDef::Struct(r#struct) => {
+            let data = db.struct_data(r#struct.id);
+            for (_, field) in data.fields.iter() {
+                collector.type_reference(&db.lookup_intern_type_ref(field.r#type));
+            }
+        },
+        ModuleDef::TypeAlias(type_alias) => {
+            let data = db.type_alias_data(type_alias.id);
+            collector.type_reference(&db.lookup_intern_type_ref(data.r#type));
+        },
+    }
+
+    if let Some(def) = item.as_def_with_body_id() {
+        collector.body(def);
+    }
+
+    collector.references
+}
+
+struct ReferenceCollector<'db> {
+    db: &'db dyn HirDatabase,
+    /// Resolves the names of the module scope of the item.
+    resolver: Resolver,
+    references: Vec<ModuleDef>,
+}
+
+impl ReferenceCollector<'_> {
+    fn add(
+        &mut self,
+        item: ModuleDef,
+    ) {
+        if !self.references.contains(&item) {
+            self.references.push(item);
+        }
+    }
+
+    fn body(
+        &mut self,
+        def: DefinitionWithBodyId,
+    ) {
+        let db = self.db;
+        let body = db.body(def);
+        let infer = db.infer(def);
+        let body_resolver = BodyResolver::new(db, def);
+        for (id, expression) in body.exprs.iter() {
+            match expression {
+                Expression::Path(name) => {
+                    match body_resolver.for_expression(id).resolve_value(name) {
+                        Some(ResolveValue::GlobalVariable(loc)) => {
+                            let id = db.intern_global_variable(loc);
+                            self.add(ModuleDef::GlobalVariable(GlobalVariable { id }));
+                        },
+                        Some(ResolveValue::GlobalConstant(loc)) => {
+                            let id = db.intern_global_constant(loc);
+                            self.add(ModuleDef::GlobalConstant(GlobalConstant { id }));
+                        },
+                        Some(ResolveValue::Override(loc)) => {
+                            let id = db.intern_override(loc);
+                            self.add(ModuleDef::Override(Override { id }));
+                        },
+                        Some(ResolveValue::Local(_)) | None => {},
+                    }
+                },
+                Expression::Call { callee, .. } => {
+                    if let Some(ResolvedCall::UserFunction(id, _)) = infer.call_resolution(id) {
+                        self.add(ModuleDef::Function(Function { id }));
+                    }
+                    match callee {
+                        Callee::Name(name) => match self.resolver.resolve_callable(name) {
+                            Some(ResolveCallable::Struct(loc)) => {
+                                let id = db.intern_struct(loc);
+                                self.add(ModuleDef::Struct(Struct { id }));
+                            },
+                            Some(ResolveCallable::TypeAlias(loc)) => {
+                                let id = db.intern_type_alias(loc);
+                                self.add(ModuleDef::TypeAlias(TypeAlias { id }));
+                            },
+                            Some(
+                                ResolveCallable::Function(_)
+                                | ResolveCallable::PredeclaredTypeAlias(_),
+                            )
+                            | None => {},
+                        },
+                        Callee::Type(r#type) => {
+                            self.type_reference(&db.lookup_intern_type_ref(*r#type));
+                        },
+                        Callee::InferredComponentMatrix { .. }
+                        | Callee::InferredComponentVec(_)
+                        | Callee::InferredComponentArray => {},
+                    }
+                },
+                Expression::Bitcast { r#type, .. } => {
+                    self.type_reference(&db.lookup_intern_type_ref(*r#type));
+                },
+                Expression::Missing
+                | Expression::BinaryOperation { .. }
+                | Expression::UnaryOperator { .. }
+                | Expression::Field { .. }
+                | Expression::Index { .. }
+                | Expression::Literal(_) => {},
+            }
+        }
+
+        for (_, statement) in body.statements.iter() {
+            match statement {
+                Statement::LetStatement {
+                    type_ref: Some(r#type),
+                    ..
+                }
+                | Statement::ConstStatement {
+                    type_ref: Some(r#type),
+                    ..
+                }
+                | Statement::VariableStatement {
+                    type_ref: Some(r#type),
+                    ..
+                } => self.type_reference(&db.lookup_intern_type_ref(*r#type)),
+                _ => {},
+            }
+        }
+    }
+
+    fn type_reference(
+        &mut self,
+        r#type: &TypeReference,
+    ) {
+        match r#type {
+            TypeReference::Path(name) => match self.resolver.resolve_type(name) {
+                Some(ResolveType::Struct(loc)) => {
+                    let id = self.db.intern_struct(loc);
+                    self.add(ModuleDef::Struct(Struct { id }));
+                },
+                Some(ResolveType::TypeAlias(loc)) => {
+                    let id = self.db.intern_type_alias(loc);
+                    self.add(ModuleDef::TypeAlias(TypeAlias { id }));
+                },
+                Some(ResolveType::PredeclaredTypeAlias(_)) | None => {},
+            },
+            TypeReference::Vec(vec) => self.type_reference(&vec.inner),
+            TypeReference::Matrix(matrix) => self.type_reference(&matrix.inner),
+            TypeReference::Atomic(atomic) => self.type_reference(&atomic.inner),
+            TypeReference::Pointer(pointer) => self.type_reference(&pointer.inner),
+            TypeReference::Texture(texture) => {
+                if let TextureKind::Sampled(inner) = &texture.kind {
+                    self.type_reference(inner);
+                }
+            },
+            TypeReference::Array(array) => {
+                self.type_reference(&array.inner);
+                if let ArraySize::Path(name) = &array.size {
+                    match self.resolver.resolve_value(name) {
+                        Some(ResolveValue::GlobalConstant(loc)) => {
+                            let id = self.db.intern_global_constant(loc);
+                            self.add(ModuleDef::GlobalConstant(GlobalConstant { id }));
+                        },
+                        Some(ResolveValue::Override(loc)) => {
+                            let id = self.db.intern_override(loc);
+                            self.add(ModuleDef::Override(Override { id }));
+                        },
+                        _ => {},
+                    }
+                }
+            },
+            TypeReference::Error | TypeReference::Scalar(_) | TypeReference::Sampler(_) => {},
+        }
+    }
+}
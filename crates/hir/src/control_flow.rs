@@ -0,0 +1,333 @@
+//! A control flow graph over the statements of a [`Body`].
+//!
+//! Every statement is a node, with edges to the statements which can be executed
+//! right after it. Two extra nodes stand for leaving the body: through `return`
+//! (or `discard`), and by falling off the end.
+
+use either::Either;
+use hir_def::{
+    body::Body,
+    expression::{Statement, StatementId},
+};
+use la_arena::ArenaMap;
+
+type NodeId = usize;
+
+/// Left through `return` or `discard`.
+const RETURN: NodeId = 0;
+/// Falling off the end of the body.
+const END: NodeId = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ControlFlowError {
+    /// A `break` outside of a loop or switch.
+    BreakOutsideLoop(StatementId),
+    /// A `continue` outside of a loop.
+    ContinueOutsideLoop(StatementId),
+    /// A `return` inside a `continuing` block.
+    ReturnInContinuing(StatementId),
+    /// A `continuing` which is not the last statement of a loop.
+    MisplacedContinuing(StatementId),
+}
+
+#[derive(Debug)]
+pub struct ControlFlowGraph {
+    successors: Vec<Vec<NodeId>>,
+    statement_nodes: ArenaMap<StatementId, NodeId>,
+    reachable: Vec<bool>,
+    errors: Vec<ControlFlowError>,
+}
+
+/// Where `break` and `continue` jump to.
+#[derive(Clone, Copy, Default)]
+struct Targets {
+    break_target: Option<NodeId>,
+    continue_target: Option<NodeId>,
+    in_continuing: bool,
+}
+
+impl ControlFlowGraph {
+    pub fn new(body: &Body) -> Self {
+        let mut graph = ControlFlowGraph {
+            successors: vec![Vec::new(), Vec::new()],
+            statement_nodes: ArenaMap::default(),
+            reachable: Vec::new(),
+            errors: Vec::new(),
+        };
+        let entry = match body.root {
+            Some(Either::Left(root)) => graph.lower(body, root, END, Targets::default()),
+            Some(Either::Right(_)) | None => END,
+        };
+        graph.compute_reachability(entry);
+        graph
+    }
+
+    /// Whether execution can reach the start of the statement.
+    pub fn is_reachable(
+        &self,
+        statement: StatementId,
+    ) -> bool {
+        self.statement_nodes
+            .get(statement)
+            .is_none_or(|&node| self.reachable[node])
+    }
+
+    /// Whether execution can reach the end of the body without returning.
+    pub fn falls_through(&self) -> bool {
+        self.reachable[END]
+    }
+
+    /// Misplaced `break`, `continue`, `return` and `continuing` statements.
+    pub fn errors(&self) -> &[ControlFlowError] {
+        &self.errors
+    }
+
+    fn add_node(&mut self) -> NodeId {
+        self.successors.push(Vec::new());
+        self.successors.len() - 1
+    }
+
+    fn add_statement_node(
+        &mut self,
+        statement: StatementId,
+    ) -> NodeId {
+        let node = self.add_node();
+        self.statement_nodes.insert(statement, node);
+        node
+    }
+
+    fn add_edge(
+        &mut self,
+        from: NodeId,
+        to: NodeId,
+    ) {
+        self.successors[from].push(to);
+    }
+
+    /// Adds the nodes of the statement, continuing with `next` when it completes
+    /// normally. Returns the node of the statement.
+    fn lower(
+        &mut self,
+        body: &Body,
+        statement: StatementId,
+        next: NodeId,
+        targets: Targets,
+    ) -> NodeId {
+        let node = self.add_statement_node(statement);
+        match &body.statements[statement] {
+            Statement::Compound { statements } => {
+                let first = self.lower_block(body, statements, next, targets);
+                self.add_edge(node, first);
+            },
+            Statement::Return { .. } => {
+                if targets.in_continuing {
+                    self.errors
+                        .push(ControlFlowError::ReturnInContinuing(statement));
+                }
+                self.add_edge(node, RETURN);
+            },
+            Statement::Discard => self.add_edge(node, RETURN),
+            Statement::Break => match targets.break_target {
+                Some(target) => self.add_edge(node, target),
+                None => {
+                    self.errors
+                        .push(ControlFlowError::BreakOutsideLoop(statement));
+                    self.add_edge(node, next);
+                },
+            },
+            Statement::Continue => match targets.continue_target {
+                Some(target) => self.add_edge(node, target),
+                None => {
+                    self.errors
+                        .push(ControlFlowError::ContinueOutsideLoop(statement));
+                    self.add_edge(node, next);
+                },
+            },
+            Statement::Continuing { block } => {
+                // A `continuing` in the right place is lowered with its loop.
+                self.errors
+                    .push(ControlFlowError::MisplacedContinuing(statement));
+                let targets = Targets {
+                    in_continuing: true,
+                    ..targets
+                };
+                let block = self.lower(body, *block, next, targets);
+                self.add_edge(node, block);
+            },
+            Statement::If {
+                block,
+                else_if_blocks,
+                else_block,
+                ..
+            } => {
+                let block = self.lower(body, *block, next, targets);
+                self.add_edge(node, block);
+                for else_if_block in else_if_blocks {
+                    let else_if_block = self.lower(body, *else_if_block, next, targets);
+                    self.add_edge(node, else_if_block);
+                }
+                match else_block {
+                    Some(else_block) => {
+                        let else_block = self.lower(body, *else_block, next, targets);
+                        self.add_edge(node, else_block);
+                    },
+                    None => self.add_edge(node, next),
+                }
+            },
+            Statement::Switch {
+                case_blocks,
+                default_block,
+                ..
+            } => {
+                let targets = Targets {
+                    break_target: Some(next),
+                    ..targets
+                };
+                for (_, block) in case_blocks {
+                    let block = self.lower(body, *block, next, targets);
+                    self.add_edge(node, block);
+                }
+                match default_block {
+                    Some(default_block) => {
+                        let default_block = self.lower(body, *default_block, next, targets);
+                        self.add_edge(node, default_block);
+                    },
+                    None => self.add_edge(node, next),
+                }
+            },
+            Statement::Loop { body: loop_body } => {
+                self.lower_loop(body, node, *loop_body, next, targets);
+            },
+            Statement::While { block, .. } => {
+                let targets = Targets {
+                    break_target: Some(next),
+                    continue_target: Some(node),
+                    ..targets
+                };
+                let block = self.lower(body, *block, node, targets);
+                self.add_edge(node, block);
+                self.add_edge(node, next);
+            },
+            Statement::For {
+                initializer,
+                condition,
+                continuing_part,
+                block,
+            } => {
+                let head = self.add_node();
+                let continue_target = match continuing_part {
+                    Some(continuing_part) => self.lower(body, *continuing_part, head, targets),
+                    None => head,
+                };
+                let loop_targets = Targets {
+                    break_target: Some(next),
+                    continue_target: Some(continue_target),
+                    ..targets
+                };
+                let block = self.lower(body, *block, continue_target, loop_targets);
+                self.add_edge(head, block);
+                // Without a condition, only `break` leaves the loop.
+                if condition.is_some() {
+                    self.add_edge(head, next);
+                }
+                match initializer {
+                    Some(initializer) => {
+                        let initializer = self.lower(body, *initializer, head, targets);
+                        self.add_edge(node, initializer);
+                    },
+                    None => self.add_edge(node, head),
+                }
+            },
+            Statement::Missing
+            | Statement::LetStatement { .. }
+            | Statement::ConstStatement { .. }
+            | Statement::VariableStatement { .. }
+            | Statement::Assignment { .. }
+            | Statement::CompoundAssignment { .. }
+            | Statement::IncrDecr { .. }
+            | Statement::Expression { .. } => self.add_edge(node, next),
+        }
+        node
+    }
+
+    /// Lowers the statements in order. Returns the node of the first one.
+    fn lower_block(
+        &mut self,
+        body: &Body,
+        statements: &[StatementId],
+        next: NodeId,
+        targets: Targets,
+    ) -> NodeId {
+        statements.iter().rev().fold(next, |next, &statement| {
+            self.lower(body, statement, next, targets)
+        })
+    }
+
+    /// Lowers `loop { statements continuing { ... } }`, where `head` is the node of
+    /// the loop statement.
+    fn lower_loop(
+        &mut self,
+        body: &Body,
+        head: NodeId,
+        loop_body: StatementId,
+        next: NodeId,
+        targets: Targets,
+    ) {
+        let loop_targets = Targets {
+            break_target: Some(next),
+            continue_target: Some(head),
+            ..targets
+        };
+        let Statement::Compound { statements } = &body.statements[loop_body] else {
+            let loop_body = self.lower(body, loop_body, head, loop_targets);
+            self.add_edge(head, loop_body);
+            return;
+        };
+        let body_node = self.add_statement_node(loop_body);
+        self.add_edge(head, body_node);
+
+        let (statements, continue_target) = match statements.split_last() {
+            Some((&last, rest)) => match body.statements[last] {
+                Statement::Continuing { block } => {
+                    let continuing = self.add_statement_node(last);
+                    let continuing_targets = Targets {
+                        in_continuing: true,
+                        ..loop_targets
+                    };
+                    let block = self.lower(body, block, head, continuing_targets);
+                    self.add_edge(continuing, block);
+                    (rest, continuing)
+                },
+                _ => (&statements[..], head),
+            },
+            None => (&statements[..], head),
+        };
+        let targets = Targets {
+            continue_target: Some(continue_target),
+            ..loop_targets
+        };
+        let first = self.lower_block(body, statements, continue_target, targets);
+        self.add_edge(body_node, first);
+    }
+
+    fn compute_reachability(
+        &mut self,
+        entry: NodeId,
+    ) {
+        let mut reachable = vec![false; self.successors.len()];
+        let mut stack = vec![entry];
+        while let Some(node) = stack.pop() {
+            if reachable[node] {
+                continue;
+            }
+            reachable[node] = true;
+            stack.extend(
+                self.successors[node]
+                    .iter()
+                    .copied()
+                    .filter(|&successor| !reachable[successor]),
+            );
+        }
+        self.reachable = reachable;
+    }
+}
@@ -1,11 +1,22 @@
 use hir_def::{
-    HirFileId,
-    module_data::Name,
-    resolver::{ResolveCallable, ResolveType},
+    HirFileId, InFile,
+    data::FieldId,
+    db::{DefinitionWithBodyId, Location},
+    module_data::{self, ModuleItem, Name},
+    resolver::{ResolveCallable, ResolveType, ResolveValue, Resolver},
+};
+use hir_ty::{
+    db::HirDatabase,
+    ty::{TyKind, Type},
+};
+use syntax::{
+    AstNode, HasName, SyntaxKind, SyntaxNode, SyntaxToken, ast, match_ast, pointer::AstPointer,
 };
-use syntax::{AstNode, SyntaxNode, SyntaxToken, ast, match_ast};
 
-use crate::{Field, Function, Local, ModuleDef, Semantics, Struct, TypeAlias};
+use crate::{
+    Field, Function, GlobalConstant, GlobalVariable, Local, ModuleDef, Override, Semantics, Struct,
+    TypeAlias, module_item_to_def,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Definition {
@@ -43,6 +54,192 @@ impl Definition {
             }
         }
     }
+
+    /// All definitions the name at the token may refer to, starting with the one it resolves
+    /// to. A name is ambiguous when several of the imported modules declare an item with it.
+    pub fn all_from_token(
+        sema: &Semantics<'_>,
+        file_id: HirFileId,
+        token: &SyntaxToken,
+    ) -> Vec<Definition> {
+        let Some(definition) = Self::from_token(sema, file_id, token) else {
+            return Vec::new();
+        };
+        let name_ref = token.parent().and_then(ast::NameReference::cast);
+        let parent = name_ref
+            .as_ref()
+            .and_then(|name_ref| name_ref.syntax().parent());
+        let (Some(name_ref), Some(parent)) = (name_ref, parent) else {
+            return vec![definition];
+        };
+        if matches!(definition, Definition::Local(_) | Definition::Field(_)) {
+            return vec![definition];
+        }
+
+        let kind = parent.kind();
+        let in_namespace = |item: &ModuleItem| match kind {
+            SyntaxKind::PathExpression => matches!(
+                item,
+                ModuleItem::GlobalVariable(_)
+                    | ModuleItem::GlobalConstant(_)
+                    | ModuleItem::Override(_)
+            ),
+            SyntaxKind::FunctionCall => matches!(
+                item,
+                ModuleItem::Function(_) | ModuleItem::Struct(_) | ModuleItem::TypeAlias(_)
+            ),
+            SyntaxKind::PathType => {
+                matches!(item, ModuleItem::Struct(_) | ModuleItem::TypeAlias(_))
+            },
+            _ => false,
+        };
+
+        let mut definitions = vec![definition];
+        let resolver = sema.resolver(file_id, &parent);
+        for (file_id, item) in resolver.resolve_module_items(&Name::from(name_ref)) {
+            if !in_namespace(&item) {
+                continue;
+            }
+            for def in module_item_to_def(sema.db, file_id, &item) {
+                let definition = match def {
+                    ModuleDef::Struct(r#struct) => Definition::Struct(r#struct),
+                    ModuleDef::TypeAlias(type_alias) => Definition::TypeAlias(type_alias),
+                    def => Definition::ModuleDef(def),
+                };
+                if !definitions.contains(&definition) {
+                    definitions.push(definition);
+                }
+            }
+        }
+        definitions
+    }
+
+    /// The struct the type is made of, looking through references, pointers and arrays.
+    pub fn from_type(
+        db: &dyn HirDatabase,
+        r#type: Type,
+    ) -> Option<Definition> {
+        match r#type.kind(db) {
+            TyKind::Struct(id) => Some(Definition::Struct(Struct { id })),
+            TyKind::Reference(reference) => Self::from_type(db, reference.inner),
+            TyKind::Pointer(pointer) => Self::from_type(db, pointer.inner),
+            TyKind::Array(array) => Self::from_type(db, array.inner),
+            _ => None,
+        }
+    }
+
+    /// The type of the local, global variable, constant, override or field.
+    pub fn value_type(
+        &self,
+        db: &dyn HirDatabase,
+    ) -> Option<Type> {
+        match self {
+            Definition::Local(local) => {
+                let infer = db.infer(DefinitionWithBodyId::Function(local.parent));
+                infer.type_of_binding.get(local.binding).copied()
+            },
+            Definition::Field(field) => {
+                let field_types = db.field_types(field.id.r#struct);
+                field_types.get(field.id.field).copied()
+            },
+            Definition::ModuleDef(
+                def @ (ModuleDef::GlobalVariable(_)
+                | ModuleDef::GlobalConstant(_)
+                | ModuleDef::Override(_)),
+            ) => db.infer(def.as_def_with_body_id()?).return_type,
+            Definition::ModuleDef(_) | Definition::Struct(_) | Definition::TypeAlias(_) => None,
+        }
+    }
+
+    /// The definition declared by the name.
+    pub fn from_name(
+        sema: &Semantics<'_>,
+        file_id: HirFileId,
+        name: &ast::Name,
+    ) -> Option<Definition> {
+        let parent = name.syntax().parent()?;
+        match_ast! {
+            match parent {
+                ast::Function(function) => {
+                    let function = sema.function(InFile::new(file_id, function))?;
+                    Some(Definition::ModuleDef(ModuleDef::Function(function)))
+                },
+                ast::StructDeclaration(declaration) => {
+                    let id = module_data::find_item(sema.db.upcast(), file_id, &declaration)?;
+                    let id = sema.db.intern_struct(Location::new(file_id, id));
+                    Some(Definition::Struct(Struct { id }))
+                },
+                ast::TypeAliasDeclaration(declaration) => {
+                    let id = module_data::find_item(sema.db.upcast(), file_id, &declaration)?;
+                    let id = sema.db.intern_type_alias(Location::new(file_id, id));
+                    Some(Definition::TypeAlias(TypeAlias { id }))
+                },
+                ast::Binding(binding) => binding_definition(sema, file_id, &binding),
+                _ => None,
+            }
+        }
+    }
+}
+
+fn binding_definition(
+    sema: &Semantics<'_>,
+    file_id: HirFileId,
+    binding: &ast::Binding,
+) -> Option<Definition> {
+    let parent = binding.syntax().parent()?;
+    match_ast! {
+        match parent {
+            ast::GlobalVariableDeclaration(declaration) => {
+                let id = sema.global_variable_to_def(InFile::new(file_id, declaration))?;
+                Some(Definition::ModuleDef(ModuleDef::GlobalVariable(GlobalVariable { id })))
+            },
+            ast::GlobalConstantDeclaration(declaration) => {
+                let id = sema.global_constant_to_def(InFile::new(file_id, declaration))?;
+                Some(Definition::ModuleDef(ModuleDef::GlobalConstant(GlobalConstant { id })))
+            },
+            ast::OverrideDeclaration(declaration) => {
+                let id = module_data::find_item(sema.db.upcast(), file_id, &declaration)?;
+                let id = sema.db.intern_override(Location::new(file_id, id));
+                Some(Definition::ModuleDef(ModuleDef::Override(Override { id })))
+            },
+            _ => {
+                if let Some(field) = parent.parent().and_then(ast::StructDeclarationField::cast) {
+                    return field_definition(sema, file_id, &field, binding);
+                }
+                let DefinitionWithBodyId::Function(function) =
+                    sema.find_container(file_id, binding.syntax())?
+                else {
+                    return None;
+                };
+                let (_, source_map) =
+                    sema.db.body_with_source_map(DefinitionWithBodyId::Function(function));
+                let binding = source_map.lookup_binding(&AstPointer::new(binding))?;
+                Some(Definition::Local(Local {
+                    parent: function,
+                    binding,
+                }))
+            },
+        }
+    }
+}
+
+fn field_definition(
+    sema: &Semantics<'_>,
+    file_id: HirFileId,
+    field: &ast::StructDeclarationField,
+    binding: &ast::Binding,
+) -> Option<Definition> {
+    let declaration = field
+        .syntax()
+        .ancestors()
+        .find_map(ast::StructDeclaration::cast)?;
+    let id = module_data::find_item(sema.db.upcast(), file_id, &declaration)?;
+    let r#struct = sema.db.intern_struct(Location::new(file_id, id));
+    let name = Name::from(binding.name()?);
+    let field = sema.db.struct_data(r#struct).field(&name)?;
+    Some(Definition::Field(Field {
+        id: FieldId { r#struct, field },
+    }))
 }
 
 fn resolve_name_ref(
@@ -81,6 +278,13 @@ fn resolve_name_ref(
             },
             ResolveCallable::PredeclaredTypeAlias(_) => None,
         }
+    } else if let Some(item) = ast::ImportItem::cast(parent.clone()) {
+        // `#import a::b::{x as y}` makes the item visible as `y`
+        let name = item
+            .alias()
+            .map_or_else(|| Name::from(name_ref.clone()), Name::from);
+        let resolver = sema.resolver(file_id, item.syntax());
+        resolve_module_item(sema, &resolver, &name)
     } else if let Some(r#type) = ast::PathType::cast(parent) {
         let resolver = sema.resolver(file_id, r#type.syntax());
 
@@ -102,3 +306,47 @@ fn resolve_name_ref(
         None
     }
 }
+
+fn resolve_module_item(
+    sema: &Semantics<'_>,
+    resolver: &Resolver,
+    name: &Name,
+) -> Option<Definition> {
+    if let Some(callable) = resolver.resolve_callable(name) {
+        return match callable {
+            ResolveCallable::Struct(loc) => {
+                let id = sema.db.intern_struct(loc);
+                Some(Definition::Struct(Struct { id }))
+            },
+            ResolveCallable::TypeAlias(loc) => {
+                let id = sema.db.intern_type_alias(loc);
+                Some(Definition::TypeAlias(TypeAlias { id }))
+            },
+            ResolveCallable::Function(function) => {
+                let id = sema.db.intern_function(function);
+                Some(Definition::ModuleDef(ModuleDef::Function(Function { id })))
+            },
+            ResolveCallable::PredeclaredTypeAlias(_) => None,
+        };
+    }
+
+    match resolver.resolve_value(name)? {
+        ResolveValue::GlobalVariable(loc) => {
+            let id = sema.db.intern_global_variable(loc);
+            Some(Definition::ModuleDef(ModuleDef::GlobalVariable(
+                GlobalVariable { id },
+            )))
+        },
+        ResolveValue::GlobalConstant(loc) => {
+            let id = sema.db.intern_global_constant(loc);
+            Some(Definition::ModuleDef(ModuleDef::GlobalConstant(
+                GlobalConstant { id },
+            )))
+        },
+        ResolveValue::Override(loc) => {
+            let id = sema.db.intern_override(loc);
+            Some(Definition::ModuleDef(ModuleDef::Override(Override { id })))
+        },
+        ResolveValue::Local(_) => None,
+    }
+}
@@ -0,0 +1,66 @@
+use hir_def::{
+    db::DefinitionWithBodyId,
+    expression::{Statement, StatementId},
+};
+use hir_ty::db::HirDatabase;
+
+use crate::control_flow::{ControlFlowError, ControlFlowGraph};
+
+#[derive(Debug)]
+pub enum ControlFlowDiagnostic {
+    /// A function with a return type can reach its end.
+    MissingReturn,
+    /// The statements from `first` to `last` of a block are never executed.
+    UnreachableCode {
+        first: StatementId,
+        last: StatementId,
+    },
+    Misplaced(ControlFlowError),
+}
+
+pub fn collect(
+    db: &dyn HirDatabase,
+    def: DefinitionWithBodyId,
+    mut f: impl FnMut(ControlFlowDiagnostic),
+) {
+    let DefinitionWithBodyId::Function(function) = def else {
+        return;
+    };
+    let body = db.body(def);
+    if body.root.is_none() {
+        return;
+    }
+    let graph = ControlFlowGraph::new(&body);
+
+    for &error in graph.errors() {
+        f(ControlFlowDiagnostic::Misplaced(error));
+    }
+
+    if db.fn_data(function).return_type.is_some() && graph.falls_through() {
+        f(ControlFlowDiagnostic::MissingReturn);
+    }
+
+    for (id, statement) in body.statements.iter() {
+        let Statement::Compound { statements } = statement else {
+            continue;
+        };
+        // Only report the outermost unreachable block.
+        if !graph.is_reachable(id) {
+            continue;
+        }
+        let Some(start) = statements
+            .iter()
+            .position(|&statement| !graph.is_reachable(statement))
+        else {
+            continue;
+        };
+        let first = statements[start];
+        let last = statements[start..]
+            .iter()
+            .copied()
+            .take_while(|&statement| !graph.is_reachable(statement))
+            .last()
+            .unwrap_or(first);
+        f(ControlFlowDiagnostic::UnreachableCode { first, last });
+    }
+}
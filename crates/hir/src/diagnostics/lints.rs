@@ -0,0 +1,407 @@
+//! Opt-in checks for code which is valid, but likely a mistake.
+//!
+//! Unlike the other diagnostics, a lint only runs when a level is configured for its code in
+//! [`DiagnosticsConfig::severity`], for example `{ "float-equality": "warning" }`.
+
+use hir_def::{
+    InFile,
+    body::{BindingId, Body},
+    db::DefinitionWithBodyId,
+    expression::{
+        ArithmeticOperation, BinaryOperation, Callee, ComparisonOperation, Expression,
+        ExpressionId, Literal, Statement, UnaryOperator, parse_literal,
+    },
+    resolver::ResolveValue,
+    type_ref::StorageClass,
+};
+use hir_ty::{
+    builtins::Builtin,
+    db::HirDatabase,
+    infer::{InferenceResult, ResolvedCall},
+    ty::{ScalarType, TyKind, Type},
+};
+use rustc_hash::FxHashSet;
+use syntax::{
+    HasAttributes,
+    ast::{self, IdentOrLiteral},
+    pointer::AstPointer,
+};
+
+use super::{DiagnosticLevel, DiagnosticsConfig};
+use crate::{Function, GlobalVariable, HasSource, ModuleDef, call_graph::BodyResolver};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Lint {
+    FloatEquality,
+    IntegerDivisionInFloat,
+    ShadowedBuiltin,
+    SelectArgumentOrder,
+    WorkgroupSizeNotPowerOfTwo,
+    PrivateNeverWritten,
+    RedundantConversion,
+}
+
+impl Lint {
+    pub const ALL: [Lint; 7] = [
+        Lint::FloatEquality,
+        Lint::IntegerDivisionInFloat,
+        Lint::ShadowedBuiltin,
+        Lint::SelectArgumentOrder,
+        Lint::WorkgroupSizeNotPowerOfTwo,
+        Lint::PrivateNeverWritten,
+        Lint::RedundantConversion,
+    ];
+
+    /// The diagnostic code of the lint, which is also the key of its level in the config.
+    pub const fn code(self) -> &'static str {
+        match self {
+            Lint::FloatEquality => "float-equality",
+            Lint::IntegerDivisionInFloat => "integer-division-in-float",
+            Lint::ShadowedBuiltin => "shadowed-builtin",
+            Lint::SelectArgumentOrder => "select-argument-order",
+            Lint::WorkgroupSizeNotPowerOfTwo => "workgroup-size-not-power-of-two",
+            Lint::PrivateNeverWritten => "private-never-written",
+            Lint::RedundantConversion => "redundant-conversion",
+        }
+    }
+
+    /// The configured level of the lint, which is off unless configured.
+    pub fn level(
+        self,
+        config: &DiagnosticsConfig,
+    ) -> DiagnosticLevel {
+        config
+            .severity
+            .get(self.code())
+            .copied()
+            .unwrap_or(DiagnosticLevel::Off)
+    }
+
+    pub fn is_enabled(
+        self,
+        config: &DiagnosticsConfig,
+    ) -> bool {
+        self.level(config) != DiagnosticLevel::Off
+    }
+}
+
+#[derive(Debug)]
+pub enum LintDiagnostic {
+    FloatEquality {
+        expression: ExpressionId,
+        negated: bool,
+    },
+    IntegerDivisionInFloat {
+        division: ExpressionId,
+        r#type: Type,
+    },
+    ShadowedBuiltin(BindingId),
+    SelectArgumentOrder(ExpressionId),
+    RedundantConversion {
+        expression: ExpressionId,
+        r#type: Type,
+    },
+}
+
+impl LintDiagnostic {
+    pub fn lint(&self) -> Lint {
+        match self {
+            LintDiagnostic::FloatEquality { .. } => Lint::FloatEquality,
+            LintDiagnostic::IntegerDivisionInFloat { .. } => Lint::IntegerDivisionInFloat,
+            LintDiagnostic::ShadowedBuiltin(_) => Lint::ShadowedBuiltin,
+            LintDiagnostic::SelectArgumentOrder(_) => Lint::SelectArgumentOrder,
+            LintDiagnostic::RedundantConversion { .. } => Lint::RedundantConversion,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ItemLintDiagnostic {
+    ShadowedBuiltin(ModuleDef),
+    WorkgroupSizeNotPowerOfTwo {
+        attribute: InFile<AstPointer<ast::Attribute>>,
+        invocations: u64,
+    },
+    PrivateNeverWritten(GlobalVariable),
+}
+
+impl ItemLintDiagnostic {
+    pub fn lint(&self) -> Lint {
+        match self {
+            ItemLintDiagnostic::ShadowedBuiltin(_) => Lint::ShadowedBuiltin,
+            ItemLintDiagnostic::WorkgroupSizeNotPowerOfTwo { .. } => {
+                Lint::WorkgroupSizeNotPowerOfTwo
+            },
+            ItemLintDiagnostic::PrivateNeverWritten(_) => Lint::PrivateNeverWritten,
+        }
+    }
+}
+
+/// Runs the enabled lints which look at a single body.
+pub fn collect(
+    db: &dyn HirDatabase,
+    def: DefinitionWithBodyId,
+    is_enabled: impl Fn(Lint) -> bool,
+    mut f: impl FnMut(LintDiagnostic),
+) {
+    let body = db.body(def);
+    let infer = db.infer(def);
+    let type_of = |expression: ExpressionId| {
+        infer
+            .type_of_expression
+            .get(expression)
+            .map(|r#type| r#type.unref(db))
+    };
+
+    for (id, expression) in body.exprs.iter() {
+        match expression {
+            Expression::BinaryOperation {
+                left_side,
+                operation: BinaryOperation::Comparison(ComparisonOperation::Equality { negated }),
+                ..
+            } if is_enabled(Lint::FloatEquality)
+                && type_of(*left_side).is_some_and(|r#type| is_float(db, r#type)) =>
+            {
+                f(LintDiagnostic::FloatEquality {
+                    expression: id,
+                    negated: *negated,
+                });
+            },
+            Expression::Call {
+                callee: Callee::Name(name),
+                arguments,
+            } if is_enabled(Lint::SelectArgumentOrder) && name.as_str() == "select" => {
+                let is_builtin =
+                    matches!(infer.call_resolution(id), Some(ResolvedCall::Function(_)));
+                if let [on_false, on_true, _] = arguments[..] {
+                    if is_builtin && is_one(&body, on_false) && is_zero(&body, on_true) {
+                        f(LintDiagnostic::SelectArgumentOrder(id));
+                    }
+                }
+            },
+            Expression::Call {
+                callee:
+                    Callee::Type(_)
+                    | Callee::InferredComponentVec(_)
+                    | Callee::InferredComponentMatrix { .. },
+                arguments,
+            } => {
+                let (Some(r#type), &[argument]) = (type_of(id), &arguments[..]) else {
+                    continue;
+                };
+                if is_enabled(Lint::IntegerDivisionInFloat)
+                    && is_float(db, r#type)
+                    && is_integer_division(db, &body, &infer, argument)
+                {
+                    f(LintDiagnostic::IntegerDivisionInFloat {
+                        division: argument,
+                        r#type,
+                    });
+                }
+                if is_enabled(Lint::RedundantConversion)
+                    && !r#type.is_err(db)
+                    && type_of(argument) == Some(r#type)
+                {
+                    f(LintDiagnostic::RedundantConversion {
+                        expression: id,
+                        r#type,
+                    });
+                }
+            },
+            _ => {},
+        }
+    }
+
+    if is_enabled(Lint::ShadowedBuiltin) {
+        // The binding of a global declaration is checked with the other items.
+        for (binding, data) in body.bindings.iter() {
+            if Some(binding) != body.main_binding && Builtin::for_name(db, &data.name).is_some() {
+                f(LintDiagnostic::ShadowedBuiltin(binding));
+            }
+        }
+    }
+}
+
+/// Runs the enabled lints which look at the items of a module. The `private` variables are
+/// only checked for writes by the bodies of `items`.
+pub fn collect_items(
+    db: &dyn HirDatabase,
+    items: &[ModuleDef],
+    is_enabled: impl Fn(Lint) -> bool,
+    mut f: impl FnMut(ItemLintDiagnostic),
+) {
+    if is_enabled(Lint::ShadowedBuiltin) {
+        for &item in items {
+            if Builtin::for_name(db, &item.name(db)).is_some() {
+                f(ItemLintDiagnostic::ShadowedBuiltin(item));
+            }
+        }
+    }
+
+    if is_enabled(Lint::WorkgroupSizeNotPowerOfTwo) {
+        for &item in items {
+            let ModuleDef::Function(function) = item else {
+                continue;
+            };
+            if let Some((attribute, invocations)) = workgroup_size(db, function) {
+                if !invocations.is_power_of_two() {
+                    f(ItemLintDiagnostic::WorkgroupSizeNotPowerOfTwo {
+                        attribute: attribute.map(|attribute| AstPointer::new(&attribute)),
+                        invocations,
+                    });
+                }
+            }
+        }
+    }
+
+    if is_enabled(Lint::PrivateNeverWritten) {
+        let written = written_variables(db, items);
+        for &item in items {
+            let ModuleDef::GlobalVariable(var) = item else {
+                continue;
+            };
+            let data = db.global_var_data(var.id);
+            if data.storage_class == Some(StorageClass::Private) && !written.contains(&var) {
+                f(ItemLintDiagnostic::PrivateNeverWritten(var));
+            }
+        }
+    }
+}
+
+fn is_float(
+    db: &dyn HirDatabase,
+    r#type: Type,
+) -> bool {
+    matches!(
+        r#type.this_or_vec_inner(db).kind(db),
+        TyKind::Scalar(ScalarType::F32)
+    )
+}
+
+fn is_integer_division(
+    db: &dyn HirDatabase,
+    body: &Body,
+    infer: &InferenceResult,
+    expression: ExpressionId,
+) -> bool {
+    let Expression::BinaryOperation {
+        operation: BinaryOperation::Arithmetic(ArithmeticOperation::Divide),
+        ..
+    } = body.exprs[expression]
+    else {
+        return false;
+    };
+    infer
+        .type_of_expression
+        .get(expression)
+        .is_some_and(|r#type| {
+            matches!(
+                r#type.this_or_vec_inner(db).kind(db),
+                TyKind::Scalar(ScalarType::I32 | ScalarType::U32)
+            )
+        })
+}
+
+fn is_one(
+    body: &Body,
+    expression: ExpressionId,
+) -> bool {
+    match body.exprs[expression] {
+        Expression::Literal(Literal::Bool(value)) => value,
+        Expression::Literal(Literal::Int(value, _)) => value == 1,
+        Expression::Literal(Literal::Uint(value, _)) => value == 1,
+        Expression::Literal(Literal::Float(bits, _)) => f64::from_bits(bits) == 1.0,
+        _ => false,
+    }
+}
+
+fn is_zero(
+    body: &Body,
+    expression: ExpressionId,
+) -> bool {
+    match body.exprs[expression] {
+        Expression::Literal(Literal::Bool(value)) => !value,
+        Expression::Literal(Literal::Int(value, _)) => value == 0,
+        Expression::Literal(Literal::Uint(value, _)) => value == 0,
+        Expression::Literal(Literal::Float(bits, _)) => f64::from_bits(bits) == 0.0,
+        _ => false,
+    }
+}
+
+/// The `@workgroup_size` attribute of the function and its number of invocations, if all of
+/// its dimensions are literals.
+fn workgroup_size(
+    db: &dyn HirDatabase,
+    function: Function,
+) -> Option<(InFile<ast::Attribute>, u64)> {
+    let source = function.source(db.upcast())?;
+    let attribute = source.value.attributes().find(|attribute| {
+        attribute
+            .ident_token()
+            .is_some_and(|name| name.text() == "workgroup_size")
+    })?;
+    let mut invocations: u64 = 1;
+    for value in attribute.parameters()?.values() {
+        let IdentOrLiteral::Literal(literal) = value else {
+            return None;
+        };
+        let dimension = match parse_literal(literal.kind()) {
+            Literal::Int(value, _) => u64::try_from(value).ok()?,
+            Literal::Uint(value, _) => value,
+            _ => return None,
+        };
+        invocations = invocations.checked_mul(dimension)?;
+    }
+    Some((source.with_value(attribute), invocations))
+}
+
+/// The global variables which are assigned to, or whose address is taken, by the bodies of
+/// the items.
+fn written_variables(
+    db: &dyn HirDatabase,
+    items: &[ModuleDef],
+) -> FxHashSet<GlobalVariable> {
+    let mut written = FxHashSet::default();
+    for def in items.iter().filter_map(ModuleDef::as_def_with_body_id) {
+        let body = db.body(def);
+        let body_resolver = BodyResolver::new(db, def);
+        let mut targets = Vec::new();
+        for (_, statement) in body.statements.iter() {
+            match *statement {
+                Statement::Assignment { left_side, .. }
+                | Statement::CompoundAssignment { left_side, .. } => targets.push(left_side),
+                Statement::IncrDecr { expression, .. } => targets.push(expression),
+                _ => {},
+            }
+        }
+        for (_, expression) in body.exprs.iter() {
+            if let Expression::UnaryOperator {
+                expression,
+                op: UnaryOperator::Reference,
+            } = *expression
+            {
+                targets.push(expression);
+            }
+        }
+
+        for mut target in targets {
+            // `a.b[i] = ...` writes to `a`.
+            loop {
+                match body.exprs[target] {
+                    Expression::Field { expression, .. } => target = expression,
+                    Expression::Index { left_side, .. } => target = left_side,
+                    _ => break,
+                }
+            }
+            let Expression::Path(name) = &body.exprs[target] else {
+                continue;
+            };
+            let resolver = body_resolver.for_expression(target);
+            if let Some(ResolveValue::GlobalVariable(loc)) = resolver.resolve_value(name) {
+                let id = db.intern_global_variable(loc);
+                written.insert(GlobalVariable { id });
+            }
+        }
+    }
+    written
+}
@@ -1,25 +1,41 @@
+pub mod control_flow;
 pub mod global_variable;
+pub mod lints;
 pub mod precedence;
+pub mod unused;
 
-use base_db::{FileRange, TextRange};
+use base_db::{FileRange, TextRange, UnconfiguredCodeKind};
 use hir_def::{
-    HirFileId, InFile, body::BodySourceMap, expression::BinaryOperation, module_data::Name,
+    HirFileId, InFile,
+    body::{Body, BodySourceMap},
+    expression::BinaryOperation,
+    module_data::Name,
 };
 use hir_ty::{
     builtins::BuiltinId,
     db::HirDatabase,
     infer::{InferenceDiagnostic, TypeExpectation, TypeLoweringError},
-    ty::Type,
+    ty::{Type, pretty::pretty_type},
     validate::StorageClassError,
 };
+use rustc_hash::FxHashMap;
 use serde::Deserialize;
 use syntax::{
-    AstNode, ast,
+    AstNode, HasName, ast,
     pointer::{AstPointer, SyntaxNodePointer},
 };
 
-use self::{global_variable::GlobalVariableDiagnostic, precedence::PrecedenceDiagnostic};
-use crate::{Function, GlobalConstant, GlobalVariable, HasSource, Override, TypeAlias};
+use self::{
+    control_flow::ControlFlowDiagnostic,
+    global_variable::GlobalVariableDiagnostic,
+    lints::{ItemLintDiagnostic, Lint, LintDiagnostic},
+    precedence::PrecedenceDiagnostic,
+    unused::UnusedDiagnostic,
+};
+use crate::{
+    Function, GlobalConstant, GlobalVariable, HasSource, ModuleDef, Override, TypeAlias,
+    control_flow::ControlFlowError,
+};
 
 #[derive(Clone, Debug, Deserialize)]
 pub enum NagaVersion {
@@ -49,6 +65,19 @@ pub struct DiagnosticsConfig {
     pub naga_parsing_errors: bool,
     pub naga_validation_errors: bool,
     pub naga_version: NagaVersion,
+    /// Severity overrides, keyed by diagnostic code.
+    #[serde(default)]
+    pub severity: FxHashMap<String, DiagnosticLevel>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagnosticLevel {
+    Error,
+    Warning,
+    Info,
+    Hint,
+    Off,
 }
 
 pub enum AnyDiagnostic {
@@ -60,6 +89,7 @@ pub enum AnyDiagnostic {
 
     UnconfiguredCode {
         def: String,
+        kind: UnconfiguredCodeKind,
         range: TextRange,
         file_id: HirFileId,
     },
@@ -132,6 +162,43 @@ pub enum AnyDiagnostic {
         operation: BinaryOperation,
         sequence_permitted: bool,
     },
+
+    MissingReturn {
+        function: InFile<AstPointer<ast::Function>>,
+    },
+    UnreachableCode {
+        file_id: HirFileId,
+        first: AstPointer<ast::Statement>,
+        last: AstPointer<ast::Statement>,
+    },
+    BreakOutsideLoop {
+        statement: InFile<AstPointer<ast::Statement>>,
+    },
+    ContinueOutsideLoop {
+        statement: InFile<AstPointer<ast::Statement>>,
+    },
+    ReturnInContinuing {
+        statement: InFile<AstPointer<ast::Statement>>,
+    },
+    MisplacedContinuing {
+        statement: InFile<AstPointer<ast::Statement>>,
+    },
+
+    UnusedItem {
+        item: ModuleDef,
+        name: InFile<AstPointer<ast::Name>>,
+    },
+    UnusedVariable {
+        binding: InFile<AstPointer<ast::Binding>>,
+        name: Name,
+        parameter: bool,
+    },
+    Lint {
+        lint: Lint,
+        file_id: HirFileId,
+        location: SyntaxNodePointer,
+        message: String,
+    },
     NagaValidationError {
         file_id: HirFileId,
         range: TextRange,
@@ -168,6 +235,15 @@ impl AnyDiagnostic {
             AnyDiagnostic::UnconfiguredCode { file_id, .. } => *file_id,
             AnyDiagnostic::NoConstructor { expression, .. } => expression.file_id,
             AnyDiagnostic::PrecedenceParensRequired { expression, .. } => expression.file_id,
+            AnyDiagnostic::MissingReturn { function } => function.file_id,
+            AnyDiagnostic::UnreachableCode { file_id, .. } => *file_id,
+            AnyDiagnostic::BreakOutsideLoop { statement } => statement.file_id,
+            AnyDiagnostic::ContinueOutsideLoop { statement } => statement.file_id,
+            AnyDiagnostic::ReturnInContinuing { statement } => statement.file_id,
+            AnyDiagnostic::MisplacedContinuing { statement } => statement.file_id,
+            AnyDiagnostic::UnusedItem { name, .. } => name.file_id,
+            AnyDiagnostic::UnusedVariable { binding, .. } => binding.file_id,
+            AnyDiagnostic::Lint { file_id, .. } => *file_id,
         }
     }
 }
@@ -393,3 +469,211 @@ pub(crate) fn any_diag_from_shift(
         },
     }
 }
+
+pub(crate) fn any_diag_from_control_flow(
+    db: &dyn HirDatabase,
+    diagnostic: &ControlFlowDiagnostic,
+    source_map: &BodySourceMap,
+    file_id: HirFileId,
+    function: Function,
+) -> Option<AnyDiagnostic> {
+    let statement_source = |statement| -> Option<InFile<AstPointer<ast::Statement>>> {
+        let pointer = source_map.statement_to_source(statement).ok()?.clone();
+        Some(InFile::new(file_id, pointer))
+    };
+    Some(match *diagnostic {
+        ControlFlowDiagnostic::MissingReturn => {
+            let source = function.source(db.upcast())?;
+            AnyDiagnostic::MissingReturn {
+                function: source.map(|function| AstPointer::new(&function)),
+            }
+        },
+        ControlFlowDiagnostic::UnreachableCode { first, last } => AnyDiagnostic::UnreachableCode {
+            file_id,
+            first: source_map.statement_to_source(first).ok()?.clone(),
+            last: source_map.statement_to_source(last).ok()?.clone(),
+        },
+        ControlFlowDiagnostic::Misplaced(error) => match error {
+            ControlFlowError::BreakOutsideLoop(statement) => AnyDiagnostic::BreakOutsideLoop {
+                statement: statement_source(statement)?,
+            },
+            ControlFlowError::ContinueOutsideLoop(statement) => {
+                AnyDiagnostic::ContinueOutsideLoop {
+                    statement: statement_source(statement)?,
+                }
+            },
+            ControlFlowError::ReturnInContinuing(statement) => AnyDiagnostic::ReturnInContinuing {
+                statement: statement_source(statement)?,
+            },
+            ControlFlowError::MisplacedContinuing(statement) => {
+                AnyDiagnostic::MisplacedContinuing {
+                    statement: statement_source(statement)?,
+                }
+            },
+        },
+    })
+}
+
+pub(crate) fn any_diag_from_unused_item(
+    db: &dyn HirDatabase,
+    item: ModuleDef,
+) -> Option<AnyDiagnostic> {
+    Some(AnyDiagnostic::UnusedItem {
+        item,
+        name: item_name(db, item)?,
+    })
+}
+
+fn item_name(
+    db: &dyn HirDatabase,
+    item: ModuleDef,
+) -> Option<InFile<AstPointer<ast::Name>>> {
+    let db = db.upcast();
+    let name = match item {
+        ModuleDef::Function(function) => {
+            let source = function.source(db)?;
+            source.value.name().map(|name| (source.file_id, name))
+        },
+        ModuleDef::GlobalVariable(var) => {
+            let source = var.source(db)?;
+            let name = source.value.binding().and_then(|binding| binding.name());
+            name.map(|name| (source.file_id, name))
+        },
+        ModuleDef::GlobalConstant(constant) => {
+            let source = constant.source(db)?;
+            let name = source.value.binding().and_then(|binding| binding.name());
+            name.map(|name| (source.file_id, name))
+        },
+        ModuleDef::Override(override_declaration) => {
+            let source = override_declaration.source(db)?;
+            let name = source.value.binding().and_then(|binding| binding.name());
+            name.map(|name| (source.file_id, name))
+        },
+        ModuleDef::Struct(r#struct) => {
+            let source = r#struct.source(db)?;
+            source.value.name().map(|name| (source.file_id, name))
+        },
+        ModuleDef::TypeAlias(type_alias) => {
+            let source = type_alias.source(db)?;
+            source.value.name().map(|name| (source.file_id, name))
+        },
+    };
+    let (file_id, name) = name?;
+    Some(InFile::new(file_id, AstPointer::new(&name)))
+}
+
+pub(crate) fn any_diag_from_unused(
+    diagnostic: &UnusedDiagnostic,
+    body: &Body,
+    source_map: &BodySourceMap,
+    file_id: HirFileId,
+) -> Option<AnyDiagnostic> {
+    let (binding, parameter) = match *diagnostic {
+        UnusedDiagnostic::UnusedParameter(binding) => (binding, true),
+        UnusedDiagnostic::UnusedVariable(binding) => (binding, false),
+    };
+    let pointer = source_map.binding_to_source(binding).ok()?.clone();
+    Some(AnyDiagnostic::UnusedVariable {
+        binding: InFile::new(file_id, pointer),
+        name: body.bindings[binding].name.clone(),
+        parameter,
+    })
+}
+
+pub(crate) fn any_diag_from_lint(
+    db: &dyn HirDatabase,
+    diagnostic: &LintDiagnostic,
+    body: &Body,
+    source_map: &BodySourceMap,
+    file_id: HirFileId,
+) -> Option<AnyDiagnostic> {
+    let expression_location = |expression| -> Option<SyntaxNodePointer> {
+        let pointer = source_map.expression_to_source(expression).ok()?;
+        Some(pointer.syntax_node_pointer())
+    };
+    let (location, message) = match *diagnostic {
+        LintDiagnostic::FloatEquality {
+            expression,
+            negated,
+        } => (
+            expression_location(expression)?,
+            format!(
+                "floating point values are compared with `{}`, which is sensitive to \
+                rounding errors",
+                if negated { "!=" } else { "==" }
+            ),
+        ),
+        LintDiagnostic::IntegerDivisionInFloat { division, r#type } => (
+            expression_location(division)?,
+            format!(
+                "the integer division is truncated before the conversion to `{}`",
+                pretty_type(db, r#type)
+            ),
+        ),
+        LintDiagnostic::ShadowedBuiltin(binding) => {
+            let pointer = source_map.binding_to_source(binding).ok()?;
+            let name = body.bindings[binding].name.as_str();
+            (
+                pointer.syntax_node_pointer(),
+                format!("`{name}` shadows the builtin function `{name}`"),
+            )
+        },
+        LintDiagnostic::SelectArgumentOrder(call) => (
+            expression_location(call)?,
+            "`select(f, t, condition)` returns `t` when the condition is true, \
+            the arguments may be swapped"
+                .to_owned(),
+        ),
+        LintDiagnostic::RedundantConversion { expression, r#type } => (
+            expression_location(expression)?,
+            format!(
+                "redundant conversion, the value already has the type `{}`",
+                pretty_type(db, r#type)
+            ),
+        ),
+    };
+    Some(AnyDiagnostic::Lint {
+        lint: diagnostic.lint(),
+        file_id,
+        location,
+        message,
+    })
+}
+
+pub(crate) fn any_diag_from_item_lint(
+    db: &dyn HirDatabase,
+    diagnostic: &ItemLintDiagnostic,
+) -> Option<AnyDiagnostic> {
+    let (location, message) = match *diagnostic {
+        ItemLintDiagnostic::ShadowedBuiltin(item) => {
+            let name = item.name(db);
+            (
+                item_name(db, item)?.map(|name| name.syntax_node_pointer()),
+                format!("`{0}` shadows the builtin function `{0}`", name.as_str()),
+            )
+        },
+        ItemLintDiagnostic::WorkgroupSizeNotPowerOfTwo {
+            ref attribute,
+            invocations,
+        } => (
+            attribute
+                .as_ref()
+                .map(|attribute| attribute.syntax_node_pointer()),
+            format!("the workgroup has {invocations} invocations, which is not a power of two"),
+        ),
+        ItemLintDiagnostic::PrivateNeverWritten(var) => {
+            let name = ModuleDef::GlobalVariable(var).name(db);
+            (
+                item_name(db, ModuleDef::GlobalVariable(var))?
+                    .map(|name| name.syntax_node_pointer()),
+                format!("`var<private>` `{}` is never written", name.as_str()),
+            )
+        },
+    };
+    Some(AnyDiagnostic::Lint {
+        lint: diagnostic.lint(),
+        file_id: location.file_id,
+        location: location.value,
+        message,
+    })
+}
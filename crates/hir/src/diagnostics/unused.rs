@@ -0,0 +1,113 @@
+use hir_def::{
+    body::BindingId,
+    db::{DefinitionWithBodyId, FunctionId},
+    expression::{Callee, Expression, Statement},
+    module_data::Name,
+    resolver::{ResolveValue, Resolver},
+    type_ref::{ArraySize, TypeReference},
+};
+use hir_ty::db::HirDatabase;
+use rustc_hash::FxHashSet;
+
+use crate::{Function, call_graph::BodyResolver};
+
+#[derive(Debug)]
+pub enum UnusedDiagnostic {
+    UnusedParameter(BindingId),
+    UnusedVariable(BindingId),
+}
+
+/// Reports the parameters and local declarations of the function which are never referred
+/// to. Names starting with `_` may be unused, and so may the parameters of entry points,
+/// which are part of the interface of the pipeline stage.
+pub fn collect(
+    db: &dyn HirDatabase,
+    function: FunctionId,
+    mut f: impl FnMut(UnusedDiagnostic),
+) {
+    let def = DefinitionWithBodyId::Function(function);
+    let body = db.body(def);
+    if body.root.is_none() {
+        return;
+    }
+
+    let body_resolver = BodyResolver::new(db, def);
+    let mut used = FxHashSet::default();
+    let mut mark_used = |resolver: Resolver, name: &Name| {
+        if let Some(ResolveValue::Local(binding)) = resolver.resolve_value(name) {
+            used.insert(binding);
+        }
+    };
+    for (id, expression) in body.exprs.iter() {
+        match expression {
+            Expression::Path(name) => mark_used(body_resolver.for_expression(id), name),
+            Expression::Call {
+                callee: Callee::Type(r#type),
+                ..
+            }
+            | Expression::Bitcast { r#type, .. } => {
+                for name in array_sizes(&db.lookup_intern_type_ref(*r#type)) {
+                    mark_used(body_resolver.for_expression(id), name);
+                }
+            },
+            _ => {},
+        }
+    }
+    // Constants may be used as the sizes of the arrays in the types of declarations.
+    for (id, statement) in body.statements.iter() {
+        if let Statement::LetStatement {
+            type_ref: Some(r#type),
+            ..
+        }
+        | Statement::ConstStatement {
+            type_ref: Some(r#type),
+            ..
+        }
+        | Statement::VariableStatement {
+            type_ref: Some(r#type),
+            ..
+        } = statement
+        {
+            for name in array_sizes(&db.lookup_intern_type_ref(*r#type)) {
+                mark_used(body_resolver.for_statement(id), name);
+            }
+        }
+    }
+
+    let is_entry_point = Function { id: function }.is_entry_point(db);
+    for (binding, data) in body.bindings.iter() {
+        if used.contains(&binding) || may_be_unused(&data.name) {
+            continue;
+        }
+        if !body.parameters.contains(&binding) {
+            f(UnusedDiagnostic::UnusedVariable(binding));
+        } else if !is_entry_point {
+            f(UnusedDiagnostic::UnusedParameter(binding));
+        }
+    }
+}
+
+/// The names used as array sizes in the type.
+fn array_sizes(r#type: &TypeReference) -> Vec<&Name> {
+    let mut names = Vec::new();
+    let mut r#type = r#type;
+    loop {
+        r#type = match r#type {
+            TypeReference::Array(array) => {
+                if let ArraySize::Path(name) = &array.size {
+                    names.push(name);
+                }
+                &array.inner
+            },
+            TypeReference::Vec(vec) => &vec.inner,
+            TypeReference::Matrix(matrix) => &matrix.inner,
+            TypeReference::Atomic(atomic) => &atomic.inner,
+            TypeReference::Pointer(pointer) => &pointer.inner,
+            _ => return names,
+        };
+    }
+}
+
+fn may_be_unused(name: &Name) -> bool {
+    name.as_str().starts_with('_') || Name::is_missing(name.as_str())
+}
@@ -1,14 +1,18 @@
+pub mod call_graph;
+pub mod control_flow;
 pub mod definition;
 pub mod diagnostics;
 
 use std::sync::Arc;
 
 use base_db::FileId;
+use call_graph::{Call, CallGraph};
 use definition::Definition;
-use diagnostics::{AnyDiagnostic, DiagnosticsConfig};
+use diagnostics::{AnyDiagnostic, DiagnosticsConfig, lints::Lint};
 use either::Either;
 use hir_def::{
     HasSource as _, HirFileId, InFile,
+    attributes::AttributeDefId,
     body::{BindingId, Body, BodySourceMap},
     data::FieldId,
     db::{
@@ -16,12 +20,15 @@ use hir_def::{
         ImportId, Location, Lookup, OverrideId, StructId, TypeAliasId,
     },
     expression::{ExpressionId, StatementId},
-    hir_file_id::{ImportFile, relative_file},
+    hir_file_id::{ImportFile, custom_import_file, relative_file, resolve_wesl_import},
     module_data::{self, ImportValue, ModuleInfo, ModuleItem, Name},
     resolver::{ResolveValue, Resolver},
 };
 pub use hir_ty::db::HirDatabase;
-use hir_ty::{infer::InferenceResult, ty::Type};
+use hir_ty::{
+    infer::{InferenceResult, TypeExpectation},
+    ty::Type,
+};
 use smallvec::SmallVec;
 use syntax::{AstNode, HasName, SyntaxNode, ast, match_ast, pointer::AstPointer};
 
@@ -68,6 +75,7 @@ impl<'db> Semantics<'db> {
                     ast::Function(function) => self.function_to_def(InFile::new(file_id, function)).map(DefinitionWithBodyId::Function),
                     ast::GlobalVariableDeclaration(var) => self.global_variable_to_def(InFile::new(file_id, var)).map(DefinitionWithBodyId::GlobalVariable),
                     ast::GlobalConstantDeclaration(constant) => self.global_constant_to_def(InFile::new(file_id, constant)).map(DefinitionWithBodyId::GlobalConstant),
+                    ast::OverrideDeclaration(override_declaration) => self.override_to_def(InFile::new(file_id, override_declaration)).map(DefinitionWithBodyId::Override),
                     _ => None,
                 }
             }
@@ -142,6 +150,14 @@ impl<'db> Semantics<'db> {
         Some(def)
     }
 
+    /// The function declared by the syntax node.
+    pub fn function(
+        &self,
+        source: InFile<ast::Function>,
+    ) -> Option<Function> {
+        self.function_to_def(source).map(|id| Function { id })
+    }
+
     fn function_to_def(
         &self,
         source: InFile<ast::Function>,
@@ -165,6 +181,18 @@ impl<'db> Semantics<'db> {
         Some(id)
     }
 
+    fn override_to_def(
+        &self,
+        source: InFile<ast::OverrideDeclaration>,
+    ) -> Option<OverrideId> {
+        let override_declaration =
+            module_data::find_item(self.db.upcast(), source.file_id, &source.value)?;
+        let id = self
+            .db
+            .intern_override(Location::new(source.file_id, override_declaration));
+        Some(id)
+    }
+
     fn global_variable_to_def(
         &self,
         source: InFile<ast::GlobalVariableDeclaration>,
@@ -293,6 +321,24 @@ impl<'db> SourceAnalyzer<'db> {
         Some(r#type)
     }
 
+    pub fn expected_type_of_expression(
+        &self,
+        expression: &ast::Expression,
+    ) -> Option<TypeExpectation> {
+        let id = self.expression_id(expression)?;
+        self.infer.expected_type(id).cloned()
+    }
+
+    /// The type expected for the argument at `index` of `call`, even if it is not written yet.
+    pub fn expected_type_of_argument(
+        &self,
+        call: &ast::Expression,
+        index: usize,
+    ) -> Option<TypeExpectation> {
+        let id = self.expression_id(call)?;
+        self.infer.expected_argument_type(self.db, id, index)
+    }
+
     pub fn type_of_binding(
         &self,
         binding: &ast::Binding,
@@ -397,11 +443,39 @@ impl HasSource for Local {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Copy)]
+#[derive(Debug, Clone, PartialEq, Eq, Copy, Hash)]
 pub struct Function {
     id: FunctionId,
 }
 
+impl Function {
+    /// Whether the function is a `@vertex`, `@fragment` or `@compute` entry point.
+    pub fn is_entry_point(
+        self,
+        db: &dyn HirDatabase,
+    ) -> bool {
+        let attributes = db.attrs(AttributeDefId::FunctionId(self.id));
+        ["vertex", "fragment", "compute"]
+            .iter()
+            .any(|stage| attributes.attribute_list.has(db.upcast(), stage))
+    }
+
+    pub fn name(
+        self,
+        db: &dyn HirDatabase,
+    ) -> Name {
+        db.fn_data(self.id).name.clone()
+    }
+
+    /// The calls of functions in the body of the function.
+    pub fn calls(
+        self,
+        db: &dyn HirDatabase,
+    ) -> Vec<Call> {
+        call_graph::calls(db, self)
+    }
+}
+
 impl HasSource for Function {
     type Ast = ast::Function;
 
@@ -413,7 +487,7 @@ impl HasSource for Function {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Copy)]
+#[derive(Debug, Clone, PartialEq, Eq, Copy, Hash)]
 pub struct GlobalVariable {
     id: GlobalVariableId,
 }
@@ -429,7 +503,7 @@ impl HasSource for GlobalVariable {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Copy)]
+#[derive(Debug, Clone, PartialEq, Eq, Copy, Hash)]
 pub struct GlobalConstant {
     id: GlobalConstantId,
 }
@@ -445,7 +519,7 @@ impl HasSource for GlobalConstant {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Copy)]
+#[derive(Debug, Clone, PartialEq, Eq, Copy, Hash)]
 pub struct Override {
     id: OverrideId,
 }
@@ -461,7 +535,7 @@ impl HasSource for Override {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Copy)]
+#[derive(Debug, Clone, PartialEq, Eq, Copy, Hash)]
 pub struct Struct {
     id: StructId,
 }
@@ -477,7 +551,7 @@ impl HasSource for Struct {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Copy)]
+#[derive(Debug, Clone, PartialEq, Eq, Copy, Hash)]
 pub struct TypeAlias {
     id: TypeAliasId,
 }
@@ -520,7 +594,7 @@ impl HasSource for Field {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ModuleDef {
     Function(Function),
     GlobalVariable(GlobalVariable),
@@ -545,6 +619,39 @@ impl ModuleDef {
             ModuleDef::TypeAlias(_) => None, // TODO: ?
         }
     }
+
+    pub fn name(
+        &self,
+        db: &dyn HirDatabase,
+    ) -> Name {
+        match *self {
+            ModuleDef::Function(function) => function.name(db),
+            ModuleDef::GlobalVariable(var) => db.global_var_data(var.id).name.clone(),
+            ModuleDef::GlobalConstant(constant) => {
+                db.global_constant_data(constant.id).name.clone()
+            },
+            ModuleDef::Override(override_declaration) => {
+                db.override_data(override_declaration.id).name.clone()
+            },
+            ModuleDef::Struct(r#struct) => db.struct_data(r#struct.id).name.clone(),
+            ModuleDef::TypeAlias(type_alias) => db.type_alias_data(type_alias.id).name.clone(),
+        }
+    }
+
+    /// The file declaring the item, which is an import file for imported items.
+    pub fn file_id(
+        &self,
+        db: &dyn DefDatabase,
+    ) -> HirFileId {
+        match *self {
+            ModuleDef::Function(function) => function.id.lookup(db).file_id,
+            ModuleDef::GlobalVariable(var) => var.id.lookup(db).file_id,
+            ModuleDef::GlobalConstant(constant) => constant.id.lookup(db).file_id,
+            ModuleDef::Override(override_declaration) => override_declaration.id.lookup(db).file_id,
+            ModuleDef::Struct(r#struct) => r#struct.id.lookup(db).file_id,
+            ModuleDef::TypeAlias(type_alias) => type_alias.id.lookup(db).file_id,
+        }
+    }
 }
 
 pub struct Module {
@@ -652,6 +759,96 @@ impl Module {
                         },
                     }
                 });
+
+                if file == self.file_id {
+                    let body = db.body(def);
+                    let is_enabled = |lint: Lint| lint.is_enabled(config);
+                    diagnostics::lints::collect(db, def, is_enabled, |diagnostic| {
+                        match diagnostics::any_diag_from_lint(
+                            db,
+                            &diagnostic,
+                            &body,
+                            &source_map,
+                            file,
+                        ) {
+                            Some(diagnostic) => accumulator.push(diagnostic),
+                            None => {
+                                tracing::warn!("could not create diagnostic from {:?}", diagnostic)
+                            },
+                        }
+                    });
+                }
+
+                if let ModuleDef::Function(function) = item {
+                    diagnostics::control_flow::collect(db, def, |diagnostic| {
+                        match diagnostics::any_diag_from_control_flow(
+                            db,
+                            &diagnostic,
+                            &source_map,
+                            file,
+                            function,
+                        ) {
+                            Some(diagnostic) => accumulator.push(diagnostic),
+                            None => {
+                                tracing::warn!("could not create diagnostic from {:?}", diagnostic)
+                            },
+                        }
+                    });
+
+                    if file == self.file_id {
+                        let body = db.body(def);
+                        diagnostics::unused::collect(db, function.id, |diagnostic| {
+                            match diagnostics::any_diag_from_unused(
+                                &diagnostic,
+                                &body,
+                                &source_map,
+                                file,
+                            ) {
+                                Some(diagnostic) => accumulator.push(diagnostic),
+                                None => {
+                                    tracing::warn!(
+                                        "could not create diagnostic from {:?}",
+                                        diagnostic
+                                    )
+                                },
+                            }
+                        });
+                    }
+                }
+            }
+        }
+
+        // A module without entry points is a library, whose items are used by the modules
+        // importing it, and whose private variables may be written by them.
+        let call_graph = CallGraph::new(db, self);
+        let is_library = call_graph.entry_points().is_empty();
+
+        let items: Vec<_> = self
+            .items(db)
+            .into_iter()
+            .filter(|item| item.file_id(db.upcast()) == self.file_id)
+            .collect();
+        let is_enabled = |lint: Lint| {
+            lint.is_enabled(config) && !(is_library && lint == Lint::PrivateNeverWritten)
+        };
+        diagnostics::lints::collect_items(db, &items, is_enabled, |diagnostic| {
+            match diagnostics::any_diag_from_item_lint(db, &diagnostic) {
+                Some(diagnostic) => accumulator.push(diagnostic),
+                None => tracing::warn!("could not create diagnostic from {:?}", diagnostic),
+            }
+        });
+
+        if !is_library {
+            for &item in call_graph.items() {
+                if item.file_id(db.upcast()) != self.file_id
+                    || call_graph.is_reachable(item)
+                    || item.name(db).as_str().starts_with('_')
+                {
+                    continue;
+                }
+                if let Some(diagnostic) = diagnostics::any_diag_from_unused_item(db, item) {
+                    accumulator.push(diagnostic);
+                }
             }
         }
     }
@@ -688,10 +885,17 @@ impl Import {
                 Some(db.file_text(file_id).to_string())
             },
             ImportValue::Custom(key) => {
+                if let Some(file_id) = custom_import_file(db.upcast(), key) {
+                    return Some(db.file_text(file_id).to_string());
+                }
                 let imports = db.custom_imports();
                 let source = imports.get(key)?;
                 Some(source.clone())
             },
+            ImportValue::Wesl(path) => {
+                let (file_id, _) = resolve_wesl_import(db.upcast(), import_loc.file_id, path)?;
+                Some(db.file_text(file_id).to_string())
+            },
         }
     }
 
@@ -714,12 +918,16 @@ impl Import {
             },
             ImportValue::Custom(key) => {
                 let imports = db.custom_imports();
-                if imports.contains_key(key) {
+                if imports.contains_key(key) || custom_import_file(db.upcast(), key).is_some() {
                     Ok(())
                 } else {
                     Err(())
                 }
             },
+            ImportValue::Wesl(path) => {
+                resolve_wesl_import(db.upcast(), import_loc.file_id, path).ok_or(())?;
+                Ok(())
+            },
         }
     }
 }
@@ -1,3 +1,4 @@
+use base_db::is_configured;
 use either::Either;
 use syntax::{AstNode, HasGenerics, HasName, ast, pointer::AstPointer};
 
@@ -6,7 +7,7 @@ use crate::{
     HirFileId, InFile,
     db::DefDatabase,
     expression::{Callee, Expression, ExpressionId, Statement, StatementId, parse_literal},
-    hir_file_id::relative_file,
+    hir_file_id::{parse_custom_import, relative_file},
     module_data::Name,
     type_ref::{TypeReference, matrix_dimensions, vector_dimensions},
 };
@@ -95,7 +96,11 @@ impl Collector<'_> {
         param_list: Option<ast::ParameterList>,
     ) {
         if let Some(param_list) = param_list {
-            for p in param_list.parameters() {
+            let shader_defs = self.db.shader_defs();
+            for p in param_list
+                .parameters()
+                .filter(|parameter| is_configured(parameter, &shader_defs))
+            {
                 if let Some(binding) = p
                     .variable_ident_declaration()
                     .and_then(|declaration| declaration.binding())
@@ -117,13 +122,16 @@ impl Collector<'_> {
                                             relative_file(self.db, import_loc.file_id, path)?;
                                         Some(self.db.parse(file_id))
                                     },
-                                    crate::module_data::ImportValue::Custom(key) => self
-                                        .db
-                                        .parse_import(
-                                            key.clone(),
+                                    crate::module_data::ImportValue::Custom(key) => {
+                                        parse_custom_import(
+                                            self.db,
+                                            key,
                                             syntax::ParseEntryPoint::FunctionParameterList,
                                         )
-                                        .ok(),
+                                        .ok()
+                                    },
+                                    // WESL imports are items, not parameters
+                                    crate::module_data::ImportValue::Wesl(_) => None,
                                 }
                             })
                             .and_then(|parse| ast::ParameterList::cast(parse.syntax()));
@@ -213,8 +221,10 @@ impl Collector<'_> {
         &mut self,
         compound_statement: ast::CompoundStatement,
     ) -> StatementId {
+        let shader_defs = self.db.shader_defs();
         let statements = compound_statement
             .statements()
+            .filter(|statement| is_configured(statement, &shader_defs))
             .filter_map(|statement| self.collect_statement(statement))
             .collect();
 
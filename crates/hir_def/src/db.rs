@@ -17,7 +17,10 @@ use crate::{
         FunctionData, GlobalConstantData, GlobalVariableData, OverrideData, StructData,
         TypeAliasData,
     },
-    hir_file_id::{HirFileIdRepr, ImportFile, relative_file},
+    hir_file_id::{
+        HirFileIdRepr, ImportFile, parse_custom_import, relative_file, resolve_wesl_import,
+    },
+    import_index::{ImportableItem, importable_items_query},
     module_data::{
         Function, GlobalConstant, GlobalVariable, Import, ModuleInfo, ModuleItemId, Override,
         Struct, TypeAlias,
@@ -124,6 +127,9 @@ pub trait DefDatabase: InternDatabase + Upcast<dyn SourceDatabase> {
         &self,
         def: AttributeDefId,
     ) -> Arc<AttributesWithOwner>;
+
+    #[salsa::invoke(importable_items_query)]
+    fn importable_items(&self) -> Arc<Vec<ImportableItem>>;
 }
 
 fn get_path(
@@ -160,7 +166,12 @@ fn parse_or_resolve(
                     Ok(db.parse(file_id))
                 },
                 crate::module_data::ImportValue::Custom(key) => {
-                    db.parse_import(key.clone(), syntax::ParseEntryPoint::File)
+                    parse_custom_import(db, key, syntax::ParseEntryPoint::File)
+                },
+                crate::module_data::ImportValue::Wesl(path) => {
+                    let (file_id, _) =
+                        resolve_wesl_import(db, import_loc.file_id, path).ok_or(())?;
+                    Ok(db.parse(file_id))
                 },
             }
         },
@@ -15,17 +15,28 @@ pub type ExpressionId = Idx<Expression>;
 pub enum Literal {
     Int(i64, BuiltinInt),
     Uint(u64, BuiltinUint),
-    Float(u32, BuiltinFloat), // FIXME: f32 is not Eq
+    /// The bits of the `f64` value, as floats are not `Eq`.
+    Float(u64, BuiltinFloat),
     Bool(bool),
 }
 
+/// The type of a float literal, given by its suffix.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum BuiltinFloat {
+    /// Without a suffix, like `1.5`.
+    Abstract,
+    /// `1.5f`
     F32,
+    /// `1.5h`
+    F16,
 }
 
+/// The type of a signed integer literal, given by its suffix.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum BuiltinInt {
+    /// Without a suffix, like `1`.
+    Abstract,
+    /// `1i`
     I32,
 }
 
@@ -157,8 +168,15 @@ pub enum Statement {
 
 pub fn parse_literal(literal: ast::LiteralKind) -> Literal {
     match literal {
+        // The lexer accepts a `u` suffix on hexadecimal integers, too.
+        ast::LiteralKind::HexIntLiteral(literal) if literal.text().ends_with('u') => {
+            parse_unsigned(literal.text())
+        },
         ast::LiteralKind::HexIntLiteral(literal) | ast::LiteralKind::DecimalIntLiteral(literal) => {
-            let text = literal.text().trim_end_matches('i');
+            let (text, kind) = match literal.text().strip_suffix('i') {
+                Some(text) => (text, BuiltinInt::I32),
+                None => (literal.text(), BuiltinInt::Abstract),
+            };
             let (text, negative) = match text.strip_prefix('-') {
                 Some(new) => (new, true),
                 None => (text, false),
@@ -173,31 +191,41 @@ pub fn parse_literal(literal: ast::LiteralKind) -> Literal {
                 value = -value;
             }
 
-            Literal::Int(value, BuiltinInt::I32)
-        },
-        ast::LiteralKind::UnsignedIntLiteral(literal) => {
-            let text = literal.text().trim_end_matches('u');
-            let value = match text.strip_prefix("0x") {
-                Some(hex) => u64::from_str_radix(hex, 16),
-                None => text.parse(),
-            }
-            .expect("invalid literal");
-
-            Literal::Uint(value, BuiltinUint::U32)
+            Literal::Int(value, kind)
         },
-        ast::LiteralKind::HexFloatLiteral(_) => Literal::Float(0, BuiltinFloat::F32),
+        ast::LiteralKind::UnsignedIntLiteral(literal) => parse_unsigned(literal.text()),
+        ast::LiteralKind::HexFloatLiteral(_) => Literal::Float(0, BuiltinFloat::Abstract),
         ast::LiteralKind::DecimalFloatLiteral(literal) => {
             use std::str::FromStr;
-            // Float suffixes are not accepted by `f32::from_str`. Ignore them
-            let text = literal.text().trim_end_matches(char::is_alphabetic);
-            let _value = f32::from_str(text).expect("invalid literal");
-            Literal::Float(0, BuiltinFloat::F32)
+            // Float suffixes are not accepted by `f64::from_str`
+            let text = literal.text();
+            let (text, kind) = if let Some(text) = text.strip_suffix('f') {
+                (text, BuiltinFloat::F32)
+            } else if let Some(text) = text.strip_suffix('h') {
+                (text, BuiltinFloat::F16)
+            } else {
+                (text, BuiltinFloat::Abstract)
+            };
+            // Parsing as `f64` keeps the precision of abstract floats, such as `0.1`
+            let value = f64::from_str(text).expect("invalid literal");
+            Literal::Float(value.to_bits(), kind)
         },
         ast::LiteralKind::True(_) => Literal::Bool(true),
         ast::LiteralKind::False(_) => Literal::Bool(false),
     }
 }
 
+fn parse_unsigned(text: &str) -> Literal {
+    let text = text.trim_end_matches('u');
+    let value = match text.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => text.parse(),
+    }
+    .expect("invalid literal");
+
+    Literal::Uint(value, BuiltinUint::U32)
+}
+
 impl Expression {
     pub fn walk_child_expressions(
         &self,
@@ -1,9 +1,10 @@
 use base_db::FileId;
+use syntax::{Parse, ParseEntryPoint};
 use vfs::AnchoredPath;
 
 use crate::{
     db::{DefDatabase, ImportId},
-    module_data::ImportValue,
+    module_data::{ImportValue, Name},
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -44,6 +45,9 @@ impl HirFileId {
                 match &import.value {
                     ImportValue::Path(path) => relative_file(db, import_loc.file_id, path),
                     ImportValue::Custom(key) => {
+                        if let Some(file_id) = custom_import_file(db, key) {
+                            return Some(file_id);
+                        }
                         // Try to resolve the custom import as a file
                         let imports = db.custom_imports();
                         if imports.contains_key(key) {
@@ -54,6 +58,8 @@ impl HirFileId {
                             None
                         }
                     },
+                    ImportValue::Wesl(path) => resolve_wesl_import(db, import_loc.file_id, path)
+                        .map(|(file_id, _)| file_id),
                 }
             },
         }
@@ -82,3 +88,83 @@ pub fn relative_file(
         _ => None,
     }
 }
+
+/// Finds the workspace file which declares `key` with `#define_import_path`.
+pub fn custom_import_file(
+    database: &dyn DefDatabase,
+    key: &str,
+) -> Option<FileId> {
+    database.import_path_index().get(key).copied()
+}
+
+/// Parses the source of a custom import.
+///
+/// Workspace files declaring the import path take precedence over the configured `custom_imports`.
+pub(crate) fn parse_custom_import(
+    database: &dyn DefDatabase,
+    key: &str,
+    parse_entrypoint: ParseEntryPoint,
+) -> Result<Parse, ()> {
+    match custom_import_file(database, key) {
+        Some(file_id) if parse_entrypoint == ParseEntryPoint::File => Ok(database.parse(file_id)),
+        Some(file_id) => Ok(syntax::parse_entrypoint_with_edition(
+            &database.file_text(file_id),
+            parse_entrypoint,
+            database.file_edition(file_id),
+        )),
+        None => database.parse_import(key.to_owned(), parse_entrypoint),
+    }
+}
+
+/// Resolves a WESL module path such as `package::lighting::pbr` or `super::util`
+/// against the directory tree of the importing file.
+///
+/// `package::` starts at the package root and `super::` at the directory of the importing file,
+/// going up one directory for every further `super`, but never above the package root.
+/// The remaining segments name `<directory>/<segment>.wesl` (or `.wgsl`) modules, except for an
+/// optional last segment, which names an item of the module and is returned alongside its file.
+pub fn resolve_wesl_import(
+    database: &dyn DefDatabase,
+    importer: HirFileId,
+    path: &str,
+) -> Option<(FileId, Option<Name>)> {
+    let importer = importer.original_file(database)?;
+    let source_root_id = database.file_source_root(importer);
+    let source_root = database.source_root(source_root_id);
+
+    let mut segments = path.split("::").peekable();
+    let mut directory = match segments.next()? {
+        "package" => database.package_root(source_root_id)?,
+        "super" => {
+            let package_root = database.package_root(source_root_id)?;
+            let mut directory = database.file_path(importer).parent()?;
+            while segments.next_if_eq(&"super").is_some() {
+                // Modules at the package root have no parent module
+                if directory == package_root {
+                    return None;
+                }
+                directory = directory.parent()?;
+            }
+            directory
+        },
+        // Dependencies are not supported yet
+        _ => return None,
+    };
+
+    let segments: Vec<&str> = segments.collect();
+    for (index, segment) in segments.iter().enumerate() {
+        let module_file = ["wesl", "wgsl"].into_iter().find_map(|extension| {
+            let path = directory.join(&format!("{segment}.{extension}"))?;
+            source_root.file_for_path(&path).copied()
+        });
+        if let Some(file_id) = module_file {
+            return match &segments[index + 1..] {
+                [] => Some((file_id, None)),
+                [item] => Some((file_id, Some(Name::from(*item)))),
+                _ => None,
+            };
+        }
+        directory = directory.join(segment)?;
+    }
+    None
+}
@@ -0,0 +1,91 @@
+//! The items of the modules which can be imported with `#import`, i.e. the
+//! configured `custom_imports` and the workspace files with a `#define_import_path`.
+
+use std::sync::Arc;
+
+use base_db::FileId;
+use syntax::{HasName, ParseEntryPoint, ast};
+
+use crate::{
+    db::DefDatabase,
+    hir_file_id::{custom_import_file, parse_custom_import},
+    module_data::Name,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ImportableItemKind {
+    Function,
+    GlobalVariable,
+    GlobalConstant,
+    Override,
+    Struct,
+    TypeAlias,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportableItem {
+    /// The path of the module, as written after `#import`.
+    pub import_path: Arc<str>,
+    /// The workspace file declaring the module, `None` for a configured custom import.
+    pub file_id: Option<FileId>,
+    pub name: Name,
+    pub kind: ImportableItemKind,
+}
+
+pub(crate) fn importable_items_query(db: &dyn DefDatabase) -> Arc<Vec<ImportableItem>> {
+    let custom_imports = db.custom_imports();
+    let import_path_index = db.import_path_index();
+    let mut import_paths: Vec<&str> = custom_imports
+        .keys()
+        .map(String::as_str)
+        .chain(import_path_index.keys().map(AsRef::as_ref))
+        .collect();
+    import_paths.sort_unstable();
+    import_paths.dedup();
+
+    let mut items = Vec::new();
+    for import_path in import_paths {
+        let Ok(parse) = parse_custom_import(db, import_path, ParseEntryPoint::File) else {
+            continue;
+        };
+        let import_path: Arc<str> = Arc::from(import_path);
+        let file_id = custom_import_file(db, &import_path);
+        for item in parse.tree().items() {
+            let Some((name, kind)) = name_and_kind(&item) else {
+                continue;
+            };
+            items.push(ImportableItem {
+                import_path: import_path.clone(),
+                file_id,
+                name: Name::from(name),
+                kind,
+            });
+        }
+    }
+    Arc::new(items)
+}
+
+fn name_and_kind(item: &ast::Item) -> Option<(ast::Name, ImportableItemKind)> {
+    match item {
+        ast::Item::Function(function) => Some((function.name()?, ImportableItemKind::Function)),
+        ast::Item::StructDeclaration(r#struct) => {
+            Some((r#struct.name()?, ImportableItemKind::Struct))
+        },
+        ast::Item::GlobalVariableDeclaration(variable) => Some((
+            variable.binding()?.name()?,
+            ImportableItemKind::GlobalVariable,
+        )),
+        ast::Item::GlobalConstantDeclaration(constant) => Some((
+            constant.binding()?.name()?,
+            ImportableItemKind::GlobalConstant,
+        )),
+        ast::Item::OverrideDeclaration(override_declaration) => Some((
+            override_declaration.binding()?.name()?,
+            ImportableItemKind::Override,
+        )),
+        ast::Item::TypeAliasDeclaration(type_alias) => {
+            Some((type_alias.name()?, ImportableItemKind::TypeAlias))
+        },
+        ast::Item::Import(_) => None,
+    }
+}
@@ -5,6 +5,7 @@ pub mod data;
 pub mod db;
 pub mod expression;
 pub mod hir_file_id;
+pub mod import_index;
 pub mod module_data;
 pub mod resolver;
 pub mod type_ref;
@@ -95,6 +96,12 @@ impl HasTextRange for SyntaxNode {
     }
 }
 
+impl HasTextRange for TextRange {
+    fn text_range(&self) -> TextRange {
+        *self
+    }
+}
+
 impl<N: HasTextRange, T: HasTextRange> HasTextRange for NodeOrToken<N, T> {
     fn text_range(&self) -> TextRange {
         match self {
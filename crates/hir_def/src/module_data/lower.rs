@@ -1,7 +1,8 @@
 use crate::HirFileId;
-use crate::hir_file_id::relative_file;
+use crate::hir_file_id::{parse_custom_import, relative_file};
 use crate::module_data::{Function, ModuleData, ModuleItem, ModuleItemId, Parameter};
 use crate::{ast_id::AstIdMap, db::DefDatabase, type_ref::TypeReference};
+use base_db::is_configured;
 use la_arena::{Idx, IdxRange};
 use std::sync::Arc;
 
@@ -11,7 +12,8 @@ use syntax::{
 };
 
 use super::{
-    Field, GlobalConstant, GlobalVariable, Import, ImportValue, Name, Override, Struct, TypeAlias,
+    Field, GlobalConstant, GlobalVariable, Import, ImportItem, ImportValue, Name, Override, Struct,
+    TypeAlias,
 };
 
 pub(crate) struct Ctx<'a> {
@@ -40,9 +42,13 @@ impl<'a> Ctx<'a> {
         &mut self,
         source_file: SourceFile,
     ) {
-        source_file.items().for_each(|item| {
-            self.lower_item(item);
-        })
+        let shader_defs = self.db.shader_defs();
+        source_file
+            .items()
+            .filter(|item| is_configured(item, &shader_defs))
+            .for_each(|item| {
+                self.lower_item(item);
+            })
     }
 
     fn lower_item(
@@ -76,6 +82,8 @@ impl<'a> Ctx<'a> {
     ) -> Option<ModuleItemId<Import>> {
         let ast_id = self.source_ast_id_map.ast_id(import);
 
+        let mut alias = None;
+        let mut items = None;
         let value = match import.import()? {
             ast::ImportKind::ImportPath(path) => {
                 let import_path = path
@@ -86,10 +94,32 @@ impl<'a> Ctx<'a> {
                     .collect();
                 ImportValue::Path(import_path)
             },
-            ast::ImportKind::ImportCustom(custom) => ImportValue::Custom(custom.key()),
+            ast::ImportKind::ImportCustom(custom) => {
+                alias = custom.alias().map(Name::from);
+                items = custom.item_list().map(|list| {
+                    list.items()
+                        .filter_map(|item| {
+                            Some(ImportItem {
+                                name: Name::from(item.name_ref()?),
+                                alias: item.alias().map(Name::from),
+                            })
+                        })
+                        .collect()
+                });
+                if import.import_keyword_token().is_some() {
+                    ImportValue::Wesl(custom.key())
+                } else {
+                    ImportValue::Custom(custom.key())
+                }
+            },
         };
 
-        let import = Import { value, ast_id };
+        let import = Import {
+            value,
+            alias,
+            items,
+            ast_id,
+        };
 
         Some(self.module_data.imports.alloc(import).into())
     }
@@ -210,9 +240,11 @@ impl<'a> Ctx<'a> {
         let ast_id = self.source_ast_id_map.ast_id(r#struct);
 
         let start_field = self.next_field_index();
+        let shader_defs = self.db.shader_defs();
         r#struct
             .body()?
             .fields()
+            .filter(|field| is_configured(field, &shader_defs))
             .map(|field| {
                 let declaration = field.variable_ident_declaration()?;
                 let name = Name::from(declaration.binding()?.name()?);
@@ -267,7 +299,11 @@ impl<'a> Ctx<'a> {
         &mut self,
         function_param_list: ast::ParameterList,
     ) -> Option<()> {
-        for parameter in function_param_list.parameters() {
+        let shader_defs = self.db.shader_defs();
+        for parameter in function_param_list
+            .parameters()
+            .filter(|parameter| is_configured(parameter, &shader_defs))
+        {
             if let Some(parameter) = parameter.variable_ident_declaration() {
                 let r#type = parameter
                     .ty()
@@ -290,9 +326,13 @@ impl<'a> Ctx<'a> {
                         let file_id = relative_file(self.db, self.file_id, path)?;
                         Ok(self.db.parse(file_id))
                     },
-                    crate::module_data::ImportValue::Custom(key) => self
-                        .db
-                        .parse_import(key.clone(), syntax::ParseEntryPoint::FunctionParameterList),
+                    crate::module_data::ImportValue::Custom(key) => parse_custom_import(
+                        self.db,
+                        key,
+                        syntax::ParseEntryPoint::FunctionParameterList,
+                    ),
+                    // WESL imports are items, not parameters
+                    crate::module_data::ImportValue::Wesl(_) => Err(()),
                 };
                 if let Ok(parse) = parse {
                     let param_list = ast::ParameterList::cast(parse.syntax())?;
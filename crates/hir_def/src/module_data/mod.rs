@@ -31,6 +31,12 @@ impl Name {
     pub fn as_str(&self) -> &str {
         &self.0
     }
+
+    /// Splits a qualified name like `module::item` into `("module", "item")`.
+    pub fn split_qualifier(&self) -> Option<(&str, Name)> {
+        let (qualifier, name) = self.0.rsplit_once("::")?;
+        Some((qualifier, Name::from(name)))
+    }
 }
 
 impl AsRef<str> for Name {
@@ -53,7 +59,7 @@ impl From<ast::Name> for Name {
 
 impl From<ast::NameReference> for Name {
     fn from(name: ast::NameReference) -> Self {
-        Name(name.text().as_str().into())
+        Name(name.path().into())
     }
 }
 
@@ -132,14 +138,93 @@ pub struct Directive;
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Import {
     pub value: ImportValue,
+    /// The `c` of `#import a::b as c`
+    pub alias: Option<Name>,
+    /// The `{x, y as z}` of `#import a::b::{x, y as z}`
+    pub items: Option<Vec<ImportItem>>,
     pub ast_id: FileAstId<ast::Import>,
 }
 
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ImportItem {
+    pub name: Name,
+    pub alias: Option<Name>,
+}
+
+impl ImportItem {
+    /// The name under which the item is visible in the importing module.
+    pub fn visible_name(&self) -> &Name {
+        self.alias.as_ref().unwrap_or(&self.name)
+    }
+}
+
+impl Import {
+    /// Whether `qualifier` names this import's module, as in `qualifier::item`.
+    ///
+    /// A module can always be referred to by its full path. Unless the import lists
+    /// individual items, it can also be referred to by its alias, or the last segment of its path
+    /// if it has no alias.
+    pub fn matches_qualifier(
+        &self,
+        qualifier: &str,
+    ) -> bool {
+        let (ImportValue::Custom(key) | ImportValue::Wesl(key)) = &self.value else {
+            return false;
+        };
+        if key == qualifier {
+            return true;
+        }
+        if self.items.is_some() {
+            return false;
+        }
+        match &self.alias {
+            Some(alias) => alias.as_str() == qualifier,
+            None => key.rsplit("::").next() == Some(qualifier),
+        }
+    }
+
+    /// Maps a name used in the importing module to the name of the item in the imported module.
+    pub fn resolve_unqualified(
+        &self,
+        name: &Name,
+    ) -> Option<Name> {
+        match &self.items {
+            Some(items) => items
+                .iter()
+                .find(|item| item.visible_name() == name)
+                .map(|item| item.name.clone()),
+            // `#import a::b as c` only makes the items available as `c::item`
+            None if self.alias.is_some() => None,
+            None => Some(name.clone()),
+        }
+    }
+
+    /// The names under which an item of the imported module is visible in the importing module.
+    pub fn visible_names(
+        &self,
+        name: &Name,
+    ) -> Vec<Name> {
+        match (&self.items, &self.alias) {
+            (Some(items), _) => items
+                .iter()
+                .filter(|item| item.name == *name)
+                .map(|item| item.visible_name().clone())
+                .collect(),
+            (None, Some(alias)) => vec![Name::from(
+                format!("{}::{}", alias.as_str(), name.as_str()).as_str(),
+            )],
+            (None, None) => vec![name.clone()],
+        }
+    }
+}
+
 // PERF: maybe intern string
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum ImportValue {
     Path(String),
     Custom(String),
+    /// A WESL `import` of a module path, like `package::util`
+    Wesl(String),
 }
 
 #[derive(Debug, Default, Eq, PartialEq)]
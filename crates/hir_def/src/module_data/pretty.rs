@@ -91,7 +91,24 @@ fn write_pretty_module_item(
             let _ = match &import.value {
                 ImportValue::Path(path) => write!(f, "#import \"{}\"", path),
                 ImportValue::Custom(key) => write!(f, "#import {}", key),
+                ImportValue::Wesl(path) => write!(f, "import {}", path),
             };
+            if let Some(items) = &import.items {
+                let items: Vec<_> = items
+                    .iter()
+                    .map(|item| match &item.alias {
+                        Some(alias) => format!("{} as {}", item.name.0, alias.0),
+                        None => item.name.0.to_string(),
+                    })
+                    .collect();
+                let _ = write!(f, "::{{{}}}", items.join(", "));
+            }
+            if let Some(alias) = &import.alias {
+                let _ = write!(f, " as {}", alias.0);
+            }
+            if matches!(import.value, ImportValue::Wesl(_)) {
+                let _ = write!(f, ";");
+            }
         },
         ModuleItem::TypeAlias(type_alias) => {
             let type_alias = &module.data[type_alias.index];
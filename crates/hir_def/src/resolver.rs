@@ -8,15 +8,45 @@ use crate::{
         BindingId,
         scope::{ExprScopes, ScopeId},
     },
-    db::{DefDatabase, FunctionId, Location},
-    hir_file_id::ImportFile,
+    db::{DefDatabase, FunctionId, ImportId, Location},
+    hir_file_id::{ImportFile, resolve_wesl_import},
     module_data::{
-        Function, GlobalConstant, GlobalVariable, ModuleInfo, ModuleItem, Name, Override, Struct,
-        TypeAlias,
+        Function, GlobalConstant, GlobalVariable, Import, ImportItem, ImportValue, ModuleInfo,
+        ModuleItem, Name, Override, Struct, TypeAlias,
     },
     type_ref::{TypeReference, VecDimensionality, VecType},
 };
 
+/// WESL imports never make all items of a module visible unqualified:
+/// a path ending in an item imports just that item, and a path naming a module
+/// makes its items visible as `module::item`.
+fn normalize_wesl_import(
+    db: &dyn DefDatabase,
+    file_id: HirFileId,
+    mut import: Import,
+) -> Import {
+    let ImportValue::Wesl(path) = &import.value else {
+        return import;
+    };
+    if import.items.is_some() {
+        return import;
+    }
+    match resolve_wesl_import(db, file_id, path).and_then(|(_, item)| item) {
+        Some(item) => {
+            import.items = Some(vec![ImportItem {
+                name: item,
+                alias: import.alias.take(),
+            }]);
+        },
+        None => {
+            if import.alias.is_none() {
+                import.alias = path.rsplit("::").next().map(Name::from);
+            }
+        },
+    }
+    import
+}
+
 #[derive(Clone)]
 pub enum Scope {
     /// The items inside a module
@@ -31,6 +61,36 @@ pub enum Scope {
 pub struct ModuleScope {
     module_info: Arc<ModuleInfo>,
     file_id: HirFileId,
+    /// The import through which this module is visible, `None` for the module being resolved in
+    import: Option<Import>,
+}
+
+impl ModuleScope {
+    /// Maps a name used in the resolving module to the name of an item of this module.
+    fn local_name(
+        &self,
+        name: &Name,
+    ) -> Option<Name> {
+        match (&self.import, name.split_qualifier()) {
+            (None, None) => Some(name.clone()),
+            (None, Some(_)) => None,
+            (Some(import), None) => import.resolve_unqualified(name),
+            (Some(import), Some((qualifier, name))) => {
+                import.matches_qualifier(qualifier).then_some(name)
+            },
+        }
+    }
+
+    /// The names under which an item of this module is visible in the resolving module.
+    fn visible_names(
+        &self,
+        name: &Name,
+    ) -> Vec<Name> {
+        match &self.import {
+            Some(import) => import.visible_names(name),
+            None => vec![name.clone()],
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -95,26 +155,64 @@ impl Resolver {
 
     #[must_use]
     pub fn push_module_scope(
+        self,
+        db: &dyn DefDatabase,
+        file_id: HirFileId,
+        module_info: Arc<ModuleInfo>,
+    ) -> Resolver {
+        self.push_module_scope_inner(db, file_id, module_info, None, &mut Vec::new())
+    }
+
+    fn push_module_scope_inner(
         mut self,
         db: &dyn DefDatabase,
         file_id: HirFileId,
         module_info: Arc<ModuleInfo>,
+        via_import: Option<Import>,
+        visited: &mut Vec<ImportId>,
     ) -> Resolver {
+        // Only whole-module imports without an alias behave like textual inclusion,
+        // which makes the imports of the imported module visible as well
+        let is_transitive = via_import
+            .as_ref()
+            .is_none_or(|import| import.items.is_none() && import.alias.is_none());
+
         for item in module_info.items() {
             if let ModuleItem::Import(import) = item {
+                if !is_transitive {
+                    break;
+                }
                 let loc = Location::new(file_id, *import);
                 let import_id = db.intern_import(loc);
+                // Import cycles are an error, but must not send us into an endless loop
+                if visited.contains(&import_id) {
+                    continue;
+                }
+                visited.push(import_id);
                 let import_file = HirFileId::from(ImportFile { import_id });
                 let import_module_info = db.module_info(import_file);
+                let import = normalize_wesl_import(db, file_id, module_info.get(*import).clone());
                 // If we can find the original source file for this import, push its scope
                 if let Some(original_file_id) = import_file.original_file(db) {
                     let original_file_id = HirFileId::from(original_file_id);
-                    self = self.push_module_scope(db, original_file_id, import_module_info);
+                    self = self.push_module_scope_inner(
+                        db,
+                        original_file_id,
+                        import_module_info,
+                        Some(import),
+                        visited,
+                    );
                 } else {
                     info!("Failed to resolve import file for {file_id:?}");
                     // This import might be a custom import without a direct file
                     // For these cases, we'll use the imported module info with the original file ID
-                    self = self.push_module_scope(db, file_id, import_module_info);
+                    self = self.push_module_scope_inner(
+                        db,
+                        file_id,
+                        import_module_info,
+                        Some(import),
+                        visited,
+                    );
                     info!("Using module_info for import without resolving to a file: {file_id:?}");
                 }
             }
@@ -123,6 +221,7 @@ impl Resolver {
         self.scopes.push(Scope::ModuleScope(ModuleScope {
             module_info,
             file_id,
+            import: via_import,
         }));
         self
     }
@@ -160,31 +259,26 @@ impl Resolver {
     ) {
         self.scopes().for_each(|scope| match scope {
             Scope::ModuleScope(scope) => {
-                scope
-                    .module_info
-                    .items()
-                    .iter()
-                    .for_each(|item| match item {
-                        ModuleItem::Function(func) => f(
-                            scope.module_info.data[func.index].name.clone(),
-                            ScopeDef::ModuleItem(scope.file_id, *item),
-                        ),
-                        ModuleItem::GlobalVariable(var) => f(
-                            scope.module_info.data[var.index].name.clone(),
-                            ScopeDef::ModuleItem(scope.file_id, *item),
-                        ),
-                        ModuleItem::GlobalConstant(constant) => f(
-                            scope.module_info.data[constant.index].name.clone(),
-                            ScopeDef::ModuleItem(scope.file_id, *item),
-                        ),
-                        ModuleItem::Override(override_decl) => f(
-                            scope.module_info.data[override_decl.index].name.clone(),
-                            ScopeDef::ModuleItem(scope.file_id, *item),
-                        ),
-                        ModuleItem::Struct(_) => {},
-                        ModuleItem::Import(_) => {},
-                        ModuleItem::TypeAlias(_) => {},
-                    });
+                for item in scope.module_info.items() {
+                    let name = match item {
+                        ModuleItem::Function(func) => &scope.module_info.data[func.index].name,
+                        ModuleItem::GlobalVariable(var) => &scope.module_info.data[var.index].name,
+                        ModuleItem::GlobalConstant(constant) => {
+                            &scope.module_info.data[constant.index].name
+                        },
+                        ModuleItem::Override(override_decl) => {
+                            &scope.module_info.data[override_decl.index].name
+                        },
+                        ModuleItem::Struct(_)
+                        | ModuleItem::Import(_)
+                        | ModuleItem::TypeAlias(_) => {
+                            continue;
+                        },
+                    };
+                    for name in scope.visible_names(name) {
+                        f(name, ScopeDef::ModuleItem(scope.file_id, *item));
+                    }
+                }
             },
             Scope::ExprScope(expression_scope) => {
                 expression_scope
@@ -201,6 +295,67 @@ impl Resolver {
         });
     }
 
+    /// Calls `f` for the user-defined types in scope, i.e. structs and type aliases.
+    pub fn process_type_names(
+        &self,
+        mut f: impl FnMut(Name, ScopeDef),
+    ) {
+        self.scopes().for_each(|scope| {
+            let Scope::ModuleScope(scope) = scope else {
+                return;
+            };
+            for item in scope.module_info.items() {
+                let name = match item {
+                    ModuleItem::Struct(r#struct) => &scope.module_info.data[r#struct.index].name,
+                    ModuleItem::TypeAlias(type_alias) => {
+                        &scope.module_info.data[type_alias.index].name
+                    },
+                    ModuleItem::Function(_)
+                    | ModuleItem::GlobalVariable(_)
+                    | ModuleItem::GlobalConstant(_)
+                    | ModuleItem::Override(_)
+                    | ModuleItem::Import(_) => continue,
+                };
+                for name in scope.visible_names(name) {
+                    f(name, ScopeDef::ModuleItem(scope.file_id, *item));
+                }
+            }
+        });
+    }
+
+    /// All items the name may refer to in the module and its imports, from the innermost scope
+    /// outwards. The first one is the item the name resolves to when several modules declare it.
+    pub fn resolve_module_items(
+        &self,
+        name: &Name,
+    ) -> Vec<(HirFileId, ModuleItem)> {
+        let mut items = Vec::new();
+        for scope in self.scopes() {
+            let Scope::ModuleScope(scope) = scope else {
+                continue;
+            };
+            let Some(name) = scope.local_name(name) else {
+                continue;
+            };
+            for item in scope.module_info.items() {
+                let item_name = match *item {
+                    ModuleItem::Function(id) => &scope.module_info.get(id).name,
+                    ModuleItem::Struct(id) => &scope.module_info.get(id).name,
+                    ModuleItem::GlobalVariable(id) => &scope.module_info.get(id).name,
+                    ModuleItem::GlobalConstant(id) => &scope.module_info.get(id).name,
+                    ModuleItem::Override(id) => &scope.module_info.get(id).name,
+                    ModuleItem::TypeAlias(id) => &scope.module_info.get(id).name,
+                    ModuleItem::Import(_) => continue,
+                };
+                let item = (scope.file_id, *item);
+                if *item_name == name && !items.contains(&item) {
+                    items.push(item);
+                }
+            }
+        }
+        items
+    }
+
     pub fn resolve_value(
         &self,
         name: &Name,
@@ -214,6 +369,7 @@ impl Resolver {
                     Some(ResolveValue::Local(entry.binding))
                 },
                 Scope::ModuleScope(scope) => {
+                    let name = &scope.local_name(name)?;
                     scope
                         .module_info
                         .items()
@@ -254,6 +410,7 @@ impl Resolver {
     ) -> Option<ResolveType> {
         self.scopes().find_map(|scope| match scope {
             Scope::ModuleScope(scope) => {
+                let name = &scope.local_name(name)?;
                 scope
                     .module_info
                     .items()
@@ -287,6 +444,7 @@ impl Resolver {
     ) -> Option<ResolveCallable> {
         self.scopes().find_map(|scope| match scope {
             Scope::ModuleScope(scope) => {
+                let name = &scope.local_name(name)?;
                 scope
                     .module_info
                     .items()
@@ -41,7 +41,7 @@ impl TryFrom<ast::Type> for TypeReference {
 
     fn try_from(r#type: ast::Type) -> Result<Self, ()> {
         let type_ref = match r#type {
-            ast::Type::PathType(path) => TypeReference::Path(path.name().ok_or(())?.text().into()),
+            ast::Type::PathType(path) => TypeReference::Path(path.name().ok_or(())?.into()),
             ast::Type::ScalarType(scalar) => TypeReference::Scalar(scalar.into()),
             ast::Type::VecType(vec) => TypeReference::Vec(vec.try_into()?),
             ast::Type::MatrixType(matrix) => TypeReference::Matrix(matrix.try_into()?),
@@ -76,6 +76,43 @@ impl Builtin {
     ) -> &BuiltinOverload {
         &self.overloads[overload_id.0]
     }
+
+    /// The sections of the builtin functions chapter of the WGSL specification describing the
+    /// builtin, including their headings.
+    pub fn documentation(&self) -> Option<String> {
+        let mut sections = Vec::new();
+        let mut in_section = false;
+        for line in SPECIFICATION.lines() {
+            if let Some(title) = section_title(line) {
+                in_section = title == self.name()
+                    || title
+                        .strip_prefix(self.name())
+                        .is_some_and(|rest| rest.starts_with(" ("));
+                if in_section {
+                    sections.push(String::new());
+                }
+            }
+            if let Some(section) = sections.last_mut().filter(|_| in_section) {
+                section.push_str(line.trim_end());
+                section.push('\n');
+            }
+        }
+        (!sections.is_empty()).then(|| sections.join("\n"))
+    }
+}
+
+/// The chapter of the WGSL specification about builtin functions.
+const SPECIFICATION: &str = include_str!("../builtins.txt");
+
+/// The title of a numbered section heading of the specification, like `17.5.1. abs`.
+fn section_title(line: &str) -> Option<&str> {
+    let (number, title) = line.split_once(' ')?;
+    let is_number = number.ends_with('.')
+        && number.starts_with("17.")
+        && number
+            .chars()
+            .all(|character| character.is_ascii_digit() || character == '.');
+    is_number.then_some(title)
 }
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone)]
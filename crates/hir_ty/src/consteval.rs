@@ -0,0 +1,436 @@
+//! Evaluation of the initializers of `const` and `override` declarations.
+//!
+//! Only scalar values are supported. Integer and float literals are evaluated as abstract
+//! values, which become concrete when they meet a concrete operand, a conversion or the
+//! declared type.
+
+use std::fmt;
+
+use either::Either;
+use hir_def::{
+    body::Body,
+    db::DefinitionWithBodyId,
+    expression::{
+        ArithmeticOperation, BinaryOperation, BuiltinFloat, BuiltinInt, Callee,
+        ComparisonOperation, Expression, ExpressionId, Literal, LogicOperation, Ordering,
+        UnaryOperator,
+    },
+    resolver::{ResolveValue, Resolver},
+    type_ref::{ScalarType, TypeReference},
+};
+use rustc_hash::FxHashMap;
+
+use crate::db::HirDatabase;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ConstValue {
+    Bool(bool),
+    AbstractInt(i64),
+    AbstractFloat(f64),
+    I32(i32),
+    U32(u32),
+    F32(f32),
+}
+
+impl fmt::Display for ConstValue {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match *self {
+            ConstValue::Bool(value) => write!(f, "{value}"),
+            ConstValue::AbstractInt(value) => write!(f, "{value}"),
+            ConstValue::AbstractFloat(value) => write!(f, "{value:?}"),
+            ConstValue::I32(value) => write!(f, "{value}i"),
+            ConstValue::U32(value) => write!(f, "{value}u"),
+            ConstValue::F32(value) => write!(f, "{value:?}f"),
+        }
+    }
+}
+
+impl ConstValue {
+    const fn scalar_type(self) -> Option<ScalarType> {
+        match self {
+            ConstValue::Bool(_) => Some(ScalarType::Bool),
+            ConstValue::I32(_) => Some(ScalarType::Int32),
+            ConstValue::U32(_) => Some(ScalarType::Uint32),
+            ConstValue::F32(_) => Some(ScalarType::Float32),
+            ConstValue::AbstractInt(_) | ConstValue::AbstractFloat(_) => None,
+        }
+    }
+
+    /// The value converted like `T(value)` would. Integers which do not fit into the type are
+    /// an error, floats are truncated and saturated.
+    pub fn convert(
+        self,
+        to: ScalarType,
+    ) -> Option<ConstValue> {
+        let value = match (self, to) {
+            (ConstValue::Bool(value), ScalarType::Bool) => ConstValue::Bool(value),
+            (ConstValue::Bool(value), ScalarType::Int32) => ConstValue::I32(i32::from(value)),
+            (ConstValue::Bool(value), ScalarType::Uint32) => ConstValue::U32(u32::from(value)),
+            (ConstValue::Bool(value), ScalarType::Float32) => {
+                ConstValue::F32(if value { 1.0 } else { 0.0 })
+            },
+            (ConstValue::AbstractInt(value), ScalarType::Bool) => ConstValue::Bool(value != 0),
+            (ConstValue::AbstractInt(value), ScalarType::Int32) => {
+                ConstValue::I32(i32::try_from(value).ok()?)
+            },
+            (ConstValue::AbstractInt(value), ScalarType::Uint32) => {
+                ConstValue::U32(u32::try_from(value).ok()?)
+            },
+            (ConstValue::AbstractInt(value), ScalarType::Float32) => ConstValue::F32(value as f32),
+            (ConstValue::I32(value), ScalarType::Bool) => ConstValue::Bool(value != 0),
+            (ConstValue::I32(value), ScalarType::Int32) => ConstValue::I32(value),
+            (ConstValue::I32(value), ScalarType::Uint32) => ConstValue::U32(value as u32),
+            (ConstValue::I32(value), ScalarType::Float32) => ConstValue::F32(value as f32),
+            (ConstValue::U32(value), ScalarType::Bool) => ConstValue::Bool(value != 0),
+            (ConstValue::U32(value), ScalarType::Int32) => ConstValue::I32(value as i32),
+            (ConstValue::U32(value), ScalarType::Uint32) => ConstValue::U32(value),
+            (ConstValue::U32(value), ScalarType::Float32) => ConstValue::F32(value as f32),
+            (ConstValue::AbstractFloat(value), ScalarType::Bool) => ConstValue::Bool(value != 0.0),
+            (ConstValue::AbstractFloat(value), ScalarType::Int32) => ConstValue::I32(value as i32),
+            (ConstValue::AbstractFloat(value), ScalarType::Uint32) => ConstValue::U32(value as u32),
+            (ConstValue::AbstractFloat(value), ScalarType::Float32) => {
+                ConstValue::F32(value as f32)
+            },
+            (ConstValue::F32(value), ScalarType::Bool) => ConstValue::Bool(value != 0.0),
+            (ConstValue::F32(value), ScalarType::Int32) => ConstValue::I32(value as i32),
+            (ConstValue::F32(value), ScalarType::Uint32) => ConstValue::U32(value as u32),
+            (ConstValue::F32(value), ScalarType::Float32) => ConstValue::F32(value),
+        };
+        value.is_finite().then_some(value)
+    }
+
+    const fn is_finite(self) -> bool {
+        match self {
+            ConstValue::AbstractFloat(value) => value.is_finite(),
+            ConstValue::F32(value) => value.is_finite(),
+            ConstValue::Bool(_)
+            | ConstValue::AbstractInt(_)
+            | ConstValue::I32(_)
+            | ConstValue::U32(_) => true,
+        }
+    }
+}
+
+/// The value of the initializer of a `const` declaration, or the default value of an
+/// `override` declaration.
+pub fn eval(
+    db: &dyn HirDatabase,
+    def: DefinitionWithBodyId,
+) -> Option<ConstValue> {
+    ConstEvaluator {
+        db,
+        values: FxHashMap::default(),
+    }
+    .eval_definition(def)
+}
+
+struct ConstEvaluator<'db> {
+    db: &'db dyn HirDatabase,
+    /// Each definition is only evaluated once, and `None` while it is being evaluated, so that
+    /// cyclic declarations do not have a value.
+    values: FxHashMap<DefinitionWithBodyId, Option<ConstValue>>,
+}
+
+impl ConstEvaluator<'_> {
+    fn eval_definition(
+        &mut self,
+        def: DefinitionWithBodyId,
+    ) -> Option<ConstValue> {
+        if let Some(value) = self.values.get(&def) {
+            return *value;
+        }
+        self.values.insert(def, None);
+        let value = self.eval_definition_uncached(def);
+        self.values.insert(def, value);
+        value
+    }
+
+    fn eval_definition_uncached(
+        &mut self,
+        def: DefinitionWithBodyId,
+    ) -> Option<ConstValue> {
+        let declared_type = match def {
+            DefinitionWithBodyId::GlobalConstant(id) => self.db.global_constant_data(id).r#type,
+            DefinitionWithBodyId::Override(id) => self.db.override_data(id).r#type,
+            DefinitionWithBodyId::Function(_) | DefinitionWithBodyId::GlobalVariable(_) => {
+                return None;
+            },
+        };
+        let body = self.db.body(def);
+        let Some(Either::Right(root)) = body.root else {
+            return None;
+        };
+        let resolver = def.resolver(self.db.upcast());
+        let value = self.eval_expression(&body, &resolver, root)?;
+        match declared_type.map(|r#type| self.db.lookup_intern_type_ref(r#type)) {
+            Some(TypeReference::Scalar(scalar)) => value.convert(scalar),
+            _ => Some(value),
+        }
+    }
+
+    fn eval_expression(
+        &mut self,
+        body: &Body,
+        resolver: &Resolver,
+        expression: ExpressionId,
+    ) -> Option<ConstValue> {
+        match &body.exprs[expression] {
+            Expression::Literal(literal) => Some(match *literal {
+                Literal::Int(value, BuiltinInt::Abstract) => ConstValue::AbstractInt(value),
+                Literal::Int(value, BuiltinInt::I32) => ConstValue::I32(i32::try_from(value).ok()?),
+                Literal::Uint(value, _) => ConstValue::U32(u32::try_from(value).ok()?),
+                Literal::Float(bits, BuiltinFloat::Abstract) => {
+                    ConstValue::AbstractFloat(f64::from_bits(bits))
+                },
+                Literal::Float(bits, BuiltinFloat::F32) => {
+                    ConstValue::F32(f64::from_bits(bits) as f32)
+                },
+                // `f16` values are not evaluated.
+                Literal::Float(_, BuiltinFloat::F16) => return None,
+                Literal::Bool(value) => ConstValue::Bool(value),
+            }),
+            Expression::UnaryOperator { expression, op } => {
+                let value = self.eval_expression(body, resolver, *expression)?;
+                unary_operation(*op, value)
+            },
+            Expression::BinaryOperation {
+                left_side,
+                right_side,
+                operation,
+            } => {
+                let left = self.eval_expression(body, resolver, *left_side)?;
+                let right = self.eval_expression(body, resolver, *right_side)?;
+                binary_operation(*operation, left, right)
+            },
+            Expression::Call {
+                callee: Callee::Type(r#type),
+                arguments,
+            } => {
+                let TypeReference::Scalar(scalar) = self.db.lookup_intern_type_ref(*r#type) else {
+                    return None;
+                };
+                let &[argument] = &arguments[..] else {
+                    return None;
+                };
+                self.eval_expression(body, resolver, argument)?
+                    .convert(scalar)
+            },
+            Expression::Path(name) => {
+                let def = match resolver.resolve_value(name)? {
+                    ResolveValue::GlobalConstant(loc) => {
+                        DefinitionWithBodyId::GlobalConstant(self.db.intern_global_constant(loc))
+                    },
+                    ResolveValue::Override(loc) => {
+                        DefinitionWithBodyId::Override(self.db.intern_override(loc))
+                    },
+                    ResolveValue::Local(_) | ResolveValue::GlobalVariable(_) => return None,
+                };
+                self.eval_definition(def)
+            },
+            Expression::Missing
+            | Expression::Field { .. }
+            | Expression::Call { .. }
+            | Expression::Index { .. }
+            | Expression::Bitcast { .. } => None,
+        }
+    }
+}
+
+fn unary_operation(
+    op: UnaryOperator,
+    value: ConstValue,
+) -> Option<ConstValue> {
+    let value = match (op, value) {
+        (UnaryOperator::Minus, ConstValue::AbstractInt(value)) => {
+            ConstValue::AbstractInt(value.checked_neg()?)
+        },
+        (UnaryOperator::Minus, ConstValue::I32(value)) => ConstValue::I32(value.checked_neg()?),
+        (UnaryOperator::Minus, ConstValue::AbstractFloat(value)) => {
+            ConstValue::AbstractFloat(-value)
+        },
+        (UnaryOperator::Minus, ConstValue::F32(value)) => ConstValue::F32(-value),
+        (UnaryOperator::Not, ConstValue::Bool(value)) => ConstValue::Bool(!value),
+        (UnaryOperator::BitNot, ConstValue::AbstractInt(value)) => ConstValue::AbstractInt(!value),
+        (UnaryOperator::BitNot, ConstValue::I32(value)) => ConstValue::I32(!value),
+        (UnaryOperator::BitNot, ConstValue::U32(value)) => ConstValue::U32(!value),
+        _ => return None,
+    };
+    Some(value)
+}
+
+fn binary_operation(
+    operation: BinaryOperation,
+    left: ConstValue,
+    right: ConstValue,
+) -> Option<ConstValue> {
+    match operation {
+        BinaryOperation::Arithmetic(
+            op @ (ArithmeticOperation::ShiftLeft | ArithmeticOperation::ShiftRight),
+        ) => shift(op, left, right),
+        BinaryOperation::Arithmetic(op) => {
+            let (left, right) = unify(left, right)?;
+            let value = arithmetic(op, left, right)?;
+            value.is_finite().then_some(value)
+        },
+        BinaryOperation::Logical(op) => match (left, right) {
+            (ConstValue::Bool(left), ConstValue::Bool(right)) => Some(ConstValue::Bool(match op {
+                LogicOperation::And => left && right,
+                LogicOperation::Or => left || right,
+            })),
+            _ => None,
+        },
+        BinaryOperation::Comparison(op) => {
+            let (left, right) = unify(left, right)?;
+            let ordering = compare(left, right)?;
+            Some(ConstValue::Bool(match op {
+                ComparisonOperation::Equality { negated } => ordering.is_eq() != negated,
+                ComparisonOperation::Ordering {
+                    ordering: Ordering::Less,
+                    strict,
+                } => ordering.is_lt() || (!strict && ordering.is_eq()),
+                ComparisonOperation::Ordering {
+                    ordering: Ordering::Greater,
+                    strict,
+                } => ordering.is_gt() || (!strict && ordering.is_eq()),
+            }))
+        },
+    }
+}
+
+/// Converts an abstract operand to the type of the other operand.
+fn unify(
+    left: ConstValue,
+    right: ConstValue,
+) -> Option<(ConstValue, ConstValue)> {
+    match (left, right) {
+        (ConstValue::AbstractInt(_), ConstValue::AbstractInt(_))
+        | (ConstValue::AbstractFloat(_), ConstValue::AbstractFloat(_)) => Some((left, right)),
+        (ConstValue::AbstractInt(left), ConstValue::AbstractFloat(_)) => {
+            Some((ConstValue::AbstractFloat(left as f64), right))
+        },
+        (ConstValue::AbstractFloat(_), ConstValue::AbstractInt(right)) => {
+            Some((left, ConstValue::AbstractFloat(right as f64)))
+        },
+        (ConstValue::AbstractInt(_) | ConstValue::AbstractFloat(_), _) => {
+            Some((left.convert(right.scalar_type()?)?, right))
+        },
+        (_, ConstValue::AbstractInt(_) | ConstValue::AbstractFloat(_)) => {
+            Some((left, right.convert(left.scalar_type()?)?))
+        },
+        _ => Some((left, right)),
+    }
+}
+
+fn arithmetic(
+    op: ArithmeticOperation,
+    left: ConstValue,
+    right: ConstValue,
+) -> Option<ConstValue> {
+    let value = match (left, right) {
+        (ConstValue::AbstractInt(left), ConstValue::AbstractInt(right)) => {
+            ConstValue::AbstractInt(integer_arithmetic(op, left, right)?)
+        },
+        (ConstValue::I32(left), ConstValue::I32(right)) => {
+            ConstValue::I32(i32::try_from(integer_arithmetic(op, left.into(), right.into())?).ok()?)
+        },
+        (ConstValue::U32(left), ConstValue::U32(right)) => {
+            ConstValue::U32(u32::try_from(integer_arithmetic(op, left.into(), right.into())?).ok()?)
+        },
+        (ConstValue::AbstractFloat(left), ConstValue::AbstractFloat(right)) => {
+            ConstValue::AbstractFloat(float_arithmetic(op, left, right)?)
+        },
+        (ConstValue::F32(left), ConstValue::F32(right)) => {
+            ConstValue::F32(float_arithmetic(op, left.into(), right.into())? as f32)
+        },
+        (ConstValue::Bool(left), ConstValue::Bool(right)) => ConstValue::Bool(match op {
+            ArithmeticOperation::BitAnd => left & right,
+            ArithmeticOperation::BitOr => left | right,
+            _ => return None,
+        }),
+        _ => return None,
+    };
+    Some(value)
+}
+
+fn integer_arithmetic(
+    op: ArithmeticOperation,
+    left: i64,
+    right: i64,
+) -> Option<i64> {
+    match op {
+        ArithmeticOperation::Add => left.checked_add(right),
+        ArithmeticOperation::Subtract => left.checked_sub(right),
+        ArithmeticOperation::Multiply => left.checked_mul(right),
+        ArithmeticOperation::Divide => left.checked_div(right),
+        ArithmeticOperation::Modulo => left.checked_rem(right),
+        ArithmeticOperation::BitAnd => Some(left & right),
+        ArithmeticOperation::BitOr => Some(left | right),
+        ArithmeticOperation::BitXor => Some(left ^ right),
+        ArithmeticOperation::ShiftLeft | ArithmeticOperation::ShiftRight => None,
+    }
+}
+
+fn float_arithmetic(
+    op: ArithmeticOperation,
+    left: f64,
+    right: f64,
+) -> Option<f64> {
+    match op {
+        ArithmeticOperation::Add => Some(left + right),
+        ArithmeticOperation::Subtract => Some(left - right),
+        ArithmeticOperation::Multiply => Some(left * right),
+        ArithmeticOperation::Divide => Some(left / right),
+        ArithmeticOperation::Modulo => Some(left % right),
+        ArithmeticOperation::BitAnd
+        | ArithmeticOperation::BitOr
+        | ArithmeticOperation::BitXor
+        | ArithmeticOperation::ShiftLeft
+        | ArithmeticOperation::ShiftRight => None,
+    }
+}
+
+fn shift(
+    op: ArithmeticOperation,
+    left: ConstValue,
+    right: ConstValue,
+) -> Option<ConstValue> {
+    let amount = match right {
+        ConstValue::AbstractInt(amount) => u32::try_from(amount).ok()?,
+        ConstValue::I32(amount) => u32::try_from(amount).ok()?,
+        ConstValue::U32(amount) => amount,
+        _ => return None,
+    };
+    let left_shift = matches!(op, ArithmeticOperation::ShiftLeft);
+    let value = match left {
+        ConstValue::AbstractInt(value) if left_shift => {
+            ConstValue::AbstractInt(value.checked_shl(amount)?)
+        },
+        ConstValue::AbstractInt(value) => ConstValue::AbstractInt(value.checked_shr(amount)?),
+        ConstValue::I32(value) if left_shift => ConstValue::I32(value.checked_shl(amount)?),
+        ConstValue::I32(value) => ConstValue::I32(value.checked_shr(amount)?),
+        ConstValue::U32(value) if left_shift => ConstValue::U32(value.checked_shl(amount)?),
+        ConstValue::U32(value) => ConstValue::U32(value.checked_shr(amount)?),
+        _ => return None,
+    };
+    Some(value)
+}
+
+fn compare(
+    left: ConstValue,
+    right: ConstValue,
+) -> Option<std::cmp::Ordering> {
+    match (left, right) {
+        (ConstValue::Bool(left), ConstValue::Bool(right)) => Some(left.cmp(&right)),
+        (ConstValue::AbstractInt(left), ConstValue::AbstractInt(right)) => Some(left.cmp(&right)),
+        (ConstValue::I32(left), ConstValue::I32(right)) => Some(left.cmp(&right)),
+        (ConstValue::U32(left), ConstValue::U32(right)) => Some(left.cmp(&right)),
+        (ConstValue::AbstractFloat(left), ConstValue::AbstractFloat(right)) => {
+            left.partial_cmp(&right)
+        },
+        (ConstValue::F32(left), ConstValue::F32(right)) => left.partial_cmp(&right),
+        _ => None,
+    }
+}
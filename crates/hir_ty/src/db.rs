@@ -6,6 +6,7 @@ use std::sync::Arc;
 use crate::builtins::{Builtin, BuiltinId};
 use crate::function::{FunctionDetails, ResolvedFunctionId};
 use crate::infer::{InferenceResult, TyLoweringContext};
+use crate::layout::StructAddressSpaces;
 use crate::ty::{TyKind, Type};
 use base_db::Upcast;
 use hir_def::{
@@ -35,11 +36,11 @@ pub trait HirDatabase: DefDatabase + Upcast<dyn DefDatabase> {
         function: FunctionId,
     ) -> ResolvedFunctionId;
 
-    fn struct_is_used_in_uniform(
+    fn struct_address_spaces(
         &self,
         r#struct: StructId,
         file_id: HirFileId,
-    ) -> bool;
+    ) -> StructAddressSpaces;
 
     #[salsa::interned]
     fn intern_ty(
@@ -114,34 +115,39 @@ fn function_type(
     .intern(db)
 }
 
-fn struct_is_used_in_uniform(
+fn struct_address_spaces(
     db: &dyn HirDatabase,
     r#struct: StructId,
     file_id: HirFileId,
-) -> bool {
+) -> StructAddressSpaces {
+    let mut address_spaces = StructAddressSpaces::default();
     let module_info = db.module_info(file_id);
-    module_info.items().iter().any(|item| match *item {
-        hir_def::module_data::ModuleItem::Import(import) => {
-            let import_id = db.intern_import(InFile::new(file_id, import));
-            let file_id = ImportFile { import_id };
-            db.struct_is_used_in_uniform(r#struct, file_id.into())
-        },
-        hir_def::module_data::ModuleItem::GlobalVariable(decl) => {
-            let decl = db.intern_global_variable(InFile::new(file_id, decl));
-            let data = db.global_var_data(decl);
-
-            if !matches!(data.storage_class, Some(StorageClass::Uniform)) {
-                return false;
-            }
-
-            let inference = db.infer(DefinitionWithBodyId::GlobalVariable(decl));
-            let r#type = match inference.return_type {
-                Some(r#type) => r#type,
-                None => return false,
-            };
-
-            r#type.contains_struct(db, r#struct)
-        },
-        _ => false,
-    })
+    for item in module_info.items() {
+        match *item {
+            hir_def::module_data::ModuleItem::Import(import) => {
+                let import_id = db.intern_import(InFile::new(file_id, import));
+                let file_id = ImportFile { import_id };
+                let imported = db.struct_address_spaces(r#struct, file_id.into());
+                address_spaces.uniform |= imported.uniform;
+                address_spaces.storage |= imported.storage;
+            },
+            hir_def::module_data::ModuleItem::GlobalVariable(decl) => {
+                let decl = db.intern_global_variable(InFile::new(file_id, decl));
+                let data = db.global_var_data(decl);
+
+                let used_in = match data.storage_class {
+                    Some(StorageClass::Uniform) => &mut address_spaces.uniform,
+                    Some(StorageClass::Storage) => &mut address_spaces.storage,
+                    _ => continue,
+                };
+
+                let inference = db.infer(DefinitionWithBodyId::GlobalVariable(decl));
+                if let Some(r#type) = inference.return_type {
+                    *used_in |= r#type.contains_struct(db, r#struct);
+                }
+            },
+            _ => {},
+        }
+    }
+    address_spaces
 }
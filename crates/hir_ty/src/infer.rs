@@ -136,7 +136,10 @@ impl From<ExpressionId> for TypeContainer {
 
 #[derive(PartialEq, Eq, Debug, Copy, Clone)]
 pub enum ResolvedCall {
+    /// A builtin function, or a type initializer of a builtin type.
     Function(ResolvedFunctionId),
+    /// A function declared in the module or its imports.
+    UserFunction(FunctionId, ResolvedFunctionId),
     OtherTypeInitializer(Type),
 }
 
@@ -148,6 +151,7 @@ pub struct InferenceResult {
     pub return_type: Option<Type>,
     call_resolutions: FxHashMap<ExpressionId, ResolvedCall>,
     field_resolutions: FxHashMap<ExpressionId, FieldId>,
+    expectations: FxHashMap<ExpressionId, TypeExpectation>,
 }
 
 impl InferenceResult {
@@ -164,6 +168,47 @@ impl InferenceResult {
     ) -> Option<ResolvedCall> {
         self.call_resolutions.get(&expression).copied()
     }
+
+    /// The type the context of the expression expects it to have, e.g. the declared
+    /// type of a variable for its initializer or the parameter type for an argument.
+    pub fn expected_type(
+        &self,
+        expression: ExpressionId,
+    ) -> Option<&TypeExpectation> {
+        self.expectations.get(&expression)
+    }
+
+    /// The type expected for the argument at `index` of a call, which need not be written yet.
+    pub fn expected_argument_type(
+        &self,
+        db: &dyn HirDatabase,
+        call: ExpressionId,
+        index: usize,
+    ) -> Option<TypeExpectation> {
+        let r#type = *self.type_of_expression.get(call)?;
+        let parameter = parameter_types(db, self, call, r#type)?
+            .into_iter()
+            .nth(index)?;
+        Some(TypeExpectation::from_ty(parameter))
+    }
+}
+
+/// The parameter types of a user function call, or the field types of a struct initializer.
+fn parameter_types(
+    db: &dyn HirDatabase,
+    result: &InferenceResult,
+    call: ExpressionId,
+    r#type: Type,
+) -> Option<Vec<Type>> {
+    match result.call_resolution(call) {
+        Some(ResolvedCall::Function(function) | ResolvedCall::UserFunction(_, function)) => {
+            Some(function.lookup(db).parameters().collect())
+        },
+        _ => match r#type.kind(db) {
+            TyKind::Struct(r#struct) => Some(db.field_types(r#struct).values().copied().collect()),
+            _ => None,
+        },
+    }
 }
 
 pub struct InferenceContext<'db> {
@@ -215,6 +260,16 @@ impl<'db> InferenceContext<'db> {
         self.result.field_resolutions.insert(expression, field);
     }
 
+    fn set_expectation(
+        &mut self,
+        expression: ExpressionId,
+        expected: TypeExpectation,
+    ) {
+        if expected != TypeExpectation::None {
+            self.result.expectations.insert(expression, expected);
+        }
+    }
+
     fn push_diagnostic(
         &mut self,
         diagnostic: InferenceDiagnostic,
@@ -589,38 +644,10 @@ impl<'db> InferenceContext<'db> {
         r#type: Type,
         expectation: &TypeExpectationInner,
     ) -> Result<(), ()> {
-        let ty_kind = r#type.kind(self.db);
-        if let TyKind::Error = ty_kind {
-            return Ok(());
-        };
-
-        match *expectation {
-            TypeExpectationInner::Exact(expected_type) => match expected_type.kind(self.db) {
-                TyKind::Error => Ok(()),
-                _ => {
-                    if r#type == expected_type {
-                        Ok(())
-                    } else {
-                        Err(())
-                    }
-                },
-            },
-            TypeExpectationInner::I32OrF32 => match r#type.kind(self.db).unref(self.db).as_ref() {
-                TyKind::Scalar(ScalarType::I32 | ScalarType::F32) => Ok(()),
-                _ => Err(()),
-            },
-            TypeExpectationInner::NumericScalar => {
-                match r#type.kind(self.db).unref(self.db).as_ref() {
-                    TyKind::Scalar(ScalarType::I32 | ScalarType::F32 | ScalarType::U32) => Ok(()),
-                    _ => Err(()),
-                }
-            },
-            TypeExpectationInner::IntegerScalar => {
-                match r#type.kind(self.db).unref(self.db).as_ref() {
-                    TyKind::Scalar(ScalarType::I32 | ScalarType::U32) => Ok(()),
-                    _ => Err(()),
-                }
-            },
+        if expectation.matches(self.db, r#type) {
+            Ok(())
+        } else {
+            Err(())
         }
     }
 
@@ -645,6 +672,7 @@ impl<'db> InferenceContext<'db> {
         expression: ExpressionId,
         expected: TypeExpectation,
     ) -> Type {
+        self.set_expectation(expression, expected.clone());
         let r#type = self.infer_expression(expression).unref(self.db);
 
         match &expected {
@@ -760,11 +788,13 @@ impl<'db> InferenceContext<'db> {
                 ref callee,
                 ref arguments,
             } => {
-                let arguments: Vec<_> = arguments
+                let argument_types: Vec<_> = arguments
                     .iter()
                     .map(|&arg| self.infer_expression(arg).unref(self.db))
                     .collect();
-                self.infer_call(expression, callee, arguments)
+                let r#type = self.infer_call(expression, callee, argument_types);
+                self.set_argument_expectations(expression, r#type, arguments);
+                r#type
             },
             Expression::Bitcast { r#type, expression } => {
                 self.infer_expression(expression);
@@ -837,6 +867,22 @@ impl<'db> InferenceContext<'db> {
         r#type
     }
 
+    /// Expects the arguments of a user function call to have the types of the parameters,
+    /// and the arguments of a struct initializer to have the types of the fields.
+    fn set_argument_expectations(
+        &mut self,
+        expression: ExpressionId,
+        r#type: Type,
+        arguments: &[ExpressionId],
+    ) {
+        let Some(parameters) = parameter_types(self.db, &self.result, expression, r#type) else {
+            return;
+        };
+        for (&argument, parameter) in arguments.iter().zip(parameters) {
+            self.set_expectation(argument, TypeExpectation::from_ty(parameter));
+        }
+    }
+
     fn validate_function_call(
         &mut self,
         f: &FunctionDetails,
@@ -1226,7 +1272,7 @@ impl<'db> InferenceContext<'db> {
                         let details = resolved.lookup(self.db);
                         self.result
                             .call_resolutions
-                            .insert(expression, ResolvedCall::Function(resolved));
+                            .insert(expression, ResolvedCall::UserFunction(id, resolved));
                         self.validate_function_call(&details, arguments, expression, expression)
                     },
                     hir_def::resolver::ResolveCallable::PredeclaredTypeAlias(type_ref) => {
@@ -1704,7 +1750,51 @@ pub enum TypeExpectation {
     None,
 }
 
+impl TypeExpectationInner {
+    /// Whether `r#type` satisfies the expectation. The error type satisfies every
+    /// expectation, so that errors are not reported twice.
+    pub fn matches(
+        &self,
+        db: &dyn HirDatabase,
+        r#type: Type,
+    ) -> bool {
+        if let TyKind::Error = r#type.kind(db) {
+            return true;
+        }
+
+        match *self {
+            TypeExpectationInner::Exact(expected_type) => {
+                matches!(expected_type.kind(db), TyKind::Error) || r#type == expected_type
+            },
+            TypeExpectationInner::I32OrF32 => matches!(
+                r#type.kind(db).unref(db).as_ref(),
+                TyKind::Scalar(ScalarType::I32 | ScalarType::F32)
+            ),
+            TypeExpectationInner::NumericScalar => matches!(
+                r#type.kind(db).unref(db).as_ref(),
+                TyKind::Scalar(ScalarType::I32 | ScalarType::F32 | ScalarType::U32)
+            ),
+            TypeExpectationInner::IntegerScalar => matches!(
+                r#type.kind(db).unref(db).as_ref(),
+                TyKind::Scalar(ScalarType::I32 | ScalarType::U32)
+            ),
+        }
+    }
+}
+
 impl TypeExpectation {
+    pub fn matches(
+        &self,
+        db: &dyn HirDatabase,
+        r#type: Type,
+    ) -> bool {
+        match self {
+            TypeExpectation::Type(inner) => inner.matches(db, r#type),
+            TypeExpectation::TypeOrVecOf(inner) => inner.matches(db, r#type.this_or_vec_inner(db)),
+            TypeExpectation::None => true,
+        }
+    }
+
     fn from_option(option: Option<Type>) -> Self {
         match option {
             Some(r#type) => TypeExpectation::Type(TypeExpectationInner::Exact(r#type)),
@@ -1,5 +1,9 @@
-use hir_def::data::LocalFieldId;
-use la_arena::ArenaMap;
+use hir_def::{
+    attributes::{AttributeDefId, AttributeList, AttributeValue},
+    data::{FieldId, LocalFieldId},
+    db::StructId,
+    expression::Literal,
+};
 
 use crate::{
     db::HirDatabase,
@@ -24,12 +28,39 @@ fn test_round_up() {
     assert_eq!(round_up(32, 102), 128);
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum LayoutAddressSpace {
     Storage,
     Uniform,
 }
 
+impl LayoutAddressSpace {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LayoutAddressSpace::Storage => "storage",
+            LayoutAddressSpace::Uniform => "uniform",
+        }
+    }
+}
+
+/// The host-shareable address spaces global variables use a struct in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StructAddressSpaces {
+    pub uniform: bool,
+    pub storage: bool,
+}
+
+impl StructAddressSpaces {
+    pub fn iter(self) -> impl Iterator<Item = LayoutAddressSpace> {
+        [
+            self.uniform.then_some(LayoutAddressSpace::Uniform),
+            self.storage.then_some(LayoutAddressSpace::Storage),
+        ]
+        .into_iter()
+        .flatten()
+    }
+}
+
 impl ArrayType {
     pub fn stride(
         &self,
@@ -88,14 +119,8 @@ impl TyKind {
                 VecSize::BoundVar(_) => return None,
             },
             TyKind::Struct(r#struct) => {
-                let fields = db.field_types(*r#struct);
-                let (align, _) =
-                    struct_member_layout(&fields, db, LayoutAddressSpace::Storage, |_, _| {})?;
-
-                match address_space {
-                    LayoutAddressSpace::Storage => align,
-                    LayoutAddressSpace::Uniform => round_up(16, align),
-                }
+                let (align, _) = struct_member_layout(*r#struct, db, address_space, |_, _| {})?;
+                align
             },
             TyKind::Array(array) => {
                 let inner_align = array.inner.align(address_space, db)?;
@@ -125,7 +150,7 @@ impl TyKind {
             },
             TyKind::Matrix(m) => {
                 let n = m.columns.as_u8() as Bytes;
-                let (vec_align, vec_size) = match m.columns {
+                let (vec_align, vec_size) = match m.rows {
                     VecSize::Two => (8, 8),
                     VecSize::Three => (16, 12),
                     VecSize::Four => (16, 16),
@@ -135,9 +160,7 @@ impl TyKind {
                 round_up(vec_align, vec_size) * n
             },
             TyKind::Struct(r#struct) => {
-                let fields = db.field_types(*r#struct);
-                let (_, size) =
-                    struct_member_layout(&fields, db, LayoutAddressSpace::Storage, |_, _| {})?;
+                let (_, size) = struct_member_layout(*r#struct, db, address_space, |_, _| {})?;
                 size
             },
             TyKind::Array(array) => match array.size {
@@ -152,6 +175,25 @@ impl TyKind {
     }
 }
 
+/// The value of an `@align(n)` or `@size(n)` attribute, which is `None` without the attribute
+/// and `Some(None)` if the value is not a literal, as constants are not evaluated here.
+fn attribute_value(
+    db: &dyn HirDatabase,
+    attributes: &AttributeList,
+    name: &str,
+) -> Option<Option<Bytes>> {
+    let attribute = attributes
+        .attributes
+        .iter()
+        .map(|&attribute| db.lookup_intern_attribute(attribute))
+        .find(|attribute| attribute.name.as_str() == name)?;
+    Some(match attribute.parameters.first() {
+        Some(AttributeValue::Literal(Literal::Int(value, _))) => Bytes::try_from(*value).ok(),
+        Some(AttributeValue::Literal(Literal::Uint(value, _))) => Bytes::try_from(*value).ok(),
+        _ => None,
+    })
+}
+
 pub struct FieldLayout {
     pub offset: Bytes,
     pub align: Bytes,
@@ -160,25 +202,34 @@ pub struct FieldLayout {
 
 /// Returns the (align, size) of the struct, and calls `on_field` for every field
 pub fn struct_member_layout<R>(
-    fields: &ArenaMap<LocalFieldId, Type>,
+    r#struct: StructId,
     db: &dyn HirDatabase,
     address_space: LayoutAddressSpace,
     mut on_field: impl FnMut(LocalFieldId, FieldLayout) -> R,
 ) -> Option<(Bytes, Bytes)> {
     let mut struct_align = Bytes::MIN;
 
-    let mut offset = 0;
-    let mut last_member_size = None;
+    let mut just_past_last_member = None;
 
-    for (field_id, &field) in fields.iter() {
-        let custom_align = None; // TODO handle @align @size
-        let custom_size = None;
+    for (field_id, &field) in db.field_types(r#struct).iter() {
+        let attributes = db.attrs(AttributeDefId::FieldId(FieldId {
+            r#struct,
+            field: field_id,
+        }));
+        let attributes = &attributes.attribute_list;
 
-        let align = custom_align.or_else(|| field.align(address_space, db))?;
-        let size = custom_size.or_else(|| field.align(address_space, db))?;
+        let align = match attribute_value(db, attributes, "align") {
+            Some(align) => align?,
+            None => field.align(address_space, db)?,
+        };
+        let size = match attribute_value(db, attributes, "size") {
+            Some(size) => size?,
+            None => field.size(address_space, db)?,
+        };
 
         struct_align = struct_align.max(align);
 
+        let offset = round_up(align, just_past_last_member.unwrap_or(0));
         on_field(
             field_id,
             FieldLayout {
@@ -188,13 +239,16 @@ pub fn struct_member_layout<R>(
             },
         );
 
-        let new_offset = round_up(align, offset + size);
-        last_member_size = Some(size);
-        offset = new_offset;
+        // In the uniform address space, a struct member is followed by at least
+        // `roundUp(16, SizeOf(S))` bytes.
+        let size = match (address_space, field.kind(db)) {
+            (LayoutAddressSpace::Uniform, TyKind::Struct(_)) => round_up(16, size),
+            _ => size,
+        };
+        just_past_last_member = Some(offset + size);
     }
 
-    let just_past_last_member = offset + last_member_size?;
-    let struct_size = round_up(struct_align, just_past_last_member);
+    let struct_size = round_up(struct_align, just_past_last_member?);
 
     let struct_align = match address_space {
         LayoutAddressSpace::Storage => struct_align,
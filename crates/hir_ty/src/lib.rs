@@ -2,6 +2,7 @@
 //! information and various assists.
 
 pub mod builtins;
+pub mod consteval;
 pub mod db;
 pub mod function;
 pub mod infer;
@@ -455,6 +455,28 @@ pub enum TexelFormat {
     Any,
 }
 
+impl TexelFormat {
+    /// The formats which can be written in source code.
+    pub const ALL: [Self; 16] = [
+        TexelFormat::Rgba8unorm,
+        TexelFormat::Rgba8snorm,
+        TexelFormat::Rgba8uint,
+        TexelFormat::Rgba8sint,
+        TexelFormat::Rgba16uint,
+        TexelFormat::Rgba16sint,
+        TexelFormat::Rgba16float,
+        TexelFormat::Rgba32uint,
+        TexelFormat::Rgba32sint,
+        TexelFormat::Rgba32float,
+        TexelFormat::R32uint,
+        TexelFormat::R32sint,
+        TexelFormat::R32float,
+        TexelFormat::Rg32uint,
+        TexelFormat::Rg32sint,
+        TexelFormat::Rg32float,
+    ];
+}
+
 impl std::fmt::Display for TexelFormat {
     fn fmt(
         &self,
@@ -0,0 +1,65 @@
+use std::fmt::Write as _;
+
+use base_db::{TextRange, TextSize};
+use hir::HirDatabase;
+use hir_def::module_data::Name;
+use hir_ty::{builtins::Builtin, ty::pretty::pretty_type};
+use itertools::Itertools as _;
+
+/// A generated, read-only document declaring the overloads of a builtin function, followed by
+/// its documentation from the WGSL specification.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct BuiltinDocument {
+    pub name: String,
+    pub text: String,
+    /// The name of the first overload.
+    pub focus_range: TextRange,
+}
+
+pub(crate) fn builtin_document(
+    db: &dyn HirDatabase,
+    name: &str,
+) -> Option<BuiltinDocument> {
+    let builtin = Builtin::for_name(db, &Name::from(name))?;
+
+    let mut text = format!(
+        "// `{name}` is a builtin function.\n// This document is generated and read-only.\n\n"
+    );
+    let focus_start = TextSize::of(&text) + TextSize::of("fn ");
+    for (_, overload) in builtin.overloads() {
+        let function = overload.r#type.lookup(db);
+        let parameters = function
+            .parameters
+            .iter()
+            .map(|(r#type, name)| {
+                if *name == Name::missing() {
+                    pretty_type(db, *r#type)
+                } else {
+                    format!("{}: {}", name.as_str(), pretty_type(db, *r#type))
+                }
+            })
+            .join(", ");
+        write!(text, "fn {name}({parameters})").unwrap();
+        if let Some(return_type) = function.return_type {
+            write!(text, " -> {}", pretty_type(db, return_type)).unwrap();
+        }
+        text.push('\n');
+    }
+
+    if let Some(documentation) = builtin.documentation() {
+        text.push('\n');
+        for line in documentation.lines() {
+            if line.is_empty() {
+                text.push_str("//\n");
+            } else {
+                writeln!(text, "// {line}").unwrap();
+            }
+        }
+    }
+
+    Some(BuiltinDocument {
+        name: name.to_owned(),
+        text,
+        focus_range: TextRange::at(focus_start, TextSize::of(name)),
+    })
+}
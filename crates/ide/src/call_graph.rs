@@ -0,0 +1,59 @@
+use hir::{HirDatabase, ModuleDef, Semantics, definition::Definition};
+use hir_def::InFile;
+use vfs::FileId;
+
+use crate::goto_definition::{NavigationTarget, ToNav};
+
+/// The functions of a file and of its imports, with the functions they call.
+#[derive(Debug)]
+pub struct CallGraph {
+    pub functions: Vec<CallGraphFunction>,
+}
+
+#[derive(Debug)]
+pub struct CallGraphFunction {
+    pub name: String,
+    pub target: NavigationTarget,
+    /// Whether the function is a `@vertex`, `@fragment` or `@compute` entry point.
+    pub is_entry_point: bool,
+    /// Whether an entry point calls the function, directly or indirectly.
+    pub is_reachable: bool,
+    /// The indices in [`CallGraph::functions`] of the functions this function calls.
+    pub callees: Vec<usize>,
+}
+
+pub(crate) fn call_graph(
+    db: &dyn HirDatabase,
+    file_id: FileId,
+) -> CallGraph {
+    let module = Semantics::new(db).module(file_id);
+    let graph = hir::call_graph::CallGraph::new(db, &module);
+
+    let functions: Vec<_> = graph
+        .items()
+        .iter()
+        .filter_map(|&item| {
+            let ModuleDef::Function(function) = item else {
+                return None;
+            };
+            let target =
+                InFile::new(file_id.into(), Definition::ModuleDef(item)).to_nav(db.upcast())?;
+            Some((function, item, target))
+        })
+        .collect();
+
+    let functions = functions
+        .iter()
+        .map(|(function, item, target)| CallGraphFunction {
+            name: item.name(db).as_str().to_owned(),
+            target: target.clone(),
+            is_entry_point: graph.entry_points().contains(function),
+            is_reachable: graph.is_reachable(*item),
+            callees: graph
+                .callees(*function)
+                .filter_map(|callee| functions.iter().position(|(other, ..)| *other == callee))
+                .collect(),
+        })
+        .collect();
+    CallGraph { functions }
+}
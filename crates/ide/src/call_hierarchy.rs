@@ -0,0 +1,218 @@
+use base_db::{FilePosition, FileRange};
+use hir::{
+    Function, HirDatabase, ModuleDef, Semantics,
+    call_graph::{Call, CallGraph, CallTarget},
+    definition::Definition,
+};
+use hir_def::{InFile, original_file_range};
+use syntax::{AstNode, SyntaxKind, ast};
+use vfs::FileId;
+
+use crate::{
+    goto_definition::{NavigationTarget, ToNav},
+    helpers,
+};
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CallHierarchyItem {
+    pub name: String,
+    pub target: NavigationTarget,
+    /// Whether the function is a `@vertex`, `@fragment` or `@compute` entry point.
+    pub is_entry_point: bool,
+}
+
+/// A function calling or called by the function of the hierarchy, with the ranges of the
+/// calls.
+#[derive(Debug)]
+pub struct CallItem {
+    pub item: CallHierarchyItem,
+    pub ranges: Vec<FileRange>,
+}
+
+#[derive(Debug, Default)]
+pub struct OutgoingCalls {
+    /// The calls of functions declared in the module or in its imports.
+    pub functions: Vec<CallItem>,
+    /// The calls of builtin functions, such as `textureSample`.
+    pub builtins: Vec<BuiltinCall>,
+}
+
+#[derive(Debug)]
+pub struct BuiltinCall {
+    pub name: String,
+    pub ranges: Vec<FileRange>,
+}
+
+pub(crate) fn prepare_call_hierarchy(
+    db: &dyn HirDatabase,
+    position: FilePosition,
+) -> Option<CallHierarchyItem> {
+    let function = function_at(db, position)?;
+    call_hierarchy_item(db, function)
+}
+
+/// The functions calling the function at the position, in the file and in the shader files
+/// of the workspace which import it.
+pub(crate) fn incoming_calls(
+    db: &dyn HirDatabase,
+    position: FilePosition,
+) -> Option<Vec<CallItem>> {
+    let function = function_at(db, position)?;
+    let sema = Semantics::new(db);
+
+    let mut calls = Vec::new();
+    // The text of a file calling the function need not contain its name, as imports may
+    // rename it.
+    for file_id in shader_files(db, position.file_id) {
+        let graph = CallGraph::new(db, &sema.module(file_id));
+        // Callers declared in imported files are found with their own files.
+        let callers = graph
+            .callers(function)
+            .filter(|&caller| ModuleDef::Function(caller).file_id(db.upcast()) == file_id.into());
+        for caller in callers {
+            let mut ranges: Vec<FileRange> = caller
+                .calls(db)
+                .iter()
+                .filter(|call| call.target == CallTarget::Function(function))
+                .filter_map(|call| call_range(db, call))
+                .collect();
+            ranges.sort_by_key(|range| range.range.start());
+            if let Some(item) = call_hierarchy_item(db, caller) {
+                calls.push(CallItem { item, ranges });
+            }
+        }
+    }
+    Some(calls)
+}
+
+/// The functions called by the function at the position, in the order of their first call.
+pub(crate) fn outgoing_calls(
+    db: &dyn HirDatabase,
+    position: FilePosition,
+) -> Option<OutgoingCalls> {
+    let function = function_at(db, position)?;
+
+    let mut calls: Vec<(Call, FileRange)> = function
+        .calls(db)
+        .into_iter()
+        .filter_map(|call| {
+            let range = call_range(db, &call)?;
+            Some((call, range))
+        })
+        .collect();
+    calls.sort_by_key(|(_, range)| range.range.start());
+
+    let mut functions = Vec::new();
+    let mut builtins = Vec::new();
+    for (call, range) in calls {
+        match call.target {
+            CallTarget::Function(callee) => add_call(&mut functions, callee, range),
+            CallTarget::Builtin(name) => add_call(&mut builtins, name, range),
+        }
+    }
+
+    Some(OutgoingCalls {
+        functions: functions
+            .into_iter()
+            .filter_map(|(callee, ranges)| {
+                let item = call_hierarchy_item(db, callee)?;
+                Some(CallItem { item, ranges })
+            })
+            .collect(),
+        builtins: builtins
+            .into_iter()
+            .map(|(name, ranges)| BuiltinCall {
+                name: name.as_str().to_owned(),
+                ranges,
+            })
+            .collect(),
+    })
+}
+
+/// The function declared or called at the position.
+fn function_at(
+    db: &dyn HirDatabase,
+    position: FilePosition,
+) -> Option<Function> {
+    let sema = Semantics::new(db);
+    let file = sema.parse(position.file_id);
+    let token = file.syntax().token_at_offset(position.offset);
+    let token = helpers::pick_best_token(token, |token| match token {
+        SyntaxKind::Identifier => 2,
+        kind if kind.is_trivia() => 0,
+        _ => 1,
+    })?;
+
+    let parent = token.parent()?;
+    if ast::Name::can_cast(parent.kind()) {
+        let function = parent.parent().and_then(ast::Function::cast)?;
+        return sema.function(InFile::new(position.file_id.into(), function));
+    }
+    match Definition::from_token(&sema, position.file_id.into(), &token)? {
+        Definition::ModuleDef(ModuleDef::Function(function)) => Some(function),
+        _ => None,
+    }
+}
+
+fn call_hierarchy_item(
+    db: &dyn HirDatabase,
+    function: Function,
+) -> Option<CallHierarchyItem> {
+    let def = ModuleDef::Function(function);
+    let target =
+        InFile::new(def.file_id(db.upcast()), Definition::ModuleDef(def)).to_nav(db.upcast())?;
+    Some(CallHierarchyItem {
+        name: function.name(db).as_str().to_owned(),
+        target,
+        is_entry_point: function.is_entry_point(db),
+    })
+}
+
+/// The range of the name of the called function.
+fn call_range(
+    db: &dyn HirDatabase,
+    call: &Call,
+) -> Option<FileRange> {
+    let file_id = call.expression.file_id;
+    let root = db.parse_or_resolve(file_id).ok()?.syntax();
+    let expression = call.expression.value.to_node(&root);
+    let name = match &expression {
+        ast::Expression::FunctionCall(call) => {
+            call.name_ref().and_then(|name| name.syntax().first_token())
+        },
+        _ => None,
+    };
+    Some(match name {
+        Some(name) => original_file_range(db.upcast(), file_id, &name),
+        None => original_file_range(db.upcast(), file_id, expression.syntax()),
+    })
+}
+
+fn add_call<T: PartialEq>(
+    calls: &mut Vec<(T, Vec<FileRange>)>,
+    target: T,
+    range: FileRange,
+) {
+    match calls.iter_mut().find(|(other, _)| *other == target) {
+        Some((_, ranges)) => ranges.push(range),
+        None => calls.push((target, vec![range])),
+    }
+}
+
+/// The file and the other shader files of the workspace.
+fn shader_files(
+    db: &dyn HirDatabase,
+    file_id: FileId,
+) -> Vec<FileId> {
+    let mut files = vec![file_id];
+    for &source_root_id in db.source_root_ids().iter() {
+        let source_root = db.source_root(source_root_id);
+        files.extend(source_root.iter().filter(|&file| {
+            file != file_id
+                && source_root.path_for_file(&file).is_some_and(|path| {
+                    matches!(path.name_and_extension(), Some((_, Some("wgsl" | "wesl"))))
+                })
+        }));
+    }
+    files
+}
@@ -53,6 +53,8 @@ impl RootDatabase {
         };
         db.set_custom_imports(Arc::new(Default::default()));
         db.set_shader_defs(Arc::new(Default::default()));
+        db.set_default_edition(syntax::Edition::DEFAULT);
+        db.set_source_root_ids(Arc::new(Default::default()));
         db
     }
 
@@ -1,9 +1,11 @@
+pub mod explanations;
+
 use std::ops::Range;
 
-use base_db::{FileRange, TextRange, TextSize};
+use base_db::{FileRange, TextRange, TextSize, UnconfiguredCodeKind};
 use hir::{
-    HirDatabase, Semantics,
-    diagnostics::{AnyDiagnostic, DiagnosticsConfig, NagaVersion},
+    HirDatabase, ModuleDef, Semantics,
+    diagnostics::{AnyDiagnostic, DiagnosticLevel, DiagnosticsConfig, NagaVersion, lints::Lint},
 };
 use hir_def::original_file_range;
 use hir_ty::ty::{
@@ -12,9 +14,11 @@ use hir_ty::ty::{
 };
 use itertools::Itertools;
 use rowan::NodeOrToken;
-use syntax::AstNode;
+use syntax::{AstNode, HasName, SyntaxKind, SyntaxNode, SyntaxToken, ast};
 use vfs::FileId;
 
+use crate::helpers;
+
 pub struct Diagnostic {
     pub code: DiagnosticCode,
     pub message: String,
@@ -24,11 +28,64 @@ pub struct Diagnostic {
     pub related: Vec<(String, FileRange)>,
 }
 
+/// A stable, kebab-case name for a kind of diagnostic.
+///
+/// Codes are part of the user-facing surface (hover, `wgsl-analyzer explain`,
+/// documentation links), so they must never be renamed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct DiagnosticCode(&'static str);
 
 impl DiagnosticCode {
+    pub const ASSIGNMENT_NOT_A_REFERENCE: Self = Self("assignment-not-a-reference");
+    pub const TYPE_MISMATCH: Self = Self("type-mismatch");
+    pub const NO_SUCH_FIELD: Self = Self("no-such-field");
+    pub const ARRAY_ACCESS_INVALID_TYPE: Self = Self("array-access-invalid-type");
+    pub const UNRESOLVED_NAME: Self = Self("unresolved-name");
+    pub const INVALID_CONSTRUCTION_TYPE: Self = Self("invalid-construction-type");
+    pub const ARGUMENT_COUNT_MISMATCH: Self = Self("argument-count-mismatch");
+    pub const NO_BUILTIN_OVERLOAD: Self = Self("no-builtin-overload");
+    pub const ADDRESS_OF_NOT_REFERENCE: Self = Self("address-of-not-reference");
+    pub const DEREF_NOT_POINTER: Self = Self("deref-not-pointer");
+    pub const MISSING_STORAGE_CLASS: Self = Self("missing-storage-class");
+    pub const INVALID_STORAGE_CLASS: Self = Self("invalid-storage-class");
+    pub const INVALID_TYPE: Self = Self("invalid-type");
+    pub const UNRESOLVED_IMPORT: Self = Self("unresolved-import");
+    pub const NAGA_VALIDATION_ERROR: Self = Self("naga-validation-error");
+    pub const PARSE_ERROR: Self = Self("parse-error");
+    pub const INACTIVE_CODE: Self = Self("inactive-code");
+    pub const NO_CONSTRUCTOR: Self = Self("no-constructor");
+    pub const PRECEDENCE_NEVER_NESTED: Self = Self("precedence-never-nested");
+    pub const PRECEDENCE_SEQUENCE_ONLY: Self = Self("precedence-sequence-only");
+    pub const MISSING_RETURN: Self = Self("missing-return");
+    pub const UNREACHABLE_CODE: Self = Self("unreachable-code");
+    pub const BREAK_OUTSIDE_LOOP: Self = Self("break-outside-loop");
+    pub const CONTINUE_OUTSIDE_LOOP: Self = Self("continue-outside-loop");
+    pub const RETURN_IN_CONTINUING: Self = Self("return-in-continuing");
+    pub const MISPLACED_CONTINUING: Self = Self("misplaced-continuing");
+    pub const UNUSED_ITEM: Self = Self("unused-item");
+    pub const UNUSED_VARIABLE: Self = Self("unused-variable");
+    pub const FLOAT_EQUALITY: Self = Self(Lint::FloatEquality.code());
+    pub const INTEGER_DIVISION_IN_FLOAT: Self = Self(Lint::IntegerDivisionInFloat.code());
+    pub const SHADOWED_BUILTIN: Self = Self(Lint::ShadowedBuiltin.code());
+    pub const SELECT_ARGUMENT_ORDER: Self = Self(Lint::SelectArgumentOrder.code());
+    pub const WORKGROUP_SIZE_NOT_POWER_OF_TWO: Self = Self(Lint::WorkgroupSizeNotPowerOfTwo.code());
+    pub const PRIVATE_NEVER_WRITTEN: Self = Self(Lint::PrivateNeverWritten.code());
+    pub const REDUNDANT_CONVERSION: Self = Self(Lint::RedundantConversion.code());
+
+    /// Looks up a code by its name, returning `None` for unknown codes.
+    pub fn from_name(name: &str) -> Option<Self> {
+        explanations::explanation(name).map(|explanation| explanation.code)
+    }
+
+    pub fn from_lint(lint: Lint) -> Self {
+        Self(lint.code())
+    }
+
     pub fn url(&self) -> String {
-        self.0.to_string()
+        format!(
+            "https://wgsl-analyzer.github.io/book/diagnostics.html#{}",
+            self.0
+        )
     }
 
     pub fn as_str(&self) -> &'static str {
@@ -36,9 +93,11 @@ impl DiagnosticCode {
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Severity {
     Error,
+    Warning,
+    Info,
     WeakWarning,
 }
 
@@ -404,6 +463,7 @@ pub fn diagnostics(
             .iter()
             .map(|unconfigured| AnyDiagnostic::UnconfiguredCode {
                 def: unconfigured.def.clone(),
+                kind: unconfigured.kind,
                 range: unconfigured.range,
                 file_id: file_id.into(),
             }),
@@ -433,6 +493,8 @@ pub fn diagnostics(
         }
     }
 
+    let suppressions = Suppressions::new(&parse.syntax());
+
     diagnostics
         .into_iter()
         .map(|diagnostic| {
@@ -445,7 +507,7 @@ pub fn diagnostics(
                     let frange =
                         original_file_range(db.upcast(), left_side.file_id, source.syntax());
                     Diagnostic::new(
-                        DiagnosticCode("1"),
+                        DiagnosticCode::ASSIGNMENT_NOT_A_REFERENCE,
                         format!(
                             "left hand side of assignment should be a reference, found {}",
                             actual
@@ -464,7 +526,7 @@ pub fn diagnostics(
                     let frange =
                         original_file_range(db.upcast(), expression.file_id, source.syntax());
                     Diagnostic::new(
-                        DiagnosticCode("2"),
+                        DiagnosticCode::TYPE_MISMATCH,
                         format!("expected {}, found {}", expected, actual),
                         frange.range,
                     )
@@ -479,7 +541,7 @@ pub fn diagnostics(
                     let frange =
                         original_file_range(db.upcast(), expression.file_id, source.syntax());
                     Diagnostic::new(
-                        DiagnosticCode("3"),
+                        DiagnosticCode::NO_SUCH_FIELD,
                         format!("no field `{}` on type {}", name.as_ref(), r#type),
                         frange.range,
                     )
@@ -490,7 +552,7 @@ pub fn diagnostics(
                     let frange =
                         original_file_range(db.upcast(), expression.file_id, source.syntax());
                     Diagnostic::new(
-                        DiagnosticCode("4"),
+                        DiagnosticCode::ARRAY_ACCESS_INVALID_TYPE,
                         format!("cannot index into type {}", r#type),
                         frange.range,
                     )
@@ -500,7 +562,7 @@ pub fn diagnostics(
                     let frange =
                         original_file_range(db.upcast(), expression.file_id, source.syntax());
                     Diagnostic::new(
-                        DiagnosticCode("5"),
+                        DiagnosticCode::UNRESOLVED_NAME,
                         format!("cannot find `{}` in this scope", name.as_str()),
                         frange.range,
                     )
@@ -511,7 +573,7 @@ pub fn diagnostics(
                     let frange =
                         original_file_range(db.upcast(), expression.file_id, source.syntax());
                     Diagnostic::new(
-                        DiagnosticCode("6"),
+                        DiagnosticCode::INVALID_CONSTRUCTION_TYPE,
                         format!("cannot construct value of type {}", r#type),
                         frange.range,
                     )
@@ -525,7 +587,7 @@ pub fn diagnostics(
                     let frange =
                         original_file_range(db.upcast(), expression.file_id, source.syntax());
                     Diagnostic::new(
-                        DiagnosticCode("7"),
+                        DiagnosticCode::ARGUMENT_COUNT_MISMATCH,
                         format!("expected {} parameters, found {}", n_expected, n_actual),
                         frange.range,
                     )
@@ -557,7 +619,7 @@ pub fn diagnostics(
                     let frange =
                         original_file_range(db.upcast(), expression.file_id, source.syntax());
                     Diagnostic::new(
-                        DiagnosticCode("8"),
+                        DiagnosticCode::NO_BUILTIN_OVERLOAD,
                         format!(
                             "no overload of `{}` found for given arguments.\
                         Found ({}), expected one of:\n{}",
@@ -572,7 +634,7 @@ pub fn diagnostics(
                     let frange =
                         original_file_range(db.upcast(), expression.file_id, source.syntax());
                     Diagnostic::new(
-                        DiagnosticCode("9"),
+                        DiagnosticCode::ADDRESS_OF_NOT_REFERENCE,
                         format!("expected a reference, found {}", r#type),
                         frange.range,
                     )
@@ -583,7 +645,7 @@ pub fn diagnostics(
                     let frange =
                         original_file_range(db.upcast(), expression.file_id, source.syntax());
                     Diagnostic::new(
-                        DiagnosticCode("10"),
+                        DiagnosticCode::DEREF_NOT_POINTER,
                         format!("cannot dereference expression of type {}", r#type),
                         frange.range,
                     )
@@ -597,7 +659,7 @@ pub fn diagnostics(
 
                     let frange = original_file_range(db.upcast(), var.file_id, &source);
                     Diagnostic::new(
-                        DiagnosticCode("11"),
+                        DiagnosticCode::MISSING_STORAGE_CLASS,
                         "missing storage class on global variable".to_string(),
                         frange.range,
                     )
@@ -609,7 +671,11 @@ pub fn diagnostics(
                         .map(NodeOrToken::Token)
                         .unwrap_or_else(|| NodeOrToken::Node(var_decl.syntax()));
                     let frange = original_file_range(db.upcast(), var.file_id, &source);
-                    Diagnostic::new(DiagnosticCode("12"), format!("{}", error), frange.range)
+                    Diagnostic::new(
+                        DiagnosticCode::INVALID_STORAGE_CLASS,
+                        format!("{}", error),
+                        frange.range,
+                    )
                 },
                 AnyDiagnostic::InvalidType {
                     file_id: _,
@@ -618,13 +684,17 @@ pub fn diagnostics(
                 } => {
                     let source = location.to_node(&root);
                     let frange = original_file_range(db.upcast(), file_id, source.syntax());
-                    Diagnostic::new(DiagnosticCode("13"), format!("{}", error), frange.range)
+                    Diagnostic::new(
+                        DiagnosticCode::INVALID_TYPE,
+                        format!("{}", error),
+                        frange.range,
+                    )
                 },
                 AnyDiagnostic::UnresolvedImport { import } => {
                     let source = import.value.to_node(&root);
                     let frange = original_file_range(db.upcast(), file_id, source.syntax());
                     Diagnostic::new(
-                        DiagnosticCode("14"),
+                        DiagnosticCode::UNRESOLVED_IMPORT,
                         "unresolved import".to_string(),
                         frange.range,
                     )
@@ -635,19 +705,30 @@ pub fn diagnostics(
                     related,
                     ..
                 } => {
-                    let mut message = Diagnostic::new(DiagnosticCode("15"), message, range);
+                    let mut message =
+                        Diagnostic::new(DiagnosticCode::NAGA_VALIDATION_ERROR, message, range);
                     message.related = related;
                     message
                 },
                 AnyDiagnostic::ParseError { message, range, .. } => {
-                    Diagnostic::new(DiagnosticCode("16"), message, range)
+                    Diagnostic::new(DiagnosticCode::PARSE_ERROR, message, range)
                 },
-                AnyDiagnostic::UnconfiguredCode { def, range, .. } => Diagnostic::new(
-                    DiagnosticCode("17"),
-                    format!(
-                        "code is inactive due to `#ifdef` directives: `{}` is not enabled",
-                        def
-                    ),
+                AnyDiagnostic::UnconfiguredCode {
+                    def, kind, range, ..
+                } => Diagnostic::new(
+                    DiagnosticCode::INACTIVE_CODE,
+                    match kind {
+                        UnconfiguredCodeKind::Ifdef => format!(
+                            "code is inactive due to `#ifdef` directives: `{}` is not enabled",
+                            def
+                        ),
+                        UnconfiguredCodeKind::IfAttribute => {
+                            format!(
+                                "code is inactive due to an `@if` attribute: `{}` is false",
+                                def
+                            )
+                        },
+                    },
                     range,
                 )
                 .with_severity(Severity::WeakWarning)
@@ -692,7 +773,7 @@ pub fn diagnostics(
                     let frange =
                         original_file_range(db.upcast(), expression.file_id, source.syntax());
                     Diagnostic::new(
-                        DiagnosticCode("18"),
+                        DiagnosticCode::NO_CONSTRUCTOR,
                         format!(
                             "no overload of constructor `{}` found for given\
                             arguments. Found ({parameters}), expected one of:\n{possible}",
@@ -720,13 +801,253 @@ pub fn diagnostics(
                             More complex operands must be this with parenthesized `()`"
                         )
                     };
-                    Diagnostic::new(DiagnosticCode("19"), message, frange.range)
+                    let code = if sequence_permitted {
+                        DiagnosticCode::PRECEDENCE_SEQUENCE_ONLY
+                    } else {
+                        DiagnosticCode::PRECEDENCE_NEVER_NESTED
+                    };
+                    Diagnostic::new(code, message, frange.range)
+                },
+                AnyDiagnostic::MissingReturn { function } => {
+                    let function = function.value.to_node(&root);
+                    // Point at the end of the body, where the return is missing.
+                    let source = function
+                        .body()
+                        .and_then(|body| body.right_brace_token())
+                        .map(NodeOrToken::Token)
+                        .unwrap_or_else(|| NodeOrToken::Node(function.syntax().clone()));
+                    let frange = original_file_range(db.upcast(), file_id, &source);
+                    Diagnostic::new(
+                        DiagnosticCode::MISSING_RETURN,
+                        "missing return statement at the end of the function".to_string(),
+                        frange.range,
+                    )
+                },
+                AnyDiagnostic::UnreachableCode { first, last, .. } => {
+                    let first = first.to_node(&root);
+                    let last = last.to_node(&root);
+                    // Statements can end with the whitespace before the next token.
+                    let last_text = last.syntax().text().to_string();
+                    let trailing_whitespace =
+                        TextSize::of(last_text.as_str()) - TextSize::of(last_text.trim_end());
+                    let first = original_file_range(db.upcast(), file_id, first.syntax());
+                    let last = original_file_range(db.upcast(), file_id, last.syntax());
+                    Diagnostic::new(
+                        DiagnosticCode::UNREACHABLE_CODE,
+                        "unreachable code".to_string(),
+                        TextRange::new(first.range.start(), last.range.end() - trailing_whitespace),
+                    )
+                    .with_severity(Severity::Warning)
+                    .unused()
+                },
+                AnyDiagnostic::BreakOutsideLoop { statement } => {
+                    let source = statement.value.to_node(&root);
+                    let frange = original_file_range(db.upcast(), file_id, source.syntax());
+                    Diagnostic::new(
+                        DiagnosticCode::BREAK_OUTSIDE_LOOP,
+                        "`break` outside of a loop or switch".to_string(),
+                        frange.range,
+                    )
+                },
+                AnyDiagnostic::ContinueOutsideLoop { statement } => {
+                    let source = statement.value.to_node(&root);
+                    let frange = original_file_range(db.upcast(), file_id, source.syntax());
+                    Diagnostic::new(
+                        DiagnosticCode::CONTINUE_OUTSIDE_LOOP,
+                        "`continue` outside of a loop".to_string(),
+                        frange.range,
+                    )
+                },
+                AnyDiagnostic::ReturnInContinuing { statement } => {
+                    let source = statement.value.to_node(&root);
+                    let frange = original_file_range(db.upcast(), file_id, source.syntax());
+                    Diagnostic::new(
+                        DiagnosticCode::RETURN_IN_CONTINUING,
+                        "`return` is not allowed in a `continuing` block".to_string(),
+                        frange.range,
+                    )
+                },
+                AnyDiagnostic::MisplacedContinuing { statement } => {
+                    let statement = statement.value.to_node(&root);
+                    let source = match &statement {
+                        ast::Statement::ContinuingStatement(continuing) => {
+                            continuing.continuing_token().map(NodeOrToken::Token)
+                        },
+                        _ => None,
+                    }
+                    .unwrap_or_else(|| NodeOrToken::Node(statement.syntax().clone()));
+                    let frange = original_file_range(db.upcast(), file_id, &source);
+                    Diagnostic::new(
+                        DiagnosticCode::MISPLACED_CONTINUING,
+                        "`continuing` must be the last statement of a loop".to_string(),
+                        frange.range,
+                    )
+                },
+                AnyDiagnostic::UnusedItem { item, name } => {
+                    let name_node = name.value.to_node(&root);
+                    let source = name_node
+                        .ident_token()
+                        .map(NodeOrToken::Token)
+                        .unwrap_or_else(|| NodeOrToken::Node(name_node.syntax().clone()));
+                    let frange = original_file_range(db.upcast(), file_id, &source);
+                    let kind = match item {
+                        ModuleDef::Function(_) => "function",
+                        ModuleDef::GlobalVariable(_) => "global variable",
+                        ModuleDef::GlobalConstant(_) => "constant",
+                        ModuleDef::Override(_) => "override",
+                        ModuleDef::Struct(_) => "struct",
+                        ModuleDef::TypeAlias(_) => "type alias",
+                    };
+                    Diagnostic::new(
+                        DiagnosticCode::UNUSED_ITEM,
+                        format!(
+                            "{kind} `{}` is never used by an entry point",
+                            item.name(db).as_str()
+                        ),
+                        frange.range,
+                    )
+                    .with_severity(Severity::Warning)
+                    .unused()
+                },
+                AnyDiagnostic::UnusedVariable {
+                    binding,
+                    name,
+                    parameter,
+                } => {
+                    let binding = binding.value.to_node(&root);
+                    let source = binding
+                        .name()
+                        .and_then(|name| name.ident_token())
+                        .map(NodeOrToken::Token)
+                        .unwrap_or_else(|| NodeOrToken::Node(binding.syntax().clone()));
+                    let frange = original_file_range(db.upcast(), file_id, &source);
+                    let kind = if parameter { "parameter" } else { "variable" };
+                    Diagnostic::new(
+                        DiagnosticCode::UNUSED_VARIABLE,
+                        format!("unused {kind} `{}`", name.as_str()),
+                        frange.range,
+                    )
+                    .with_severity(Severity::Warning)
+                    .unused()
+                },
+                AnyDiagnostic::Lint {
+                    lint,
+                    file_id: _,
+                    location,
+                    message,
+                } => {
+                    let source = location.to_node(&root);
+                    let range = helpers::trimmed_range(&source);
+                    let frange = original_file_range(db.upcast(), file_id, &range);
+                    // The level of the lint is applied by the severity override.
+                    Diagnostic::new(DiagnosticCode::from_lint(lint), message, frange.range)
+                        .with_severity(Severity::Warning)
                 },
             }
         })
+        .filter(|diagnostic| !suppressions.is_suppressed(diagnostic))
+        .filter_map(|diagnostic| apply_severity_override(config, diagnostic))
         .collect()
 }
 
+fn apply_severity_override(
+    config: &DiagnosticsConfig,
+    diagnostic: Diagnostic,
+) -> Option<Diagnostic> {
+    let severity = match config.severity.get(diagnostic.code.as_str()) {
+        None => return Some(diagnostic),
+        Some(DiagnosticLevel::Off) => return None,
+        Some(DiagnosticLevel::Error) => Severity::Error,
+        Some(DiagnosticLevel::Warning) => Severity::Warning,
+        Some(DiagnosticLevel::Info) => Severity::Info,
+        Some(DiagnosticLevel::Hint) => Severity::WeakWarning,
+    };
+    Some(diagnostic.with_severity(severity))
+}
+
+/// The ranges covered by `// wgsl-analyzer: allow(<code>, ...)` comments.
+///
+/// A comment applies to the item or statement that follows it, or to the whole file when it is
+/// written as `allow-file(<code>, ...)`.
+struct Suppressions {
+    allowed: Vec<(TextRange, Vec<String>)>,
+}
+
+impl Suppressions {
+    fn new(file: &SyntaxNode) -> Self {
+        let allowed = file
+            .descendants_with_tokens()
+            .filter_map(NodeOrToken::into_token)
+            .filter(|token| token.kind() == SyntaxKind::Comment)
+            .filter_map(|comment| {
+                let (scope, codes) = parse_allow_comment(comment.text())?;
+                let range = match scope {
+                    AllowScope::NextItem => {
+                        next_item_or_statement(file, comment.text_range().end())?
+                    },
+                    AllowScope::File => file.text_range(),
+                };
+                Some((range, codes))
+            })
+            .collect();
+        Self { allowed }
+    }
+
+    fn is_suppressed(
+        &self,
+        diagnostic: &Diagnostic,
+    ) -> bool {
+        self.allowed.iter().any(|(range, codes)| {
+            range.contains_range(diagnostic.range)
+                && codes.iter().any(|code| code == diagnostic.code.as_str())
+        })
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum AllowScope {
+    NextItem,
+    File,
+}
+
+fn parse_allow_comment(text: &str) -> Option<(AllowScope, Vec<String>)> {
+    let rest = text
+        .strip_prefix("//")?
+        .trim()
+        .strip_prefix("wgsl-analyzer:")?
+        .trim();
+    let (scope, codes) = if let Some(codes) = rest.strip_prefix("allow-file(") {
+        (AllowScope::File, codes)
+    } else {
+        (AllowScope::NextItem, rest.strip_prefix("allow(")?)
+    };
+    let codes = codes.strip_suffix(')')?;
+    Some((
+        scope,
+        codes
+            .split(',')
+            .map(|code| code.trim().to_owned())
+            .filter(|code| !code.is_empty())
+            .collect(),
+    ))
+}
+
+fn next_item_or_statement(
+    file: &SyntaxNode,
+    offset: TextSize,
+) -> Option<TextRange> {
+    let token = std::iter::successors(
+        file.token_at_offset(offset).right_biased(),
+        SyntaxToken::next_token,
+    )
+    .find(|token| !token.kind().is_trivia())?;
+    token
+        .parent_ancestors()
+        .take_while(|node| node.text_range().start() == token.text_range().start())
+        .find(|node| ast::Item::can_cast(node.kind()) || ast::Statement::can_cast(node.kind()))
+        .map(|node| node.text_range())
+}
+
 fn size_compatible(
     target: VecSize,
     overload: VecSize,
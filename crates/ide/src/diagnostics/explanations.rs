@@ -0,0 +1,438 @@
+//! Long-form explanations for every [`DiagnosticCode`], shown on hover,
+//! by `wgsl-analyzer explain <code>` and in the book.
+
+use super::DiagnosticCode;
+
+pub struct DiagnosticExplanation {
+    pub code: DiagnosticCode,
+    pub summary: &'static str,
+    pub explanation: &'static str,
+    pub example: &'static str,
+}
+
+impl DiagnosticExplanation {
+    /// Renders the explanation as markdown, with the example in a `wgsl` code block.
+    pub fn to_markdown(&self) -> String {
+        format!(
+            "**{}** `{}`\n\n{}\n\n```wgsl\n{}\n```\n",
+            self.summary,
+            self.code.as_str(),
+            self.explanation,
+            self.example.trim_end()
+        )
+    }
+}
+
+pub fn explanation(code: &str) -> Option<&'static DiagnosticExplanation> {
+    EXPLANATIONS
+        .iter()
+        .find(|explanation| explanation.code.as_str() == code)
+}
+
+/// Renders the diagnostics chapter of the book.
+pub fn generate_docs() -> String {
+    let mut docs = String::from("//! Generated by `cargo test -p ide`, do not edit by hand.\n");
+    for explanation in EXPLANATIONS {
+        docs.push_str(&format!(
+            "\n### {}\n\n{}\n\n{}\n\n```wgsl\n{}\n```\n",
+            explanation.code.as_str(),
+            explanation.summary,
+            explanation.explanation,
+            explanation.example.trim_end()
+        ));
+    }
+    docs
+}
+
+pub static EXPLANATIONS: &[DiagnosticExplanation] = &[
+    DiagnosticExplanation {
+        code: DiagnosticCode::ASSIGNMENT_NOT_A_REFERENCE,
+        summary: "The left hand side of an assignment is not a reference.",
+        explanation: "Only memory locations can be assigned to: variables, \
+            their components and dereferenced pointers. Values such as `let` bindings, \
+            constants or the result of a function call cannot be assigned.",
+        example: "\
+fn main() {
+    let x = 1;
+    x = 2; // error: `x` is a value, use `var x = 1;` instead
+}",
+    },
+    DiagnosticExplanation {
+        code: DiagnosticCode::TYPE_MISMATCH,
+        summary: "An expression has a different type than expected.",
+        explanation: "WGSL performs no implicit conversions between concrete types. \
+            Use a conversion such as `f32(x)` or change the declared type.",
+        example: "\
+fn main() {
+    let a: f32 = 1u; // error: expected f32, found u32
+    let b: f32 = f32(1u); // ok
+}",
+    },
+    DiagnosticExplanation {
+        code: DiagnosticCode::NO_SUCH_FIELD,
+        summary: "A field or swizzle does not exist on the accessed type.",
+        explanation: "Member accesses must name a field of the struct, or a valid \
+            swizzle of the vector (`xyzw` or `rgba`, within the vector's size).",
+        example: "\
+struct Light { color: vec3<f32> }
+
+fn main() {
+    var light: Light;
+    let c = light.colour; // error: no field `colour`
+    let w = light.color.w; // error: vec3 has no `w` component
+}",
+    },
+    DiagnosticExplanation {
+        code: DiagnosticCode::ARRAY_ACCESS_INVALID_TYPE,
+        summary: "An index expression is applied to a type that cannot be indexed.",
+        explanation: "Only arrays, vectors and matrices (and references or pointers \
+            to them) can be indexed with `[]`.",
+        example: "\
+fn main() {
+    let x = 1.0;
+    let y = x[0]; // error: cannot index into type f32
+}",
+    },
+    DiagnosticExplanation {
+        code: DiagnosticCode::UNRESOLVED_NAME,
+        summary: "A name does not refer to any declaration in scope.",
+        explanation: "Check the spelling, and that the declaration is visible here: \
+            local declarations are only in scope after they appear, and imported \
+            items must be imported explicitly.",
+        example: "\
+fn main() {
+    let a = b + 1; // error: cannot find `b` in this scope
+}",
+    },
+    DiagnosticExplanation {
+        code: DiagnosticCode::INVALID_CONSTRUCTION_TYPE,
+        summary: "A value constructor is used with a type that cannot be constructed.",
+        explanation: "Only constructible types (scalars, vectors, matrices, fixed-size \
+            arrays and structs made of those) have value constructors. Pointers, \
+            textures, samplers and runtime-sized arrays cannot be constructed.",
+        example: "\
+fn main() {
+    let s = sampler(); // error: cannot construct value of type sampler
+}",
+    },
+    DiagnosticExplanation {
+        code: DiagnosticCode::ARGUMENT_COUNT_MISMATCH,
+        summary: "A function is called with the wrong number of arguments.",
+        explanation: "User-defined functions have no default or variadic parameters, \
+            so every call must pass exactly one argument per parameter.",
+        example: "\
+fn add(a: f32, b: f32) -> f32 { return a + b; }
+
+fn main() {
+    let x = add(1.0); // error: expected 2 parameters, found 1
+}",
+    },
+    DiagnosticExplanation {
+        code: DiagnosticCode::NO_BUILTIN_OVERLOAD,
+        summary: "No overload of a builtin function accepts the given arguments.",
+        explanation: "The message lists the argument types that were found and every \
+            available overload. Convert the arguments to match one of them.",
+        example: "\
+fn main() {
+    let x = clamp(1.0, 0u, 1u); // error: mixing f32 and u32
+}",
+    },
+    DiagnosticExplanation {
+        code: DiagnosticCode::ADDRESS_OF_NOT_REFERENCE,
+        summary: "The address-of operator `&` is applied to a value.",
+        explanation: "Only memory locations (variables and their components) have an \
+            address. Store the value in a `var` first to take a pointer to it.",
+        example: "\
+fn main() {
+    let x = 1.0;
+    let p = &x; // error: expected a reference, found f32
+}",
+    },
+    DiagnosticExplanation {
+        code: DiagnosticCode::DEREF_NOT_POINTER,
+        summary: "The dereference operator `*` is applied to a non-pointer.",
+        explanation: "Only expressions of pointer type can be dereferenced.",
+        example: "\
+fn main() {
+    var x = 1.0;
+    let y = *x; // error: cannot dereference expression of type f32
+}",
+    },
+    DiagnosticExplanation {
+        code: DiagnosticCode::MISSING_STORAGE_CLASS,
+        summary: "A module-scope `var` has no address space.",
+        explanation: "Global variables must state their address space, for example \
+            `var<private>`, `var<workgroup>`, `var<uniform>` or `var<storage>`. \
+            Only handle types (textures and samplers) may omit it.",
+        example: "\
+var counter: u32; // error: missing storage class
+var<private> ok: u32;",
+    },
+    DiagnosticExplanation {
+        code: DiagnosticCode::INVALID_STORAGE_CLASS,
+        summary: "A global variable's address space or access mode is not allowed.",
+        explanation: "Each address space restricts which types and access modes a \
+            variable may use. For example, `uniform` buffers are always read-only, \
+            and `workgroup` variables cannot have an initializer.",
+        example: "\
+@group(0) @binding(0)
+var<uniform, read_write> u: vec4<f32>; // error: uniform buffers are read-only",
+    },
+    DiagnosticExplanation {
+        code: DiagnosticCode::INVALID_TYPE,
+        summary: "A type is used somewhere it is not allowed.",
+        explanation: "Examples are runtime-sized arrays outside of storage buffers, \
+            or types that are not host-shareable in `uniform` and `storage` buffers.",
+        example: "\
+var<private> values: array<f32>; // error: runtime-sized array in private memory",
+    },
+    DiagnosticExplanation {
+        code: DiagnosticCode::UNRESOLVED_IMPORT,
+        summary: "An `#import` or `import` does not resolve to a known module.",
+        explanation: "Imports are resolved against the configured custom imports \
+            and against files in the workspace that declare a matching import path.",
+        example: "\
+#import bevy_pbr::mesh_functions // error if no file declares this path",
+    },
+    DiagnosticExplanation {
+        code: DiagnosticCode::NAGA_VALIDATION_ERROR,
+        summary: "naga rejected the shader.",
+        explanation: "These diagnostics come from naga, the shader translator used by \
+            wgpu, and mirror what your application will report at runtime. The naga \
+            version can be chosen with `wgsl-analyzer.diagnostics.nagaVersion`.",
+        example: "\
+@fragment
+fn main() -> vec4<f32> { // naga: missing `@location` on the return value
+    return vec4(1.0);
+}",
+    },
+    DiagnosticExplanation {
+        code: DiagnosticCode::PARSE_ERROR,
+        summary: "The source does not follow the WGSL grammar.",
+        explanation: "The parser recovers and keeps analyzing the rest of the file, so \
+            follow-up diagnostics may disappear once this one is fixed.",
+        example: "\
+fn main() {
+    let x = 1 // error: expected `;`
+}",
+    },
+    DiagnosticExplanation {
+        code: DiagnosticCode::INACTIVE_CODE,
+        summary: "Code is disabled by `#ifdef` directives or an `@if` attribute.",
+        explanation: "The code is not analyzed because the shader definitions it \
+            depends on are not enabled. Configure them with \
+            `wgsl-analyzer.preprocessor.shaderDefs`.",
+        example: "\
+#ifdef SHADOWS
+fn sample_shadow() {} // inactive unless `SHADOWS` is defined
+#endif",
+    },
+    DiagnosticExplanation {
+        code: DiagnosticCode::NO_CONSTRUCTOR,
+        summary: "No constructor overload accepts the given arguments.",
+        explanation: "The message lists the argument types that were found and the \
+            constructor overloads of the target type.",
+        example: "\
+fn main() {
+    let v = vec3<f32>(1.0, 2.0); // error: needs 1 or 3 components
+}",
+    },
+    DiagnosticExplanation {
+        code: DiagnosticCode::PRECEDENCE_NEVER_NESTED,
+        summary: "Shift or comparison operators are combined without parentheses.",
+        explanation: "WGSL does not define a precedence between shifts, comparisons \
+            and other binary operators, so their operands must be unary expressions \
+            or parenthesized.",
+        example: "\
+fn main() {
+    let a = 1u << 2u + 1u; // error
+    let b = 1u << (2u + 1u); // ok
+}",
+    },
+    DiagnosticExplanation {
+        code: DiagnosticCode::PRECEDENCE_SEQUENCE_ONLY,
+        summary: "Logical or bitwise operators are mixed without parentheses.",
+        explanation: "`&&`, `||`, `&`, `|` and `^` can be chained with themselves, but \
+            mixing them with other binary operators requires parentheses.",
+        example: "\
+fn main() {
+    let a = true && false || true; // error
+    let b = (true && false) || true; // ok
+    let c = 1u & 2u & 3u; // ok
+}",
+    },
+    DiagnosticExplanation {
+        code: DiagnosticCode::MISSING_RETURN,
+        summary: "A function with a return type can reach its end without returning a value.",
+        explanation: "Every path through a function which declares a return type must end in a \
+            `return` statement or a `discard`. Falling off the end of the body is an error.",
+        example: "\
+fn sign(x: f32) -> f32 {
+    if x < 0.0 {
+        return -1.0;
+    }
+} // error: missing return",
+    },
+    DiagnosticExplanation {
+        code: DiagnosticCode::UNREACHABLE_CODE,
+        summary: "Statements which can never be executed.",
+        explanation: "Statements following a `return`, `discard`, `break` or `continue` in the \
+            same block are never executed.",
+        example: "\
+fn main() -> u32 {
+    return 1u;
+    let x = 2u; // warning: unreachable
+}",
+    },
+    DiagnosticExplanation {
+        code: DiagnosticCode::BREAK_OUTSIDE_LOOP,
+        summary: "A `break` statement outside of a loop or switch.",
+        explanation: "`break` leaves the innermost `loop`, `for`, `while` or `switch` statement, \
+            so it can only appear inside one of them.",
+        example: "\
+fn main() {
+    break; // error
+}",
+    },
+    DiagnosticExplanation {
+        code: DiagnosticCode::CONTINUE_OUTSIDE_LOOP,
+        summary: "A `continue` statement outside of a loop.",
+        explanation: "`continue` starts the next iteration of the innermost `loop`, `for` or \
+            `while` statement, so it can only appear inside one of them.",
+        example: "\
+fn main() {
+    continue; // error
+}",
+    },
+    DiagnosticExplanation {
+        code: DiagnosticCode::RETURN_IN_CONTINUING,
+        summary: "A `return` statement inside a `continuing` block.",
+        explanation: "The `continuing` block of a loop runs at the end of every iteration, \
+            and must not leave the function.",
+        example: "\
+fn main() {
+    loop {
+        continuing {
+            return; // error
+        }
+    }
+}",
+    },
+    DiagnosticExplanation {
+        code: DiagnosticCode::MISPLACED_CONTINUING,
+        summary: "A `continuing` block which is not the last statement of a `loop`.",
+        explanation: "A `continuing` block can only appear as the last statement in the body \
+            of a `loop`.",
+        example: "\
+fn main() {
+    loop {
+        continuing {} // error
+        break;
+    }
+}",
+    },
+    DiagnosticExplanation {
+        code: DiagnosticCode::UNUSED_ITEM,
+        summary: "An item which is not used by any entry point.",
+        explanation: "Functions, globals, overrides, structs and type aliases which cannot be \
+            reached from a `@vertex`, `@fragment` or `@compute` function are never part of \
+            the shader. Files without entry points are treated as libraries and are not checked. \
+            Prefix the name with `_` to silence the warning.",
+        example: "\
+fn unused() {} // warning
+
+@compute @workgroup_size(1)
+fn main() {}",
+    },
+    DiagnosticExplanation {
+        code: DiagnosticCode::UNUSED_VARIABLE,
+        summary: "A local variable or parameter which is never used.",
+        explanation: "The parameters of entry points are not checked, as they are part of the \
+            interface of the pipeline stage. Prefix the name with `_` to silence the \
+            warning.",
+        example: "\
+fn add(a: u32, b: u32) -> u32 { // warning: unused parameter `b`
+    let c = a * 2u; // warning: unused variable `c`
+    return a;
+}",
+    },
+    DiagnosticExplanation {
+        code: DiagnosticCode::FLOAT_EQUALITY,
+        summary: "Floating point values are compared with `==` or `!=`.",
+        explanation: "This lint is off unless a level is configured for it. The result of \
+            floating point arithmetic is rounded, so values which are equal mathematically are \
+            often not equal exactly. Compare the difference against a tolerance instead.",
+        example: "\
+fn is_unit(v: vec3<f32>) -> bool {
+    return length(v) == 1.0; // lint: use `abs(length(v) - 1.0) < 1e-5`
+}",
+    },
+    DiagnosticExplanation {
+        code: DiagnosticCode::INTEGER_DIVISION_IN_FLOAT,
+        summary: "An integer division is converted to a floating point type.",
+        explanation: "This lint is off unless a level is configured for it. The division \
+            of integers discards the remainder before the conversion. Convert the operands \
+            instead to keep the fractional part.",
+        example: "\
+fn ratio(done: u32, total: u32) -> f32 {
+    return f32(done / total); // lint: use `f32(done) / f32(total)`
+}",
+    },
+    DiagnosticExplanation {
+        code: DiagnosticCode::SHADOWED_BUILTIN,
+        summary: "A declaration has the name of a builtin function.",
+        explanation: "This lint is off unless a level is configured for it. The builtin \
+            function cannot be called where the declaration is in scope.",
+        example: "\
+fn main() {
+    let max = 4; // lint: `max` shadows the builtin function `max`
+}",
+    },
+    DiagnosticExplanation {
+        code: DiagnosticCode::SELECT_ARGUMENT_ORDER,
+        summary: "The arguments of `select` look swapped.",
+        explanation: "This lint is off unless a level is configured for it. `select(f, t, \
+            condition)` returns `t` when the condition is true, which is the opposite order of \
+            the conditional operator of other languages. The lint checks calls whose first \
+            argument is `true`, `1` or `1.0` and whose second is `false`, `0` or `0.0`.",
+        example: "\
+fn mask(lit: bool) -> f32 {
+    return select(1.0, 0.0, lit); // lint: returns 0.0 when `lit` is true
+}",
+    },
+    DiagnosticExplanation {
+        code: DiagnosticCode::WORKGROUP_SIZE_NOT_POWER_OF_TWO,
+        summary: "The number of invocations of a workgroup is not a power of two.",
+        explanation: "This lint is off unless a level is configured for it. Invocations are \
+            scheduled in groups whose size is a power of two, so other workgroup sizes leave \
+            some of them idle. Only sizes whose dimensions are all literals are checked.",
+        example: "\
+@compute @workgroup_size(10, 10) // lint: 100 invocations
+fn main() {}",
+    },
+    DiagnosticExplanation {
+        code: DiagnosticCode::PRIVATE_NEVER_WRITTEN,
+        summary: "A `var<private>` is never written.",
+        explanation: "This lint is off unless a level is configured for it. A variable in the \
+            `private` address space which is never assigned to, and whose address is never \
+            taken, always has its initial value and can be a `const` instead. Files without \
+            entry points are treated as libraries and are not checked.",
+        example: "\
+var<private> scale: f32 = 2.0; // lint: use `const scale = 2.0;`
+
+@compute @workgroup_size(1)
+fn main() {
+    let x = scale;
+}",
+    },
+    DiagnosticExplanation {
+        code: DiagnosticCode::REDUNDANT_CONVERSION,
+        summary: "A value is converted to the type it already has.",
+        explanation: "This lint is off unless a level is configured for it. The conversion \
+            has no effect and can be removed.",
+        example: "\
+fn half(x: f32) -> f32 {
+    return f32(x) / 2.0; // lint: `x` already has the type `f32`
+}",
+    },
+];
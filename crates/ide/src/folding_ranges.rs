@@ -0,0 +1,119 @@
+use base_db::TextRange;
+use rowan::NodeOrToken;
+use syntax::{AstNode, SyntaxKind, SyntaxNode, ast};
+
+use crate::helpers;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FoldKind {
+    /// Consecutive line comments.
+    Comment,
+    /// Consecutive `#import`s.
+    Imports,
+    /// An `#ifdef ... #endif` region.
+    Region,
+    /// The braces of a function or struct body, a compound statement or a switch.
+    Block,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fold {
+    pub range: TextRange,
+    pub kind: FoldKind,
+}
+
+/// The foldable ranges of the file, which must be parsed without running the preprocessor so
+/// that the `#ifdef`s are still in the tree.
+pub(crate) fn folding_ranges(file: &ast::SourceFile) -> Vec<Fold> {
+    let root = file.syntax();
+    let mut folds = Vec::new();
+    let mut comments: Option<(TextRange, usize)> = None;
+    let mut regions = Vec::new();
+
+    for element in root.descendants_with_tokens() {
+        let token = match element {
+            NodeOrToken::Node(node) => {
+                if matches!(
+                    node.kind(),
+                    SyntaxKind::CompoundStatement
+                        | SyntaxKind::StructDeclBody
+                        | SyntaxKind::SwitchBlock
+                ) {
+                    push_multiline(
+                        &mut folds,
+                        root,
+                        helpers::trimmed_range(&node),
+                        FoldKind::Block,
+                    );
+                }
+                continue;
+            },
+            NodeOrToken::Token(token) => token,
+        };
+
+        match token.kind() {
+            SyntaxKind::Comment => {
+                comments = Some(match comments {
+                    Some((range, count)) => (range.cover(token.text_range()), count + 1),
+                    None => (token.text_range(), 1),
+                });
+                continue;
+            },
+            // A blank line ends a run of comments.
+            SyntaxKind::Whitespace if token.text().matches('\n').count() < 2 => continue,
+            SyntaxKind::UnofficialPreprocessorIfDef | SyntaxKind::UnofficialPreprocessIf => {
+                regions.push(token.text_range().start());
+            },
+            SyntaxKind::UnofficialPreprocessorEndif => {
+                if let Some(start) = regions.pop() {
+                    let range = TextRange::new(start, token.text_range().end());
+                    push_multiline(&mut folds, root, range, FoldKind::Region);
+                }
+            },
+            _ => {},
+        }
+        push_run(&mut folds, comments.take(), FoldKind::Comment);
+    }
+    push_run(&mut folds, comments, FoldKind::Comment);
+
+    let mut imports: Option<(TextRange, usize)> = None;
+    for node in root.children() {
+        if node.kind() == SyntaxKind::Import {
+            let range = helpers::trimmed_range(&node);
+            imports = Some(match imports {
+                Some((imports, count)) => (imports.cover(range), count + 1),
+                None => (range, 1),
+            });
+        } else {
+            push_run(&mut folds, imports.take(), FoldKind::Imports);
+        }
+    }
+    push_run(&mut folds, imports, FoldKind::Imports);
+
+    folds.sort_by_key(|fold| fold.range.start());
+    folds
+}
+
+/// Folds a run of comments or imports, unless it is a single one.
+fn push_run(
+    folds: &mut Vec<Fold>,
+    run: Option<(TextRange, usize)>,
+    kind: FoldKind,
+) {
+    if let Some((range, count)) = run {
+        if count > 1 {
+            folds.push(Fold { range, kind });
+        }
+    }
+}
+
+fn push_multiline(
+    folds: &mut Vec<Fold>,
+    root: &SyntaxNode,
+    range: TextRange,
+    kind: FoldKind,
+) {
+    if root.text().slice(range).contains_char('\n') {
+        folds.push(Fold { range, kind });
+    }
+}
@@ -1,25 +1,146 @@
-use base_db::{FileId, SourceDatabase, TextRange};
+use base_db::{FileId, FilePosition, SourceDatabase, TextRange, TextSize};
 use rowan::NodeOrToken;
-use syntax::{AstNode, SyntaxNode, ast};
+use syntax::{AstNode, SyntaxKind, SyntaxNode, SyntaxToken, ast};
 use wgsl_formatter::FormattingOptions;
 
 use crate::RootDatabase;
 
+/// Formats the whole file or, with a `range`, the items or statements covering it.
+/// Returns the root of the formatted file in both cases.
 pub(crate) fn format(
     db: &RootDatabase,
     file_id: FileId,
     range: Option<TextRange>,
+    options: &FormattingOptions,
 ) -> Option<SyntaxNode> {
+    let edition = db.file_edition(file_id);
+    let Some(range) = range else {
+        // Formatting the text, rather than the tree, also formats the branches of
+        // an `#ifdef` which only parse on their own.
+        let formatted =
+            wgsl_formatter::format_str_with_edition(&db.file_text(file_id), edition, options);
+        return Some(syntax::parse_with_edition(&formatted, edition).syntax());
+    };
+
     let file: ast::SourceFile = db.parse_no_preprocessor(file_id).tree();
+    let root = file.syntax().clone_for_update();
+    for node in formatting_units(&root, range) {
+        wgsl_formatter::format_recursive(node, options);
+    }
+    Some(root)
+}
 
-    let node = match range {
-        None => file.syntax().clone_for_update(),
-        Some(range) => match file.syntax().covering_element(range) {
-            NodeOrToken::Node(node) => node.clone_for_update(),
-            NodeOrToken::Token(_) => return None,
+/// The smallest items or statements which cover `range`.
+fn formatting_units(
+    root: &SyntaxNode,
+    range: TextRange,
+) -> Vec<SyntaxNode> {
+    let node = match root.covering_element(range) {
+        NodeOrToken::Node(node) => node,
+        NodeOrToken::Token(token) => match token.parent() {
+            Some(parent) => parent,
+            None => return Vec::new(),
         },
     };
+    let Some(unit) = node
+        .ancestors()
+        .find(|node| is_unit(node) || is_container(node))
+    else {
+        return Vec::new();
+    };
+    if !is_container(&unit) {
+        return vec![unit];
+    }
+    let children: Vec<SyntaxNode> = unit
+        .children()
+        .filter(|child| is_unit(child) && child.text_range().intersect(range).is_some())
+        .collect();
+    if children.is_empty() && unit.kind() == SyntaxKind::CompoundStatement {
+        // For example a `}`, which belongs to the block itself.
+        return vec![unit];
+    }
+    children
+}
+
+fn is_unit(node: &SyntaxNode) -> bool {
+    ast::Item::can_cast(node.kind()) || ast::Statement::can_cast(node.kind())
+}
+
+/// Nodes which contain a list of units.
+fn is_container(node: &SyntaxNode) -> bool {
+    matches!(
+        node.kind(),
+        SyntaxKind::SourceFile | SyntaxKind::CompoundStatement
+    )
+}
+
+/// The edit to make after the user pressed enter: the indentation of the new
+/// line and, between a pair of braces or parentheses, the closing one moved onto
+/// its own line. `$0` marks the cursor position.
+#[derive(Debug)]
+pub struct OnEnterEdit {
+    pub range: TextRange,
+    pub snippet: String,
+}
+
+/// `position` is the start of the line after the newline which was typed.
+pub(crate) fn on_enter(
+    db: &RootDatabase,
+    position: FilePosition,
+    options: &FormattingOptions,
+) -> Option<OnEnterEdit> {
+    let text = db.file_text(position.file_id);
+    let offset = usize::from(position.offset);
+    if !text.get(..offset)?.ends_with('\n') {
+        return None;
+    }
+    let file: ast::SourceFile = db.parse_no_preprocessor(position.file_id).tree();
+
+    let (open, close) = enclosing_delimiters(file.syntax(), position.offset)?;
+    let open_start = usize::from(open.text_range().start());
+    let line_start = text[..open_start].rfind('\n').map_or(0, |index| index + 1);
+    let line_indent: String = text[line_start..open_start]
+        .chars()
+        .take_while(|character| matches!(character, ' ' | '\t'))
+        .collect();
+    let indent = format!("{line_indent}{}", options.indent_symbol);
+
+    // The client may already have indented the new line.
+    let rest = &text[offset..];
+    let existing_indent = rest.len() - rest.trim_start_matches([' ', '\t']).len();
+    let range = TextRange::at(position.offset, TextSize::from(existing_indent as u32));
+    let is_before_close = usize::from(close.text_range().start()) == offset + existing_indent;
+
+    let snippet = if is_before_close {
+        format!("{indent}$0\n{line_indent}")
+    } else {
+        format!("{indent}$0")
+    };
+    Some(OnEnterEdit { range, snippet })
+}
 
-    wgsl_formatter::format_recursive(node.clone(), &FormattingOptions::default());
-    Some(node)
+/// The innermost braces or parentheses of a block or an argument list around `offset`.
+fn enclosing_delimiters(
+    root: &SyntaxNode,
+    offset: TextSize,
+) -> Option<(SyntaxToken, SyntaxToken)> {
+    let token = root.token_at_offset(offset).left_biased()?;
+    token.parent_ancestors().find_map(|node| {
+        let (open_kind, close_kind) = match node.kind() {
+            SyntaxKind::CompoundStatement
+            | SyntaxKind::SwitchBlock
+            | SyntaxKind::StructDeclBody => (SyntaxKind::BraceLeft, SyntaxKind::BraceRight),
+            SyntaxKind::FunctionParameterList | SyntaxKind::ParameterList => {
+                (SyntaxKind::ParenthesisLeft, SyntaxKind::ParenthesisRight)
+            },
+            _ => return None,
+        };
+        let mut tokens = node
+            .children_with_tokens()
+            .filter_map(NodeOrToken::into_token);
+        let open = tokens.find(|token| token.kind() == open_kind)?;
+        let close = tokens.find(|token| token.kind() == close_kind)?;
+        (open.text_range().end() <= offset && offset <= close.text_range().start())
+            .then_some((open, close))
+    })
 }
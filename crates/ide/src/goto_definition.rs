@@ -1,10 +1,13 @@
 use base_db::{FilePosition, TextRange};
 use hir::{HasSource, HirDatabase, Local, Semantics, definition::Definition};
-use hir_def::{InFile, db::DefDatabase};
-use syntax::{AstNode, HasName, SyntaxKind};
+use hir_def::{HirFileId, InFile, db::DefDatabase, module_data::Name};
+use syntax::{AstNode, HasName, SyntaxKind, SyntaxNode, ast};
 use vfs::FileId;
 
-use crate::helpers;
+use crate::{
+    builtin_document::{self, BuiltinDocument},
+    helpers,
+};
 
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct NavigationTarget {
@@ -48,10 +51,20 @@ impl NavigationTarget {
     }
 }
 
+/// Where go to definition leads from a name.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum GotoDefinition {
+    /// The declarations the name may refer to, more than one when several of the imported
+    /// modules declare it. The first one is the declaration the name resolves to.
+    Declarations(Vec<NavigationTarget>),
+    /// A builtin function, declared in a generated document.
+    Builtin(BuiltinDocument),
+}
+
 pub(crate) fn goto_definition(
     db: &dyn HirDatabase,
     file_position: FilePosition,
-) -> Option<NavigationTarget> {
+) -> Option<GotoDefinition> {
     let sema = &Semantics::new(db);
     let file_id = file_position.file_id;
     let file = db.parse(file_id).tree();
@@ -63,11 +76,24 @@ pub(crate) fn goto_definition(
         _ => 1,
     })?;
 
-    let definition = Definition::from_token(sema, file_id.into(), &token)?;
-    InFile::new(file_id.into(), definition).to_nav(db.upcast())
+    let definitions = Definition::all_from_token(sema, file_id.into(), &token);
+    if definitions.is_empty() {
+        let name_ref = token.parent().and_then(ast::NameReference::cast)?;
+        if !ast::FunctionCall::can_cast(name_ref.syntax().parent()?.kind()) {
+            return None;
+        }
+        let name = Name::from(name_ref);
+        let document = builtin_document::builtin_document(db, name.as_str())?;
+        return Some(GotoDefinition::Builtin(document));
+    }
+    let targets = definitions
+        .into_iter()
+        .filter_map(|definition| InFile::new(file_id.into(), definition).to_nav(db.upcast()))
+        .collect();
+    Some(GotoDefinition::Declarations(targets))
 }
 
-trait ToNav {
+pub(crate) trait ToNav {
     fn to_nav(
         &self,
         db: &dyn DefDatabase,
@@ -92,107 +118,119 @@ impl ToNav for InFile<Definition> {
         &self,
         db: &dyn DefDatabase,
     ) -> Option<NavigationTarget> {
-        let nav =
-            match &self.value {
-                Definition::Local(local) => InFile::new(self.file_id, *local).to_nav(db)?,
-                Definition::ModuleDef(def) => {
-                    match def {
-                        hir::ModuleDef::Function(function) => {
-                            let declaration = function.source(db)?;
-
-                            let frange = declaration.original_file_range(db);
-                            let focus_range = declaration.value.name().map(|name| {
-                                declaration.with_value(name).original_file_range(db).range
-                            });
-
-                            NavigationTarget::from_syntax(frange.file_id, frange.range, focus_range)
-                        },
-                        hir::ModuleDef::GlobalVariable(var) => {
-                            let declaration = var.source(db)?;
-
-                            let frange = declaration.original_file_range(db);
-                            let focus_range = declaration.value.binding().map(|name| {
-                                declaration.with_value(name).original_file_range(db).range
-                            });
-
-                            NavigationTarget::from_syntax(frange.file_id, frange.range, focus_range)
-                        },
-                        hir::ModuleDef::GlobalConstant(constant) => {
-                            let declaration = constant.source(db)?;
-
-                            let frange = declaration.original_file_range(db);
-                            let focus_range = declaration.value.binding().map(|name| {
-                                declaration.with_value(name).original_file_range(db).range
-                            });
-
-                            NavigationTarget::from_syntax(frange.file_id, frange.range, focus_range)
-                        },
-                        hir::ModuleDef::Override(override_declaration) => {
-                            let declaration = override_declaration.source(db)?;
-
-                            let frange = declaration.original_file_range(db);
-                            let focus_range = declaration.value.binding().map(|name| {
-                                declaration.with_value(name).original_file_range(db).range
-                            });
-
-                            NavigationTarget::from_syntax(frange.file_id, frange.range, focus_range)
-                        },
-                        hir::ModuleDef::Struct(r#struct) => {
-                            let declaration = r#struct.source(db)?;
-
-                            let frange = declaration.original_file_range(db);
-                            let focus_range = declaration.value.name().map(|name| {
-                                declaration.with_value(name).original_file_range(db).range
-                            });
-
-                            NavigationTarget::from_syntax(frange.file_id, frange.range, focus_range)
-                        },
-                        hir::ModuleDef::TypeAlias(type_alias) => {
-                            let declaration = type_alias.source(db)?;
-
-                            let frange = declaration.original_file_range(db);
-                            let focus_range = declaration.value.name().map(|name| {
-                                declaration.with_value(name).original_file_range(db).range
-                            });
-
-                            NavigationTarget::from_syntax(frange.file_id, frange.range, focus_range)
-                        },
-                    }
+        let nav = match &self.value {
+            Definition::Local(local) => InFile::new(self.file_id, *local).to_nav(db)?,
+            Definition::ModuleDef(def) => match def {
+                hir::ModuleDef::Function(function) => {
+                    let declaration = function.source(db)?;
+
+                    let frange = declaration.original_file_range(db);
+                    let focus_range = declaration
+                        .value
+                        .name()
+                        .map(|name| focus_range(db, declaration.file_id, name.syntax()));
+
+                    NavigationTarget::from_syntax(frange.file_id, frange.range, focus_range)
                 },
-                Definition::Field(field) => {
-                    let declaration = field.source(db)?;
+                hir::ModuleDef::GlobalVariable(var) => {
+                    let declaration = var.source(db)?;
 
                     let frange = declaration.original_file_range(db);
                     let focus_range = declaration
                         .value
-                        .variable_ident_declaration()
-                        .map(|name| declaration.with_value(name).original_file_range(db).range);
+                        .binding()
+                        .map(|name| focus_range(db, declaration.file_id, name.syntax()));
 
                     NavigationTarget::from_syntax(frange.file_id, frange.range, focus_range)
                 },
-                Definition::Struct(r#struct) => {
-                    let declaration = r#struct.source(db)?;
+                hir::ModuleDef::GlobalConstant(constant) => {
+                    let declaration = constant.source(db)?;
+
                     let frange = declaration.original_file_range(db);
+                    let focus_range = declaration
+                        .value
+                        .binding()
+                        .map(|name| focus_range(db, declaration.file_id, name.syntax()));
 
+                    NavigationTarget::from_syntax(frange.file_id, frange.range, focus_range)
+                },
+                hir::ModuleDef::Override(override_declaration) => {
+                    let declaration = override_declaration.source(db)?;
+
+                    let frange = declaration.original_file_range(db);
+                    let focus_range = declaration
+                        .value
+                        .binding()
+                        .map(|name| focus_range(db, declaration.file_id, name.syntax()));
+
+                    NavigationTarget::from_syntax(frange.file_id, frange.range, focus_range)
+                },
+                hir::ModuleDef::Struct(r#struct) => {
+                    let declaration = r#struct.source(db)?;
+
+                    let frange = declaration.original_file_range(db);
                     let focus_range = declaration
                         .value
                         .name()
-                        .map(|name| declaration.with_value(name).original_file_range(db).range);
+                        .map(|name| focus_range(db, declaration.file_id, name.syntax()));
 
                     NavigationTarget::from_syntax(frange.file_id, frange.range, focus_range)
                 },
-                Definition::TypeAlias(type_alias) => {
+                hir::ModuleDef::TypeAlias(type_alias) => {
                     let declaration = type_alias.source(db)?;
-                    let frange = declaration.original_file_range(db);
 
+                    let frange = declaration.original_file_range(db);
                     let focus_range = declaration
                         .value
                         .name()
-                        .map(|name| declaration.with_value(name).original_file_range(db).range);
+                        .map(|name| focus_range(db, declaration.file_id, name.syntax()));
 
                     NavigationTarget::from_syntax(frange.file_id, frange.range, focus_range)
                 },
-            };
+            },
+            Definition::Field(field) => {
+                let declaration = field.source(db)?;
+
+                let frange = declaration.original_file_range(db);
+                let focus_range = declaration
+                    .value
+                    .variable_ident_declaration()
+                    .map(|name| focus_range(db, declaration.file_id, name.syntax()));
+
+                NavigationTarget::from_syntax(frange.file_id, frange.range, focus_range)
+            },
+            Definition::Struct(r#struct) => {
+                let declaration = r#struct.source(db)?;
+                let frange = declaration.original_file_range(db);
+
+                let focus_range = declaration
+                    .value
+                    .name()
+                    .map(|name| focus_range(db, declaration.file_id, name.syntax()));
+
+                NavigationTarget::from_syntax(frange.file_id, frange.range, focus_range)
+            },
+            Definition::TypeAlias(type_alias) => {
+                let declaration = type_alias.source(db)?;
+                let frange = declaration.original_file_range(db);
+
+                let focus_range = declaration
+                    .value
+                    .name()
+                    .map(|name| focus_range(db, declaration.file_id, name.syntax()));
+
+                NavigationTarget::from_syntax(frange.file_id, frange.range, focus_range)
+            },
+        };
         Some(nav)
     }
 }
+
+/// The range of the name of a declaration, without the trivia after it.
+fn focus_range(
+    db: &dyn DefDatabase,
+    file_id: HirFileId,
+    name: &SyntaxNode,
+) -> TextRange {
+    hir_def::original_file_range(db, file_id, &helpers::trimmed_range(name)).range
+}
@@ -0,0 +1,88 @@
+use base_db::FilePosition;
+use hir::{HasSource, HirDatabase, ModuleDef, Semantics, definition::Definition};
+use hir_def::{InFile, db::DefDatabase};
+use syntax::{AstNode, SyntaxKind, ast};
+
+use crate::{
+    goto_definition::{NavigationTarget, ToNav},
+    helpers,
+};
+
+/// The declaration of the struct or type alias which is the type of the declaration or
+/// expression at the position.
+pub(crate) fn goto_type_definition(
+    db: &dyn HirDatabase,
+    position: FilePosition,
+) -> Option<NavigationTarget> {
+    let sema = &Semantics::new(db);
+    let file_id = position.file_id;
+    let file = sema.parse(file_id);
+    let token = file.syntax().token_at_offset(position.offset);
+    let token = helpers::pick_best_token(token, |kind| match kind {
+        SyntaxKind::Identifier => 2,
+        kind if kind.is_trivia() => 0,
+        _ => 1,
+    })?;
+
+    let definition = match token.parent().and_then(ast::Name::cast) {
+        Some(name) => Definition::from_name(sema, file_id.into(), &name),
+        None => Definition::from_token(sema, file_id.into(), &token),
+    };
+    let type_definition = match definition {
+        Some(definition @ (Definition::Struct(_) | Definition::TypeAlias(_))) => Some(definition),
+        Some(definition) => declared_type(sema, &definition)
+            .or_else(|| Definition::from_type(db, definition.value_type(db)?)),
+        None => None,
+    };
+    // Otherwise, the type of the expression the token is part of, such as a call.
+    let type_definition = type_definition.or_else(|| {
+        let expression = token.parent_ancestors().find_map(ast::Expression::cast)?;
+        let def = sema.find_container(file_id.into(), expression.syntax())?;
+        let r#type = sema.analyze(def).type_of_expression(&expression)?;
+        Definition::from_type(db, r#type)
+    })?;
+    InFile::new(file_id.into(), type_definition).to_nav(db.upcast())
+}
+
+/// The struct or type alias the declaration is annotated with. Unlike the inferred type, this
+/// keeps the type alias.
+fn declared_type(
+    sema: &Semantics<'_>,
+    definition: &Definition,
+) -> Option<Definition> {
+    let db: &dyn DefDatabase = sema.db.upcast();
+    let r#type = match *definition {
+        Definition::Local(local) => {
+            let binding = local.source(db)?;
+            let parent = binding.value.syntax().parent()?;
+            let r#type = match ast::VariableStatement::cast(parent.clone()) {
+                Some(statement) => statement.ty(),
+                None => ast::VariableIdentDeclaration::cast(parent)?.ty(),
+            };
+            binding.with_value(r#type?)
+        },
+        Definition::Field(field) => {
+            let declaration = field.source(db)?;
+            let r#type = declaration.value.variable_ident_declaration()?.ty()?;
+            declaration.with_value(r#type)
+        },
+        Definition::ModuleDef(ModuleDef::GlobalVariable(var)) => {
+            let declaration = var.source(db)?;
+            let r#type = declaration.value.ty()?;
+            declaration.with_value(r#type)
+        },
+        Definition::ModuleDef(ModuleDef::GlobalConstant(constant)) => {
+            let declaration = constant.source(db)?;
+            let r#type = declaration.value.ty()?;
+            declaration.with_value(r#type)
+        },
+        Definition::ModuleDef(ModuleDef::Override(override_declaration)) => {
+            let declaration = override_declaration.source(db)?;
+            let r#type = declaration.value.ty()?;
+            declaration.with_value(r#type)
+        },
+        Definition::ModuleDef(_) | Definition::Struct(_) | Definition::TypeAlias(_) => return None,
+    };
+    let name_ref = r#type.value.as_name()?;
+    Definition::from_node(sema, r#type.file_id, name_ref.syntax())
+}
@@ -1,5 +1,6 @@
+use base_db::TextRange;
 use rowan::TokenAtOffset;
-use syntax::{SyntaxKind, SyntaxToken};
+use syntax::{SyntaxKind, SyntaxNode, SyntaxToken};
 
 pub(crate) fn pick_best_token(
     tokens: TokenAtOffset<SyntaxToken>,
@@ -7,3 +8,14 @@ pub(crate) fn pick_best_token(
 ) -> Option<SyntaxToken> {
     tokens.max_by_key(move |t| f(t.kind()))
 }
+
+/// The range of the node without the whitespace and comments it ends with.
+pub(crate) fn trimmed_range(node: &SyntaxNode) -> TextRange {
+    let last_token = std::iter::successors(node.last_token(), SyntaxToken::prev_token)
+        .take_while(|token| token.text_range().start() >= node.text_range().start())
+        .find(|token| !token.kind().is_trivia());
+    match last_token {
+        Some(token) => TextRange::new(node.text_range().start(), token.text_range().end()),
+        None => node.text_range(),
+    }
+}
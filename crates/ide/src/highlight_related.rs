@@ -0,0 +1,223 @@
+use base_db::{FilePosition, TextRange};
+use hir::{HirDatabase, Semantics, control_flow::ControlFlowGraph, definition::Definition};
+use hir_def::{
+    db::DefinitionWithBodyId,
+    expression::{Expression, ExpressionId, Statement, UnaryOperator},
+};
+use rustc_hash::FxHashSet;
+use syntax::{AstNode, SyntaxKind, SyntaxNode, SyntaxToken, ast};
+use vfs::FileId;
+
+use crate::helpers;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReferenceCategory {
+    Read,
+    Write,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HighlightedRange {
+    pub range: TextRange,
+    /// `None` for declarations and keywords.
+    pub category: Option<ReferenceCategory>,
+}
+
+/// Highlights the occurrences of the definition at the position, the exit points of the
+/// function for `return`, and the loop for `break` and `continue`.
+pub(crate) fn highlight_related(
+    db: &dyn HirDatabase,
+    position: FilePosition,
+) -> Option<Vec<HighlightedRange>> {
+    let sema = Semantics::new(db);
+    let file = sema.parse(position.file_id);
+    let token = file.syntax().token_at_offset(position.offset);
+    let token = helpers::pick_best_token(token, |kind| match kind {
+        SyntaxKind::Identifier | SyntaxKind::Return | SyntaxKind::Break | SyntaxKind::Continue => 2,
+        kind if kind.is_trivia() => 0,
+        _ => 1,
+    })?;
+
+    match token.kind() {
+        SyntaxKind::Identifier => highlight_references(&sema, position.file_id, &token),
+        SyntaxKind::Return => highlight_exit_points(&sema, position.file_id, &token),
+        SyntaxKind::Break | SyntaxKind::Continue => highlight_loop(&token),
+        _ => None,
+    }
+}
+
+fn highlight_references(
+    sema: &Semantics<'_>,
+    file_id: FileId,
+    token: &SyntaxToken,
+) -> Option<Vec<HighlightedRange>> {
+    let parent = token.parent()?;
+    let definition = match ast::Name::cast(parent) {
+        Some(name) => Definition::from_name(sema, file_id.into(), &name)?,
+        None => Definition::from_token(sema, file_id.into(), token)?,
+    };
+
+    let root = sema.parse(file_id).syntax().clone();
+    let written = written_expressions(sema, file_id, &root);
+    let mut highlights = Vec::new();
+    for node in root.descendants() {
+        if let Some(name) = ast::Name::cast(node.clone()) {
+            if Definition::from_name(sema, file_id.into(), &name).as_ref() == Some(&definition) {
+                highlights.extend(name.ident_token().map(|token| HighlightedRange {
+                    range: token.text_range(),
+                    category: None,
+                }));
+            }
+        } else if let Some(name_ref) = ast::NameReference::cast(node) {
+            if Definition::from_node(sema, file_id.into(), name_ref.syntax()).as_ref()
+                != Some(&definition)
+            {
+                continue;
+            }
+            // The path or field expression naming the definition.
+            let is_written = name_ref
+                .syntax()
+                .parent()
+                .is_some_and(|expression| written.contains(&expression.text_range()));
+            let category = if is_written {
+                ReferenceCategory::Write
+            } else {
+                ReferenceCategory::Read
+            };
+            highlights.extend(
+                name_ref
+                    .syntax()
+                    .first_token()
+                    .map(|token| HighlightedRange {
+                        range: token.text_range(),
+                        category: Some(category),
+                    }),
+            );
+        }
+    }
+    Some(highlights)
+}
+
+/// The ranges of the expressions assigned to, incremented or decremented in the bodies of
+/// the file, including the variables and pointers the assigned fields and elements belong to.
+fn written_expressions(
+    sema: &Semantics<'_>,
+    file_id: FileId,
+    root: &SyntaxNode,
+) -> FxHashSet<TextRange> {
+    let db = sema.db;
+    let mut written = FxHashSet::default();
+    for item in sema.module(file_id).items(db) {
+        let Some(def) = item.as_def_with_body_id() else {
+            continue;
+        };
+        if def.file_id(db.upcast()) != file_id.into() {
+            continue;
+        }
+        let (body, source_map) = db.body_with_source_map(def);
+        for (_, statement) in body.statements.iter() {
+            let mut expression: ExpressionId = match *statement {
+                Statement::Assignment { left_side, .. }
+                | Statement::CompoundAssignment { left_side, .. } => left_side,
+                Statement::IncrDecr { expression, .. } => expression,
+                _ => continue,
+            };
+            loop {
+                if let Ok(pointer) = source_map.expression_to_source(expression) {
+                    written.insert(pointer.to_node(root).syntax().text_range());
+                }
+                expression = match body.exprs[expression] {
+                    Expression::Field { expression, .. } => expression,
+                    Expression::Index { left_side, .. } => left_side,
+                    Expression::UnaryOperator {
+                        expression,
+                        op: UnaryOperator::Dereference,
+                    } => expression,
+                    _ => break,
+                };
+            }
+        }
+    }
+    written
+}
+
+/// Highlights the `return` and `discard` statements of the function, and its closing brace
+/// if the end of the function is reachable.
+fn highlight_exit_points(
+    sema: &Semantics<'_>,
+    file_id: FileId,
+    token: &SyntaxToken,
+) -> Option<Vec<HighlightedRange>> {
+    let function = token.parent_ancestors().find_map(ast::Function::cast)?;
+    let body = function.body()?;
+
+    let mut highlights: Vec<HighlightedRange> = body
+        .syntax()
+        .descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .filter(|token| matches!(token.kind(), SyntaxKind::Return | SyntaxKind::Discard))
+        .map(|token| HighlightedRange {
+            range: token.text_range(),
+            category: None,
+        })
+        .collect();
+
+    let def = sema.find_container(file_id.into(), function.syntax())?;
+    if let DefinitionWithBodyId::Function(_) = def {
+        let falls_through = ControlFlowGraph::new(&sema.db.body(def)).falls_through();
+        if let Some(brace) = body.right_brace_token().filter(|_| falls_through) {
+            highlights.push(HighlightedRange {
+                range: brace.text_range(),
+                category: None,
+            });
+        }
+    }
+    Some(highlights)
+}
+
+/// Highlights the keyword of the loop (or switch, for `break`) the statement jumps out of,
+/// and the other `break` and `continue` statements jumping out of it.
+fn highlight_loop(token: &SyntaxToken) -> Option<Vec<HighlightedRange>> {
+    let target = jump_target(token)?;
+    let keyword = target
+        .children_with_tokens()
+        .filter_map(|element| element.into_token())
+        .find(|token| {
+            matches!(
+                token.kind(),
+                SyntaxKind::Loop | SyntaxKind::While | SyntaxKind::For | SyntaxKind::Switch
+            )
+        })?;
+
+    let jumps = target
+        .descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .filter(|token| {
+            matches!(token.kind(), SyntaxKind::Break | SyntaxKind::Continue)
+                && jump_target(token).as_ref() == Some(&target)
+        });
+    Some(
+        std::iter::once(keyword)
+            .chain(jumps)
+            .map(|token| HighlightedRange {
+                range: token.text_range(),
+                category: None,
+            })
+            .collect(),
+    )
+}
+
+/// The innermost loop, or switch for `break`, around the `break` or `continue` keyword.
+fn jump_target(token: &SyntaxToken) -> Option<SyntaxNode> {
+    let is_break = token.kind() == SyntaxKind::Break;
+    token
+        .parent_ancestors()
+        .take_while(|node| node.kind() != SyntaxKind::Function)
+        .find(|node| match node.kind() {
+            SyntaxKind::LoopStatement | SyntaxKind::WhileStatement | SyntaxKind::ForStatement => {
+                true
+            },
+            SyntaxKind::SwitchStatement => is_break,
+            _ => false,
+        })
+}
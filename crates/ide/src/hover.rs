@@ -1,8 +1,10 @@
 use base_db::{FileRange, RangeInfo};
-use hir::{HirDatabase, Semantics};
+use hir::{HirDatabase, Semantics, diagnostics::DiagnosticsConfig};
 use hir_def::InFile;
 use syntax::{AstNode, ast};
 
+use crate::diagnostics::{self, explanations};
+
 pub enum HoverResult {
     SourceCode(String),
     Text(String),
@@ -10,6 +12,7 @@ pub enum HoverResult {
 
 pub(crate) fn hover(
     db: &dyn HirDatabase,
+    config: &DiagnosticsConfig,
     file_range: FileRange,
 ) -> Option<RangeInfo<HoverResult>> {
     let sema = &Semantics::new(db);
@@ -35,5 +38,30 @@ pub(crate) fn hover(
         }
     }
 
-    None
+    hover_diagnostic(db, config, file_range)
+}
+
+/// Explains the diagnostic under the cursor, if any.
+fn hover_diagnostic(
+    db: &dyn HirDatabase,
+    config: &DiagnosticsConfig,
+    file_range: FileRange,
+) -> Option<RangeInfo<HoverResult>> {
+    // Naga parses and validates the whole shader again on every call, unlike the cached queries
+    // the other diagnostics come from, which is too slow to do on every hover.
+    let config = DiagnosticsConfig {
+        naga_parsing_errors: false,
+        naga_validation_errors: false,
+        ..config.clone()
+    };
+    let diagnostic = diagnostics::diagnostics(db, &config, file_range.file_id)
+        .into_iter()
+        .filter(|diagnostic| diagnostic.range.contains_range(file_range.range))
+        .min_by_key(|diagnostic| diagnostic.range.len())?;
+    let explanation = explanations::explanation(diagnostic.code.as_str())?;
+
+    Some(RangeInfo {
+        range: diagnostic.range,
+        info: HoverResult::Text(explanation.to_markdown()),
+    })
 }
@@ -1,44 +1,75 @@
 use base_db::{FileId, FileRange, TextRange};
-use hir::{Field, HasSource, Semantics};
-use hir_def::{InFile, data::FieldId, module_data::Name};
+use hir::{Field, HasSource, HirDatabase, Semantics, SourceAnalyzer};
+use hir_def::{
+    HasSource as _, InFile,
+    data::FieldId,
+    db::{DefinitionWithBodyId, Lookup, StructId},
+    module_data::Name,
+    resolver::ResolveValue,
+    type_ref::AccessMode,
+};
 use hir_ty::{
+    consteval,
     function::FunctionDetails,
-    infer::ResolvedCall,
+    infer::{ResolvedCall, TypeExpectation, TypeExpectationInner},
     layout::{FieldLayout, LayoutAddressSpace},
-    ty::pretty::{TypeVerbosity, pretty_type_with_verbosity},
+    ty::{
+        ArrayType, MatrixType, ScalarType, TyKind, Type, VectorType,
+        pretty::{TypeVerbosity, pretty_type_with_verbosity},
+    },
 };
 use rowan::NodeOrToken;
 use smol_str::SmolStr;
-use syntax::{AstChildren, AstNode, HasName, SyntaxNode, ast};
+use syntax::{AstNode, HasGenerics, HasName, SyntaxKind, SyntaxNode, ast, match_ast};
 
-use crate::RootDatabase;
+use crate::{RootDatabase, helpers};
 
 #[derive(Clone, Debug)]
 pub struct InlayHintsConfig {
     pub enabled: bool,
     pub type_hints: bool,
     pub parameter_hints: bool,
-    pub struct_layout_hints: Option<StructLayoutHints>,
+    /// The kinds of struct layout hints to show, none if empty.
+    pub struct_layout_hints: Vec<StructLayoutHints>,
+    pub const_value_hints: bool,
+    pub conversion_hints: bool,
+    pub access_mode_hints: bool,
+    /// The number of lines a function or struct has to span to get a hint after its closing brace.
+    pub closing_brace_hints: Option<usize>,
     pub type_verbosity: TypeVerbosity,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum StructLayoutHints {
+    /// The offset of every field.
     Offset,
+    /// The size and array stride of the struct, after its closing brace.
+    Size,
+    /// The alignment of the struct, after its closing brace.
+    Align,
+    /// The padding before fields and at the end of the struct.
+    Padding,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum InlayKind {
     TypeHint,
     ParameterHint,
     StructLayoutHint,
+    StructSizeHint,
+    ConstValueHint,
+    ConversionHint,
+    ClosingBraceHint,
+    AccessModeHint,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Hash)]
 pub struct InlayHint {
     pub range: TextRange,
     pub kind: InlayKind,
     pub label: SmolStr,
+    /// Markdown explaining the hint, which clients may resolve lazily.
+    pub tooltip: Option<String>,
 }
 
 pub(crate) fn inlay_hints(
@@ -84,60 +115,158 @@ fn get_struct_layout_hints(
     sema: &Semantics,
     config: &InlayHintsConfig,
 ) -> Option<()> {
-    let display_kind = config.struct_layout_hints?;
+    if config.struct_layout_hints.is_empty() {
+        return None;
+    }
 
     let module_info = sema.db.module_info(file_id.into());
 
     for r#struct in module_info.structs() {
         let r#struct = sema.db.intern_struct(InFile::new(file_id.into(), r#struct));
-        let fields = sema.db.field_types(r#struct);
 
-        let address_space = if sema.db.struct_is_used_in_uniform(r#struct, file_id.into()) {
-            LayoutAddressSpace::Uniform
-        } else {
-            LayoutAddressSpace::Storage
-        };
+        // Structs which no global variable shares with the host are shown with the storage layout.
+        let mut address_spaces: Vec<_> = sema
+            .db
+            .struct_address_spaces(r#struct, file_id.into())
+            .iter()
+            .collect();
+        if address_spaces.is_empty() {
+            address_spaces.push(LayoutAddressSpace::Storage);
+        }
+        let name_address_space = address_spaces.len() > 1;
+
+        for address_space in address_spaces {
+            struct_layout_hints(
+                hints,
+                sema,
+                &config.struct_layout_hints,
+                r#struct,
+                address_space,
+                name_address_space,
+            );
+        }
+    }
 
-        hir_ty::layout::struct_member_layout(
-            &fields,
-            sema.db,
-            address_space,
-            |field, field_layout| {
-                let FieldLayout {
-                    offset,
-                    align: _,
-                    size: _,
-                } = field_layout;
-                let field = Field {
-                    id: FieldId { r#struct, field },
-                };
-
-                let source = field.source(sema.db.upcast())?.value;
-
-                // this is only necessary, because the field syntax nodes include the whitespace to the next line...
-                let actual_last_token = std::iter::successors(
-                    source.syntax().last_token(),
-                    rowan::SyntaxToken::prev_token, // spellchecker:disable-line
-                )
-                .find(|token| !token.kind().is_trivia())?;
-                let range = TextRange::new(
-                    source.syntax().text_range().start(),
-                    actual_last_token.text_range().end(),
-                );
+    Some(())
+}
 
+fn struct_layout_hints(
+    hints: &mut Vec<InlayHint>,
+    sema: &Semantics,
+    kinds: &[StructLayoutHints],
+    r#struct: StructId,
+    address_space: LayoutAddressSpace,
+    name_address_space: bool,
+) -> Option<()> {
+    let prefix = if name_address_space {
+        format!("{}: ", address_space.as_str())
+    } else {
+        String::new()
+    };
+    let mut just_past_last_field = 0;
+    let (align, size) = hir_ty::layout::struct_member_layout(
+        r#struct,
+        sema.db,
+        address_space,
+        |field, field_layout| {
+            let FieldLayout {
+                offset,
+                align,
+                size,
+            } = field_layout;
+            let padding = offset - just_past_last_field;
+            just_past_last_field = offset + size;
+
+            let field = Field {
+                id: FieldId { r#struct, field },
+            };
+            let source = field.source(sema.db.upcast())?.value;
+            let range = helpers::trimmed_range(source.syntax());
+
+            if kinds.contains(&StructLayoutHints::Padding) && padding > 0 {
                 hints.push(InlayHint {
                     range,
                     kind: InlayKind::StructLayoutHint,
-                    label: match display_kind {
-                        StructLayoutHints::Offset => format!("{offset}").into(),
-                    },
+                    label: format!("{prefix}{padding} bytes padding").into(),
+                    // More padding than the alignment needs follows a struct in the `uniform`
+                    // address space, whose members take a multiple of 16 bytes.
+                    tooltip: Some(if padding < align {
+                        format!(
+                            "{padding} bytes of padding align the field to {align} bytes in the \
+                             `{}` address space.",
+                            address_space.as_str()
+                        )
+                    } else {
+                        format!(
+                            "{padding} bytes of padding follow the struct before the field, \
+                             which takes a multiple of 16 bytes in the `{}` address space.",
+                            address_space.as_str()
+                        )
+                    }),
                 });
+            }
+            if kinds.contains(&StructLayoutHints::Offset) {
+                hints.push(InlayHint {
+                    range,
+                    kind: InlayKind::StructLayoutHint,
+                    label: format!("{prefix}{offset}").into(),
+                    tooltip: Some(format!(
+                        "Offset {offset}, size {size} and alignment {align} in the `{}` address \
+                         space.",
+                        address_space.as_str()
+                    )),
+                });
+            }
 
-                Some(())
-            },
-        );
+            Some(())
+        },
+    )?;
+
+    let stride = size.next_multiple_of(align);
+    let trailing_padding = size - just_past_last_field;
+    let mut parts = Vec::new();
+    if kinds.contains(&StructLayoutHints::Size) {
+        parts.push(format!("size {size}"));
+        if stride != size {
+            parts.push(format!("stride {stride}"));
+        }
+    }
+    if kinds.contains(&StructLayoutHints::Align) {
+        parts.push(format!("align {align}"));
+    }
+    if kinds.contains(&StructLayoutHints::Padding) && trailing_padding > 0 {
+        parts.push(format!("{trailing_padding} bytes padding"));
+    }
+    if parts.is_empty() {
+        return None;
     }
 
+    let source = r#struct
+        .lookup(sema.db.upcast())
+        .source(sema.db.upcast())
+        .value;
+    let brace = source
+        .body()?
+        .syntax()
+        .children_with_tokens()
+        .filter_map(NodeOrToken::into_token)
+        .find(|token| token.kind() == SyntaxKind::BraceRight)?;
+
+    let mut tooltip = format!(
+        "Size {size}, array stride {stride} and alignment {align} in the `{}` address space.",
+        address_space.as_str()
+    );
+    if trailing_padding > 0 {
+        tooltip.push_str(&format!(
+            " The last field is followed by {trailing_padding} bytes of padding."
+        ));
+    }
+    hints.push(InlayHint {
+        range: brace.text_range(),
+        kind: InlayKind::StructSizeHint,
+        label: format!("{prefix}{}", parts.join(", ")).into(),
+        tooltip: Some(tooltip),
+    });
     Some(())
 }
 
@@ -147,42 +276,65 @@ fn get_hints(
     sema: &Semantics,
     config: &InlayHintsConfig,
     node: SyntaxNode,
+) {
+    if config.parameter_hints || config.conversion_hints {
+        call_hints(hints, file_id, sema, config, &node);
+    }
+    if config.type_hints {
+        type_hints(hints, file_id, sema, config, &node);
+    }
+    if config.const_value_hints {
+        const_value_hints(hints, file_id, sema, &node);
+        array_size_hints(hints, file_id, sema, &node);
+    }
+    if config.access_mode_hints {
+        access_mode_hints(hints, file_id, sema, &node);
+    }
+    if let Some(min_lines) = config.closing_brace_hints {
+        closing_brace_hints(hints, file_id, sema, min_lines, &node);
+    }
+}
+
+fn call_hints(
+    hints: &mut Vec<InlayHint>,
+    file_id: FileId,
+    sema: &Semantics,
+    config: &InlayHintsConfig,
+    node: &SyntaxNode,
 ) -> Option<()> {
-    if let Some(expression) = ast::Expression::cast(node.clone()) {
-        #[allow(clippy::single_match)] // for extendability
-        match &expression {
-            ast::Expression::FunctionCall(function_call_expression) => {
-                if !config.parameter_hints {
-                    return None;
-                }
-                function_hints(
-                    sema,
-                    file_id,
-                    &node,
-                    &expression,
-                    function_call_expression.parameters()?.arguments(),
-                    hints,
-                )?;
-            },
-            ast::Expression::TypeInitializer(type_initialiser_expression) => {
-                if !config.parameter_hints {
-                    return None;
-                }
-                // Show hints for the built-in initializers.
-                // `vec4(xyz: val1, w: val2)` could also be
-                // `vec4(xy: val1, zw: val2)` without hints
-                function_hints(
-                    sema,
-                    file_id,
-                    &node,
-                    &expression,
-                    type_initialiser_expression.arguments()?.arguments(),
-                    hints,
-                )?;
-            },
-            _ => {},
-        }
-    } else if let Some((binding, r#type)) = ast::VariableStatement::cast(node.clone())
+    let expression = ast::Expression::cast(node.clone())?;
+    let arguments = match &expression {
+        ast::Expression::FunctionCall(function_call_expression) => {
+            function_call_expression.parameters()?.arguments()
+        },
+        // Show hints for the built-in initializers.
+        // `vec4(xyz: val1, w: val2)` could also be
+        // `vec4(xy: val1, zw: val2)` without hints
+        ast::Expression::TypeInitializer(type_initialiser_expression) => {
+            type_initialiser_expression.arguments()?.arguments()
+        },
+        _ => return None,
+    };
+    let arguments: Vec<_> = arguments.collect();
+    let container = sema.find_container(file_id.into(), node)?;
+    let analyzed = sema.analyze(container);
+    if config.parameter_hints {
+        function_hints(&analyzed, &expression, &arguments, hints);
+    }
+    if config.conversion_hints {
+        conversion_hints(&analyzed, config, &expression, &arguments, hints);
+    }
+    Some(())
+}
+
+fn type_hints(
+    hints: &mut Vec<InlayHint>,
+    file_id: FileId,
+    sema: &Semantics,
+    config: &InlayHintsConfig,
+    node: &SyntaxNode,
+) -> Option<()> {
+    let (binding, r#type) = ast::VariableStatement::cast(node.clone())
         .and_then(|statement| Some((statement.binding()?, statement.ty())))
         .or_else(|| {
             ast::GlobalConstantDeclaration::cast(node.clone())
@@ -191,41 +343,179 @@ fn get_hints(
         .or_else(|| {
             ast::GlobalVariableDeclaration::cast(node.clone())
                 .and_then(|statement| Some((statement.binding()?, statement.ty())))
-        })
-    {
-        if !config.type_hints {
-            return None;
-        }
-        if r#type.is_none() {
-            let container = sema.find_container(file_id.into(), &node)?;
-            let r#type = sema.analyze(container).type_of_binding(&binding)?;
-
-            let label = pretty_type_with_verbosity(sema.db, r#type, config.type_verbosity);
-            hints.push(InlayHint {
-                range: binding.name()?.ident_token()?.text_range(),
-                kind: InlayKind::TypeHint,
-                label: label.into(),
-            });
+        })?;
+    if r#type.is_none() {
+        let container = sema.find_container(file_id.into(), node)?;
+        let r#type = sema.analyze(container).type_of_binding(&binding)?;
+
+        let label = pretty_type_with_verbosity(sema.db, r#type, config.type_verbosity);
+        hints.push(InlayHint {
+            range: binding.name()?.ident_token()?.text_range(),
+            kind: InlayKind::TypeHint,
+            label: label.into(),
+            tooltip: None,
+        });
+    }
+
+    Some(())
+}
+
+/// The value of a `const` or `override` declaration, unless it is initialized with a literal.
+fn const_value_hints(
+    hints: &mut Vec<InlayHint>,
+    file_id: FileId,
+    sema: &Semantics,
+    node: &SyntaxNode,
+) -> Option<()> {
+    let init = match_ast! {
+        match node {
+            ast::GlobalConstantDeclaration(constant) => constant.init()?,
+            ast::OverrideDeclaration(override_declaration) => override_declaration.init()?,
+            _ => return None,
         }
+    };
+    if matches!(init, ast::Expression::Literal(_)) {
+        return None;
     }
+    let def = sema.find_container(file_id.into(), node)?;
+    let value = consteval::eval(sema.db, def)?;
+
+    hints.push(InlayHint {
+        range: helpers::trimmed_range(init.syntax()),
+        kind: InlayKind::ConstValueHint,
+        label: format!("= {value}").into(),
+        tooltip: matches!(def, DefinitionWithBodyId::Override(_))
+            .then(|| "The default value, which pipelines can override.".to_owned()),
+    });
+    Some(())
+}
 
+/// The value of a constant used as the size of an array, such as `N` in `array<f32, N>`.
+fn array_size_hints(
+    hints: &mut Vec<InlayHint>,
+    file_id: FileId,
+    sema: &Semantics,
+    node: &SyntaxNode,
+) -> Option<()> {
+    let generics = match_ast! {
+        match node {
+            ast::ArrayType(array) => array.generic_arg_list()?,
+            ast::BindingArrayType(array) => array.generic_arg_list()?,
+            _ => return None,
+        }
+    };
+    let Some(ast::GenericArg::Type(size)) = generics.generics().nth(1) else {
+        return None;
+    };
+    let name = size.as_name()?;
+    let def = match sema
+        .resolver(file_id.into(), node)
+        .resolve_value(&Name::from(name.clone()))?
+    {
+        ResolveValue::GlobalConstant(loc) => {
+            DefinitionWithBodyId::GlobalConstant(sema.db.intern_global_constant(loc))
+        },
+        ResolveValue::Override(loc) => DefinitionWithBodyId::Override(sema.db.intern_override(loc)),
+        ResolveValue::Local(_) | ResolveValue::GlobalVariable(_) => return None,
+    };
+    let value = consteval::eval(sema.db, def)?;
+
+    hints.push(InlayHint {
+        range: helpers::trimmed_range(name.syntax()),
+        kind: InlayKind::ConstValueHint,
+        label: format!("= {value}").into(),
+        tooltip: None,
+    });
     Some(())
 }
 
-fn function_hints(
+/// The access mode of a `let` binding of a pointer.
+fn access_mode_hints(
+    hints: &mut Vec<InlayHint>,
+    file_id: FileId,
     sema: &Semantics,
+    node: &SyntaxNode,
+) -> Option<()> {
+    let statement = ast::VariableStatement::cast(node.clone())?;
+    if !matches!(statement.kind()?, ast::VariableStatementKind::Let) {
+        return None;
+    }
+    let binding = statement.binding()?;
+    let container = sema.find_container(file_id.into(), node)?;
+    let r#type = sema.analyze(container).type_of_binding(&binding)?;
+    let TyKind::Pointer(pointer) = r#type.kind(sema.db) else {
+        return None;
+    };
+    if pointer.access_mode == AccessMode::Any {
+        return None;
+    }
+
+    hints.push(InlayHint {
+        range: binding.name()?.ident_token()?.text_range(),
+        kind: InlayKind::AccessModeHint,
+        label: pointer.access_mode.to_string().into(),
+        tooltip: Some(format!(
+            "A pointer into the `{}` address space with `{}` access.",
+            pointer.storage_class, pointer.access_mode
+        )),
+    });
+    Some(())
+}
+
+/// The name of a function or struct after its closing brace, if it spans enough lines.
+fn closing_brace_hints(
+    hints: &mut Vec<InlayHint>,
     file_id: FileId,
+    sema: &Semantics,
+    min_lines: usize,
     node: &SyntaxNode,
+) -> Option<()> {
+    let (label, body) = match_ast! {
+        match node {
+            ast::Function(function) => {
+                let label = format!("fn {}", function.name()?.text().as_str());
+                (label, function.body()?.syntax().clone())
+            },
+            ast::StructDeclaration(r#struct) => {
+                let label = format!("struct {}", r#struct.name()?.text().as_str());
+                (label, r#struct.body()?.syntax().clone())
+            },
+            _ => return None,
+        }
+    };
+    let brace = body
+        .children_with_tokens()
+        .filter_map(NodeOrToken::into_token)
+        .find(|token| token.kind() == SyntaxKind::BraceRight)?;
+
+    let line_index = sema.db.line_index(file_id);
+    let start = line_index.line_col(node.text_range().start());
+    let end = line_index.line_col(brace.text_range().start());
+    if ((end.line - start.line) as usize) < min_lines {
+        return None;
+    }
+
+    hints.push(InlayHint {
+        range: brace.text_range(),
+        kind: InlayKind::ClosingBraceHint,
+        label: label.into(),
+        tooltip: None,
+    });
+    Some(())
+}
+
+fn function_hints(
+    analyzed: &SourceAnalyzer,
     expression: &ast::Expression,
-    parameter_expressions: AstChildren<ast::Expression>,
+    parameter_expressions: &[ast::Expression],
     hints: &mut Vec<InlayHint>,
 ) -> Option<()> {
-    let container = sema.find_container(file_id.into(), node)?;
-    let analyzed = sema.analyze(container);
     let expression = analyzed.expression_id(expression)?;
     let resolved = analyzed.infer.call_resolution(expression)?;
     let func = match resolved {
-        ResolvedCall::Function(func) => func.lookup(analyzed.db),
+        ResolvedCall::Function(func) | ResolvedCall::UserFunction(_, func) => {
+            func.lookup(analyzed.db)
+        },
         ResolvedCall::OtherTypeInitializer(_) => return None,
     };
     let param_hints = func
@@ -239,11 +529,95 @@ fn function_hints(
             range: expression.syntax().text_range(),
             kind: InlayKind::ParameterHint,
             label: param_name.into(),
+            tooltip: None,
         });
     hints.extend(param_hints);
     Some(())
 }
 
+/// Abstract integer literals which are converted to `u32` or `f32` when passed to a function or
+/// initializer. Conversions to `i32` are what unsuffixed integers default to anyway, so they are
+/// not shown.
+fn conversion_hints(
+    analyzed: &SourceAnalyzer,
+    config: &InlayHintsConfig,
+    expression: &ast::Expression,
+    arguments: &[ast::Expression],
+    hints: &mut Vec<InlayHint>,
+) -> Option<()> {
+    let db = analyzed.db;
+    // Builtins are generic, so the other arguments or the initialized type decide the type.
+    let inferred = match expression {
+        ast::Expression::TypeInitializer(_) => {
+            let r#type = analyzed.type_of_expression(expression)?;
+            // An explicit conversion, such as `f32(1)`.
+            if matches!(r#type.kind(db), TyKind::Scalar(_)) {
+                return None;
+            }
+            scalar_component(db, r#type)
+        },
+        _ => arguments
+            .iter()
+            .filter(|argument| !is_abstract_int(argument))
+            .find_map(|argument| scalar_component(db, analyzed.type_of_expression(argument)?)),
+    };
+
+    for argument in arguments
+        .iter()
+        .filter(|argument| is_abstract_int(argument))
+    {
+        let expected = match analyzed.expected_type_of_expression(argument) {
+            Some(TypeExpectation::Type(TypeExpectationInner::Exact(r#type))) => {
+                scalar_component(db, r#type)
+            },
+            _ => None,
+        };
+        let Some(target @ (ScalarType::U32 | ScalarType::F32)) = expected.or(inferred.clone())
+        else {
+            continue;
+        };
+        let r#type = pretty_type_with_verbosity(
+            db,
+            db.intern_ty(TyKind::Scalar(target)),
+            config.type_verbosity,
+        );
+        hints.push(InlayHint {
+            range: helpers::trimmed_range(argument.syntax()),
+            kind: InlayKind::ConversionHint,
+            label: format!("as {type}").into(),
+            tooltip: Some(format!("The abstract integer is converted to `{type}`.")),
+        });
+    }
+    Some(())
+}
+
+/// Whether the expression is an integer literal without a suffix.
+fn is_abstract_int(expression: &ast::Expression) -> bool {
+    let ast::Expression::Literal(literal) = expression else {
+        return false;
+    };
+    match literal.kind() {
+        ast::LiteralKind::DecimalIntLiteral(literal) | ast::LiteralKind::HexIntLiteral(literal) => {
+            !literal.text().ends_with(['i', 'u'])
+        },
+        _ => false,
+    }
+}
+
+/// The type of a scalar, or of the components of a vector, matrix or array.
+fn scalar_component(
+    db: &dyn HirDatabase,
+    r#type: Type,
+) -> Option<ScalarType> {
+    match r#type.unref(db).kind(db) {
+        TyKind::Scalar(scalar) => Some(scalar),
+        TyKind::Vector(VectorType { inner, .. })
+        | TyKind::Matrix(MatrixType { inner, .. })
+        | TyKind::Array(ArrayType { inner, .. }) => scalar_component(db, inner),
+        _ => None,
+    }
+}
+
 // taken from https://github.com/rust-lang/rust-analyzer/blob/7308b3ef413cad8c211e239d32c9fab29ae2e664/crates/ide/src/inlay_hints.rs#L422
 
 fn should_hide_param_name_hint(
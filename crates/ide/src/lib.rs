@@ -1,13 +1,21 @@
 #![allow(dead_code)]
 
+mod builtin_document;
+mod call_graph;
+mod call_hierarchy;
 mod db;
 mod debug_command;
 pub mod diagnostics;
+mod folding_ranges;
 mod formatting;
 mod goto_definition;
+mod goto_type_definition;
 mod helpers;
+mod highlight_related;
 mod hover;
 pub mod inlay_hints;
+mod rust_mirror;
+mod selection_ranges;
 mod syntax_tree;
 
 use std::sync::Arc;
@@ -16,17 +24,26 @@ use base_db::{
     FilePosition, FileRange, RangeInfo, SourceDatabase, TextRange, change::Change,
     input::SourceRootId,
 };
+pub use builtin_document::BuiltinDocument;
+pub use call_graph::{CallGraph, CallGraphFunction};
+pub use call_hierarchy::{BuiltinCall, CallHierarchyItem, CallItem, OutgoingCalls};
 use diagnostics::Diagnostic;
-use goto_definition::NavigationTarget;
+pub use folding_ranges::{Fold, FoldKind};
+pub use formatting::OnEnterEdit;
+pub use goto_definition::{GotoDefinition, NavigationTarget};
+pub use highlight_related::{HighlightedRange, ReferenceCategory};
 use hir::diagnostics::DiagnosticsConfig;
 use hir_def::db::DefDatabase;
+use hir_ty::layout::LayoutAddressSpace;
 pub use hover::HoverResult;
 use ide_completion::{CompletionConfig, item::CompletionItem};
 use inlay_hints::{InlayHint, InlayHintsConfig};
 pub use line_index::{LineCol, LineIndex};
+pub use rust_mirror::RustMirror;
 use salsa::{Cancelled, ParallelDatabase};
 use syntax::{Parse, SyntaxNode};
 use vfs::FileId;
+pub use wgsl_formatter::FormattingOptions;
 
 pub type Cancellable<T> = Result<T, Cancelled>;
 
@@ -187,12 +204,99 @@ impl Analysis {
         self.with_db(|db| diagnostics::diagnostics(db, config, file_id))
     }
 
+    /// The ranges of the file which can be folded.
+    pub fn folding_ranges(
+        &self,
+        file_id: FileId,
+    ) -> Cancellable<Vec<Fold>> {
+        self.with_db(|db| folding_ranges::folding_ranges(&db.parse_no_preprocessor(file_id).tree()))
+    }
+
+    /// The ranges to expand the selection to from the position, from the innermost.
+    pub fn selection_ranges(
+        &self,
+        position: FilePosition,
+    ) -> Cancellable<Vec<TextRange>> {
+        self.with_db(|db| selection_ranges::selection_ranges(db, position))
+    }
+
+    /// The functions of the file and of its imports, with the functions they call.
+    pub fn call_graph(
+        &self,
+        file_id: FileId,
+    ) -> Cancellable<CallGraph> {
+        self.with_db(|db| call_graph::call_graph(db, file_id))
+    }
+
+    /// The function declared or called at the position.
+    pub fn prepare_call_hierarchy(
+        &self,
+        position: FilePosition,
+    ) -> Cancellable<Option<CallHierarchyItem>> {
+        self.with_db(|db| call_hierarchy::prepare_call_hierarchy(db, position))
+    }
+
+    /// The functions calling the function at the position, across `#import`s.
+    pub fn incoming_calls(
+        &self,
+        position: FilePosition,
+    ) -> Cancellable<Option<Vec<CallItem>>> {
+        self.with_db(|db| call_hierarchy::incoming_calls(db, position))
+    }
+
+    /// The functions and builtins called by the function at the position.
+    pub fn outgoing_calls(
+        &self,
+        position: FilePosition,
+    ) -> Cancellable<Option<OutgoingCalls>> {
+        self.with_db(|db| call_hierarchy::outgoing_calls(db, position))
+    }
+
     pub fn goto_definition(
         &self,
         file_position: FilePosition,
-    ) -> Cancellable<Option<NavigationTarget>> {
+    ) -> Cancellable<Option<GotoDefinition>> {
         self.with_db(|db| goto_definition::goto_definition(db, file_position))
     }
+    pub fn goto_type_definition(
+        &self,
+        position: FilePosition,
+    ) -> Cancellable<Option<NavigationTarget>> {
+        self.with_db(|db| goto_type_definition::goto_type_definition(db, position))
+    }
+    pub fn builtin_document(
+        &self,
+        name: &str,
+    ) -> Cancellable<Option<BuiltinDocument>> {
+        self.with_db(|db| builtin_document::builtin_document(db, name))
+    }
+
+    /// Rust `#[repr(C)]` mirrors of the struct declared at the position, for the address spaces
+    /// it is used in.
+    pub fn rust_mirrors(
+        &self,
+        position: FilePosition,
+    ) -> Cancellable<Vec<RustMirror>> {
+        self.with_db(|db| rust_mirror::rust_mirrors(db, position))
+    }
+
+    /// A Rust `#[repr(C)]` mirror of the struct declared at the position, with its layout in the
+    /// address space.
+    pub fn rust_mirror(
+        &self,
+        position: FilePosition,
+        address_space: LayoutAddressSpace,
+    ) -> Cancellable<Option<RustMirror>> {
+        self.with_db(|db| rust_mirror::rust_mirror(db, position, address_space))
+    }
+
+    /// The ranges related to the symbol or keyword at the position.
+    pub fn highlight_related(
+        &self,
+        position: FilePosition,
+    ) -> Cancellable<Option<Vec<HighlightedRange>>> {
+        self.with_db(|db| highlight_related::highlight_related(db, position))
+    }
 
     /// Computes completions at the given position.
     pub fn completions(
@@ -208,15 +312,25 @@ impl Analysis {
         &self,
         file_id: FileId,
         range: Option<TextRange>,
+        options: &FormattingOptions,
     ) -> Cancellable<Option<SyntaxNode>> {
-        self.with_db(|db| formatting::format(db, file_id, range))
+        self.with_db(|db| formatting::format(db, file_id, range, options))
+    }
+
+    pub fn on_enter(
+        &self,
+        position: FilePosition,
+        options: &FormattingOptions,
+    ) -> Cancellable<Option<OnEnterEdit>> {
+        self.with_db(|db| formatting::on_enter(db, position, options))
     }
 
     pub fn hover(
         &self,
+        config: &DiagnosticsConfig,
         range: FileRange,
     ) -> Cancellable<Option<RangeInfo<HoverResult>>> {
-        self.with_db(|db| hover::hover(db, range))
+        self.with_db(|db| hover::hover(db, config, range))
     }
 
     pub fn debug_command(
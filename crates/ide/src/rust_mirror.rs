@@ -0,0 +1,335 @@
+use std::fmt::Write as _;
+
+use base_db::FilePosition;
+use hir::{HirDatabase, Semantics};
+use hir_def::{
+    InFile,
+    db::StructId,
+    module_data::{self, find_item},
+};
+use hir_ty::{
+    layout::{FieldLayout, LayoutAddressSpace, StructAddressSpaces, struct_member_layout},
+    ty::{ArraySize, ScalarType, TyKind, Type, VecSize, pretty::pretty_type},
+};
+use syntax::{AstNode, ast};
+
+/// Rust structs with the memory layout of a WGSL struct in an address space, for sharing buffers
+/// with the host through `bytemuck`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct RustMirror {
+    /// The name of the WGSL struct.
+    pub name: String,
+    pub address_space: LayoutAddressSpace,
+    /// The `#[repr(C)]` structs, preceded by the structs their fields use, with `size_of`
+    /// assertions.
+    pub text: String,
+}
+
+/// The mirrors of the struct declared at the position, for every address space global variables
+/// use it in, or for both when none does.
+pub(crate) fn rust_mirrors(
+    db: &dyn HirDatabase,
+    position: FilePosition,
+) -> Vec<RustMirror> {
+    let Some(r#struct) = struct_at(db, position) else {
+        return Vec::new();
+    };
+    let mut address_spaces = db.struct_address_spaces(r#struct, position.file_id.into());
+    if address_spaces == StructAddressSpaces::default() {
+        address_spaces = StructAddressSpaces {
+            uniform: true,
+            storage: true,
+        };
+    }
+    address_spaces
+        .iter()
+        .filter_map(|address_space| rust_mirror_of(db, r#struct, address_space))
+        .collect()
+}
+
+pub(crate) fn rust_mirror(
+    db: &dyn HirDatabase,
+    position: FilePosition,
+    address_space: LayoutAddressSpace,
+) -> Option<RustMirror> {
+    rust_mirror_of(db, struct_at(db, position)?, address_space)
+}
+
+fn struct_at(
+    db: &dyn HirDatabase,
+    position: FilePosition,
+) -> Option<StructId> {
+    let sema = Semantics::new(db);
+    let file = sema.parse(position.file_id);
+    let declaration = file
+        .syntax()
+        .token_at_offset(position.offset)
+        .find_map(|token| {
+            token
+                .parent_ancestors()
+                .find_map(ast::StructDeclaration::cast)
+        })?;
+    let file_id = position.file_id.into();
+    let r#struct = find_item::<module_data::Struct>(db.upcast(), file_id, &declaration)?;
+    Some(db.intern_struct(InFile::new(file_id, r#struct)))
+}
+
+fn rust_mirror_of(
+    db: &dyn HirDatabase,
+    r#struct: StructId,
+    address_space: LayoutAddressSpace,
+) -> Option<RustMirror> {
+    let mut builder = MirrorBuilder {
+        db,
+        address_space,
+        written: Vec::new(),
+        padded: Vec::new(),
+        text: String::new(),
+    };
+    builder.write_struct(r#struct)?;
+    Some(RustMirror {
+        name: db.struct_data(r#struct).name.as_str().to_owned(),
+        address_space,
+        text: builder.text,
+    })
+}
+
+struct MirrorBuilder<'db> {
+    db: &'db dyn HirDatabase,
+    address_space: LayoutAddressSpace,
+    written: Vec<StructId>,
+    /// The names of the element types already padded to the stride of their arrays.
+    padded: Vec<String>,
+    text: String,
+}
+
+impl MirrorBuilder<'_> {
+    /// Writes the structs the fields use, then the struct with explicit padding fields, so that
+    /// `bytemuck` can derive `Pod`.
+    fn write_struct(
+        &mut self,
+        r#struct: StructId,
+    ) -> Option<()> {
+        if self.written.contains(&r#struct) {
+            return Some(());
+        }
+        self.written.push(r#struct);
+
+        let db = self.db;
+        let address_space = self.address_space;
+        let fields = db.field_types(r#struct);
+        for &r#type in fields.values() {
+            if let Some(inner) = used_struct(db, r#type) {
+                self.write_struct(inner)?;
+            }
+        }
+
+        let data = db.struct_data(r#struct);
+        let mut members = Vec::new();
+        let mut just_past_last_field = 0;
+        let (_, size) =
+            struct_member_layout(r#struct, db, address_space, |field, field_layout| {
+                let FieldLayout { offset, size, .. } = field_layout;
+                if offset > just_past_last_field {
+                    members.push(Member::Padding(offset - just_past_last_field));
+                }
+                // The bytes an `@size` adds after the value of the field are padding.
+                let type_size = fields[field].size(address_space, db).unwrap_or(size);
+                just_past_last_field = offset + type_size;
+                members.push(Member::Field {
+                    name: data.fields[field].name.as_str().to_owned(),
+                    r#type: self.rust_type(fields[field]),
+                });
+            })?;
+        if size > just_past_last_field {
+            members.push(Member::Padding(size - just_past_last_field));
+        }
+
+        let name = data.name.as_str();
+        let doc = format!(
+            "The WGSL struct `{name}` in the `{}` address space.",
+            address_space.as_str()
+        );
+        self.write_members(&doc, name, members, size)
+    }
+
+    /// Writes a `#[repr(C)]` struct with the members and an assertion of its size.
+    fn write_members(
+        &mut self,
+        doc: &str,
+        name: &str,
+        members: Vec<Member>,
+        size: u32,
+    ) -> Option<()> {
+        if !self.text.is_empty() {
+            self.text.push('\n');
+        }
+        writeln!(self.text, "/// {doc}").unwrap();
+        self.text.push_str("#[repr(C)]\n");
+        self.text
+            .push_str("#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]\n");
+        writeln!(self.text, "pub struct {name} {{").unwrap();
+        let mut padding_fields = 0;
+        for member in members {
+            match member {
+                Member::Field { name, r#type } => {
+                    writeln!(self.text, "    pub {name}: {},", r#type?).unwrap();
+                },
+                Member::Padding(bytes) => {
+                    writeln!(self.text, "    pub _pad{padding_fields}: [u8; {bytes}],").unwrap();
+                    padding_fields += 1;
+                },
+            }
+        }
+        self.text.push_str("}\n\n");
+        writeln!(
+            self.text,
+            "const _: () = assert!(std::mem::size_of::<{name}>() == {size});"
+        )
+        .unwrap();
+        Some(())
+    }
+
+    /// The Rust type with the size of the WGSL type, which contains no implicit padding.
+    fn rust_type(
+        &mut self,
+        r#type: Type,
+    ) -> Option<String> {
+        let db = self.db;
+        let address_space = self.address_space;
+        Some(match r#type.kind(db) {
+            TyKind::Scalar(_) | TyKind::Atomic(_) => scalar_type(db, r#type)?.to_owned(),
+            TyKind::Vector(vector) => {
+                format!(
+                    "[{}; {}]",
+                    scalar_type(db, vector.inner)?,
+                    components(&vector.size)?
+                )
+            },
+            TyKind::Matrix(matrix) => {
+                // The columns are padded to the alignment of the column vectors.
+                let rows = match components(&matrix.rows)? {
+                    3 => 4,
+                    rows => rows,
+                };
+                let columns = components(&matrix.columns)?;
+                format!("[[{}; {rows}]; {columns}]", scalar_type(db, matrix.inner)?)
+            },
+            TyKind::Array(array) => {
+                let count = match array.size {
+                    ArraySize::Constant(count) if count > 0 && !array.binding_array => count,
+                    ArraySize::Constant(_) | ArraySize::Dynamic => return None,
+                };
+                let stride = array.stride(address_space, db)?;
+                let size = array.inner.size(address_space, db)?;
+                let element = if size == stride {
+                    self.rust_type(array.inner)?
+                } else {
+                    match array.inner.kind(db) {
+                        // Scalars and vectors are widened to the stride with more components.
+                        TyKind::Scalar(_) | TyKind::Atomic(_) => {
+                            format!("[{}; {}]", scalar_type(db, array.inner)?, stride / 4)
+                        },
+                        TyKind::Vector(vector) => {
+                            format!("[{}; {}]", scalar_type(db, vector.inner)?, stride / 4)
+                        },
+                        _ => self.padded_element(array.inner, size, stride)?,
+                    }
+                };
+                format!("[{element}; {count}]")
+            },
+            TyKind::Struct(r#struct) => db.struct_data(r#struct).name.as_str().to_owned(),
+            _ => return None,
+        })
+    }
+
+    /// Writes a struct holding the element of an array followed by the padding to the stride of
+    /// the array, such as the 12 bytes after a struct of 4 bytes in the `uniform` address space.
+    fn padded_element(
+        &mut self,
+        element: Type,
+        size: u32,
+        stride: u32,
+    ) -> Option<String> {
+        let db = self.db;
+        let element_type = self.rust_type(element)?;
+        let element_name = match element.kind(db) {
+            TyKind::Struct(r#struct) => db.struct_data(r#struct).name.as_str().to_owned(),
+            _ => type_name(&pretty_type(db, element)),
+        };
+        let name = format!("Padded{element_name}");
+        if self.padded.contains(&name) {
+            return Some(name);
+        }
+        self.padded.push(name.clone());
+
+        let doc = format!(
+            "`{}` padded to the array stride of {stride} bytes in the `{}` address space.",
+            pretty_type(db, element),
+            self.address_space.as_str()
+        );
+        let members = vec![
+            Member::Field {
+                name: "value".to_owned(),
+                r#type: Some(element_type),
+            },
+            Member::Padding(stride - size),
+        ];
+        self.write_members(&doc, &name, members, stride)?;
+        Some(name)
+    }
+}
+
+enum Member {
+    Field {
+        name: String,
+        /// `None` if the type has no Rust equivalent.
+        r#type: Option<String>,
+    },
+    Padding(u32),
+}
+
+/// The struct a field of the type needs a mirror of.
+fn used_struct(
+    db: &dyn HirDatabase,
+    r#type: Type,
+) -> Option<StructId> {
+    match r#type.kind(db) {
+        TyKind::Struct(r#struct) => Some(r#struct),
+        TyKind::Array(array) => used_struct(db, array.inner),
+        _ => None,
+    }
+}
+
+fn scalar_type(
+    db: &dyn HirDatabase,
+    r#type: Type,
+) -> Option<&'static str> {
+    match r#type.kind(db) {
+        TyKind::Scalar(ScalarType::I32) => Some("i32"),
+        TyKind::Scalar(ScalarType::U32) => Some("u32"),
+        TyKind::Scalar(ScalarType::F32) => Some("f32"),
+        TyKind::Atomic(atomic) => scalar_type(db, atomic.inner),
+        _ => None,
+    }
+}
+
+fn components(size: &VecSize) -> Option<u8> {
+    match size {
+        VecSize::BoundVar(_) => None,
+        size => Some(size.as_u8()),
+    }
+}
+
+/// A Rust name for a WGSL type such as `mat3x2<f32>`, like `Mat3x2F32`.
+fn type_name(wgsl: &str) -> String {
+    wgsl.split(|character: char| !character.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut characters = part.chars();
+            characters.next().map_or_else(String::new, |first| {
+                first.to_ascii_uppercase().to_string() + characters.as_str()
+            })
+        })
+        .collect()
+}
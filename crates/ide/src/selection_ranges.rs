@@ -0,0 +1,33 @@
+use base_db::{FilePosition, SourceDatabase, TextRange};
+use syntax::SyntaxKind;
+
+use crate::helpers;
+
+/// The ranges to expand the selection to from the position, from the innermost token to the
+/// whole file, without the trivia the nodes end with.
+pub(crate) fn selection_ranges(
+    db: &dyn SourceDatabase,
+    position: FilePosition,
+) -> Vec<TextRange> {
+    let file = db.parse(position.file_id).syntax();
+    let token = file.token_at_offset(position.offset);
+    let Some(token) = helpers::pick_best_token(token, |kind| match kind {
+        SyntaxKind::Identifier => 2,
+        kind if kind.is_trivia() => 0,
+        _ => 1,
+    }) else {
+        return vec![file.text_range()];
+    };
+
+    let mut ranges = Vec::new();
+    if !token.kind().is_trivia() {
+        ranges.push(token.text_range());
+    }
+    for node in token.parent_ancestors() {
+        let range = helpers::trimmed_range(&node);
+        if ranges.last() != Some(&range) {
+            ranges.push(range);
+        }
+    }
+    ranges
+}
@@ -1,11 +1,31 @@
 use std::sync::Arc;
 
-use base_db::{FileId, change::Change};
-use expect_test::{Expect, expect};
+use base_db::{
+    FileId, FilePosition, FileRange, RangeInfo, SourceDatabase as _, TextRange, TextSize,
+    change::Change, input::SourceRoot,
+};
+use expect_test::{Expect, expect, expect_file};
 use hir_def::db::DefDatabase;
-use vfs::VfsPath;
+use vfs::{VfsPath, file_set::FileSet};
+
+use hir::diagnostics::{DiagnosticLevel, DiagnosticsConfig, lints::Lint};
+use hir_ty::ty::pretty::TypeVerbosity;
+use ide_completion::{
+    CallableSnippets, CompletionConfig, CompletionFieldsToResolve, CompletionItemKind, Snippet,
+    SnippetCap, SnippetScope,
+};
 
-use crate::RootDatabase;
+use crate::{
+    RootDatabase, call_graph, call_hierarchy,
+    diagnostics::{self, explanations},
+    folding_ranges, formatting,
+    goto_definition::{self, GotoDefinition},
+    goto_type_definition,
+    highlight_related::{self, ReferenceCategory},
+    hover::{self, HoverResult},
+    inlay_hints::{self, InlayHintsConfig, StructLayoutHints},
+    rust_mirror, selection_ranges,
+};
 
 fn single_file_db(source: &str) -> (RootDatabase, FileId) {
     let mut db = RootDatabase::new();
@@ -21,6 +41,25 @@ fn single_file_db(source: &str) -> (RootDatabase, FileId) {
     (db, file_id)
 }
 
+/// Creates a database with the files in one source root, so that they can import each other.
+fn multi_file_db(files: &[(&str, &str)]) -> (RootDatabase, Vec<FileId>) {
+    let mut db = RootDatabase::new();
+    let mut change = Change::new();
+    let mut file_set = FileSet::default();
+    let mut file_ids = Vec::new();
+    for (index, (path, source)) in files.iter().enumerate() {
+        let file_id = FileId::from_raw(index as u32);
+        let path = VfsPath::new_virtual_path((*path).to_owned());
+        file_set.insert(file_id, path.clone());
+        change.change_file(file_id, Some(Arc::new((*source).to_owned())), path);
+        file_ids.push(file_id);
+    }
+    change.set_roots(vec![SourceRoot::new_local(file_set)]);
+    db.apply_change(change);
+
+    (db, file_ids)
+}
+
 fn check_item_tree(
     source: &str,
     expect: Expect,
@@ -34,6 +73,24 @@ fn check_item_tree(
     ));
 }
 
+fn check_wesl_item_tree(
+    source: &str,
+    shader_defs: &[&str],
+    expect: Expect,
+) {
+    let (mut db, file_id) = single_file_db(source);
+    db.set_default_edition(syntax::Edition::Wesl0_0_1);
+    db.set_shader_defs(Arc::new(
+        shader_defs.iter().map(|def| (*def).to_owned()).collect(),
+    ));
+
+    let module_info = db.module_info(file_id.into());
+    expect.assert_eq(&hir_def::module_data::pretty::pretty_print_module(
+        &db,
+        &module_info,
+    ));
+}
+
 #[test]
 fn simple_item_tree() {
     check_item_tree(
@@ -94,3 +151,2426 @@ struct Test {
         "#]],
     );
 }
+
+#[test]
+fn item_tree_if_attributes() {
+    check_wesl_item_tree(
+        r#"
+@if(bindless) var textures: binding_array<texture_2d<f32>>;
+@if(!bindless) var texture: texture_2d<f32>;
+
+struct Light {
+    color: vec3<f32>,
+    @if(shadows && !bindless) shadow_index: u32,
+}
+
+fn shade(@if(shadows) shadow: f32, color: vec3<f32>) {}
+"#,
+        &["shadows"],
+        expect![[r#"
+            var texture: texture_2d<f32>;
+            struct Light {
+                color: vec3<f32>;
+                shadow_index: u32;
+            };
+            fn shade(f32, vec3<f32>);
+        "#]],
+    );
+}
+
+#[test]
+fn hover_explains_diagnostic() {
+    let source = "fn main() { let a: f32 = 1u; }";
+    let (db, file_id) = single_file_db(source);
+    let config = DiagnosticsConfig {
+        enabled: true,
+        type_errors: true,
+        naga_parsing_errors: true,
+        naga_validation_errors: true,
+        ..DiagnosticsConfig::default()
+    };
+    let position = position_of(file_id, source, "1u");
+    let file_range = FileRange {
+        file_id,
+        range: TextRange::empty(position.offset),
+    };
+
+    let Some(RangeInfo {
+        range,
+        info: HoverResult::Text(text),
+    }) = hover::hover(&db, &config, file_range)
+    else {
+        panic!("expected an explanation");
+    };
+    assert_eq!(&source[std::ops::Range::<usize>::from(range)], "1u");
+    assert!(text.starts_with("**An expression has a different type than expected.**"));
+}
+
+#[test]
+fn diagnostic_explanations_are_documented() {
+    expect_file!["../../../docs/book/src/diagnostics_generated.md"]
+        .assert_eq(&explanations::generate_docs());
+}
+
+/// Lists the code and the covered text of the diagnostics in `source`.
+fn check_diagnostics(
+    source: &str,
+    expect: Expect,
+) {
+    let (db, file_id) = single_file_db(source);
+    let config = DiagnosticsConfig {
+        enabled: true,
+        type_errors: true,
+        ..DiagnosticsConfig::default()
+    };
+    let mut diagnostics = diagnostics::diagnostics(&db, &config, file_id);
+    diagnostics.sort_by_key(|diagnostic| diagnostic.range.start());
+    let actual: String = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let text = &source[std::ops::Range::<usize>::from(diagnostic.range)];
+            format!("{} {text:?}\n", diagnostic.code.as_str())
+        })
+        .collect();
+    expect.assert_eq(&actual);
+}
+
+#[test]
+fn missing_return() {
+    check_diagnostics(
+        "fn sign(x: f32) -> f32 {
+    if x < 0.0 {
+        return -1.0;
+    }
+}
+fn abs_sign(x: f32) -> f32 {
+    if x < 0.0 {
+        return -1.0;
+    } else {
+        return 1.0;
+    }
+}
+fn forever() -> u32 {
+    loop {}
+}",
+        expect![[r#"
+            missing-return "}"
+        "#]],
+    );
+}
+
+#[test]
+fn unreachable_code() {
+    check_diagnostics(
+        "fn main() {
+    return;
+    let a = 1;
+    let b = a;
+}
+fn loops() {
+    loop {
+        break;
+        let c = 2;
+    }
+    for (var i = 0; i < 4; i++) {
+        continue;
+        let d = 3;
+    }
+}",
+        expect![[r#"
+            unreachable-code "let a = 1;\n    let b = a"
+            unused-variable "b"
+            unreachable-code "let c = 2"
+            unused-variable "c"
+            unreachable-code "let d = 3"
+            unused-variable "d"
+        "#]],
+    );
+}
+
+#[test]
+fn misplaced_control_flow() {
+    check_diagnostics(
+        "fn main() {
+    break;
+    if true {
+        continue;
+    }
+    loop {
+        continuing {
+            return;
+        }
+    }
+}
+fn misplaced() {
+    loop {
+        continuing {}
+        break;
+    }
+}",
+        expect![[r#"
+            break-outside-loop "break"
+            continue-outside-loop "continue"
+            return-in-continuing "return"
+            misplaced-continuing "continuing"
+        "#]],
+    );
+}
+
+#[test]
+fn unused_code() {
+    check_diagnostics(
+        "struct Unused { a: u32 }
+struct Used { a: u32 }
+alias Alias = Used;
+const SIZE = 4u;
+var<private> values: array<f32, SIZE>;
+var<private> unused_global: f32;
+fn helper(value: Alias, _ignored: u32, extra: u32) -> u32 {
+    return value.a;
+}
+fn unused() {}
+@compute @workgroup_size(1)
+fn main(@builtin(local_invocation_index) index: u32) {
+    let x = helper(Used(1u), 2u, 3u);
+    values[0] = f32(x);
+}",
+        expect![[r#"
+            unused-item "Unused"
+            unused-item "unused_global"
+            unused-variable "extra"
+            unused-item "unused"
+        "#]],
+    );
+}
+
+#[test]
+fn constants_used_as_array_sizes_are_used() {
+    check_diagnostics(
+        "@compute @workgroup_size(1)
+fn main() {
+    const N = 4;
+    const M = 2;
+    const UNUSED = 3;
+    var a: array<f32, N>;
+    let b = array<u32, M>();
+    a[0] = f32(b[0]);
+}",
+        expect![[r#"
+            unused-variable "UNUSED"
+        "#]],
+    );
+}
+
+#[test]
+fn allow_comments_suppress_the_next_item_or_statement() {
+    check_diagnostics(
+        "// wgsl-analyzer: allow(unused-variable)
+fn first(extra: u32) {}
+fn second(extra: u32) {}
+@compute @workgroup_size(1)
+fn main() {
+    first(1u);
+    second(2u);
+    // wgsl-analyzer: allow(unused-variable)
+    let a = 1;
+    let b = 2;
+}",
+        expect![[r#"
+            unused-variable "extra"
+            unused-variable "b"
+        "#]],
+    );
+}
+
+#[test]
+fn allow_file_comment_suppresses_the_whole_file() {
+    check_diagnostics(
+        "// wgsl-analyzer: allow-file(unused-item, unused-variable)
+fn unused(extra: u32) {}
+@compute @workgroup_size(1)
+fn main() {
+    let a = 1;
+}",
+        expect![""],
+    );
+}
+
+#[test]
+fn allow_comment_with_unknown_code() {
+    check_diagnostics(
+        "// wgsl-analyzer: allow(no-such-code)
+fn first(extra: u32) {}
+@compute @workgroup_size(1)
+fn main() {
+    first(1u);
+}",
+        expect![[r#"
+            unused-variable "extra"
+        "#]],
+    );
+}
+
+fn check_diagnostic_severities(
+    source: &str,
+    severity: &[(&str, DiagnosticLevel)],
+    expect: Expect,
+) {
+    let (db, file_id) = single_file_db(source);
+    let config = DiagnosticsConfig {
+        enabled: true,
+        type_errors: true,
+        severity: severity
+            .iter()
+            .map(|(code, level)| ((*code).to_owned(), *level))
+            .collect(),
+        ..DiagnosticsConfig::default()
+    };
+    let mut diagnostics = diagnostics::diagnostics(&db, &config, file_id);
+    diagnostics.sort_by_key(|diagnostic| diagnostic.range.start());
+    let actual: String = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let text = &source[std::ops::Range::<usize>::from(diagnostic.range)];
+            format!(
+                "{} {:?} {text:?}\n",
+                diagnostic.code.as_str(),
+                diagnostic.severity
+            )
+        })
+        .collect();
+    expect.assert_eq(&actual);
+}
+
+#[test]
+fn severity_overrides() {
+    let source = "fn main() -> u32 {
+    let a = 1;
+    return 1.0;
+}";
+    check_diagnostic_severities(
+        source,
+        &[],
+        expect![[r#"
+            unused-variable Warning "a"
+            type-mismatch Error "1.0"
+        "#]],
+    );
+    check_diagnostic_severities(
+        source,
+        &[
+            ("type-mismatch", DiagnosticLevel::Warning),
+            ("unused-variable", DiagnosticLevel::Error),
+        ],
+        expect![[r#"
+            unused-variable Error "a"
+            type-mismatch Warning "1.0"
+        "#]],
+    );
+    check_diagnostic_severities(
+        source,
+        &[("type-mismatch", DiagnosticLevel::Off)],
+        expect![[r#"
+            unused-variable Warning "a"
+        "#]],
+    );
+}
+
+#[test]
+fn unused_items_of_libraries_are_not_reported() {
+    check_diagnostics(
+        "fn helper() {}
+fn other() {}",
+        expect![""],
+    );
+}
+
+/// Lists the code and the covered text of the lints in `source`, with all lints enabled.
+fn check_lints(
+    source: &str,
+    expect: Expect,
+) {
+    let (db, file_id) = single_file_db(source);
+    let config = DiagnosticsConfig {
+        enabled: true,
+        type_errors: true,
+        severity: Lint::ALL
+            .iter()
+            .map(|lint| (lint.code().to_owned(), DiagnosticLevel::Warning))
+            .collect(),
+        ..DiagnosticsConfig::default()
+    };
+    let mut diagnostics = diagnostics::diagnostics(&db, &config, file_id);
+    diagnostics.sort_by_key(|diagnostic| diagnostic.range.start());
+    let actual: String = diagnostics
+        .iter()
+        .filter(|diagnostic| {
+            Lint::ALL
+                .iter()
+                .any(|lint| lint.code() == diagnostic.code.as_str())
+        })
+        .map(|diagnostic| {
+            let text = &source[std::ops::Range::<usize>::from(diagnostic.range)];
+            format!("{} {text:?}\n", diagnostic.code.as_str())
+        })
+        .collect();
+    expect.assert_eq(&actual);
+}
+
+#[test]
+fn lints_are_off_by_default() {
+    check_diagnostics(
+        "@compute @workgroup_size(3)
+fn main() {
+    let x = 1.0;
+    let _y = f32(x) == x;
+}",
+        expect![""],
+    );
+}
+
+#[test]
+fn lints() {
+    check_lints(
+        "var<private> written: f32;
+var<private> pointed: f32;
+var<private> read_only: f32 = 1.0;
+
+fn max(a: f32) -> f32 { return a; }
+
+@compute @workgroup_size(8, 3)
+fn main() {
+    written = 1.0;
+    let p = &pointed;
+    let min = read_only;
+    let a = 1.0 == min;
+    let b = f32(7 / 2);
+    let c = select(1.0, 0.0, a);
+    let d = select(0.0, 1.0, a);
+    let e = f32(c) + vec2<f32>(vec2(b, d)).x + max(d);
+}",
+        expect![[r#"
+            private-never-written "read_only"
+            shadowed-builtin "max"
+            workgroup-size-not-power-of-two "workgroup_size(8, 3)"
+            shadowed-builtin "min"
+            float-equality "1.0 == min"
+            integer-division-in-float "7 / 2"
+            select-argument-order "select(1.0, 0.0, a)"
+            redundant-conversion "f32(c)"
+            redundant-conversion "vec2<f32>(vec2(b, d))"
+        "#]],
+    );
+}
+
+#[test]
+fn private_variables_of_libraries_are_not_linted() {
+    check_lints("var<private> counter: u32;", expect![""]);
+}
+
+#[test]
+fn lints_are_documented() {
+    for lint in Lint::ALL {
+        assert!(
+            explanations::explanation(lint.code()).is_some(),
+            "{} has no explanation",
+            lint.code()
+        );
+    }
+}
+
+#[test]
+fn call_graph_from_entry_points() {
+    let (db, file_id) = single_file_db(
+        "fn leaf() {}
+fn middle() { leaf(); }
+fn unused() { leaf(); }
+@fragment
+fn main() { middle(); }",
+    );
+    let graph = call_graph::call_graph(&db, file_id);
+    let actual: String = graph
+        .functions
+        .iter()
+        .map(|function| {
+            let callees: Vec<&str> = function
+                .callees
+                .iter()
+                .map(|&callee| graph.functions[callee].name.as_str())
+                .collect();
+            format!(
+                "{}{}{} -> [{}]\n",
+                function.name,
+                if function.is_entry_point {
+                    " (entry point)"
+                } else {
+                    ""
+                },
+                if function.is_reachable {
+                    ""
+                } else {
+                    " (unreachable)"
+                },
+                callees.join(", ")
+            )
+        })
+        .collect();
+    expect![[r#"
+        leaf -> []
+        middle -> [leaf]
+        unused (unreachable) -> [leaf]
+        main (entry point) -> [middle]
+    "#]]
+    .assert_eq(&actual);
+}
+
+/// The position of the first occurrence of `marker` in `source`.
+fn position_of(
+    file_id: FileId,
+    source: &str,
+    marker: &str,
+) -> FilePosition {
+    let offset = source.find(marker).unwrap();
+    FilePosition {
+        file_id,
+        offset: TextSize::from(offset as u32),
+    }
+}
+
+#[test]
+fn incoming_calls_across_imports() {
+    let files = [
+        (
+            "/lighting.wgsl",
+            "#define_import_path lighting
+
+fn shade(x: f32) -> f32 { return x; }
+fn twice(x: f32) -> f32 { return shade(shade(x)); }",
+        ),
+        (
+            "/main.wgsl",
+            "#import lighting
+
+@fragment
+fn main() -> @location(0) vec4<f32> { return vec4<f32>(shade(0.5)); }",
+        ),
+    ];
+    let (db, file_ids) = multi_file_db(&files);
+    let position = position_of(file_ids[0], files[0].1, "shade(x: f32)");
+
+    let item = call_hierarchy::prepare_call_hierarchy(&db, position).unwrap();
+    assert_eq!(item.name, "shade");
+
+    let calls = call_hierarchy::incoming_calls(&db, position).unwrap();
+    let actual: String = calls
+        .iter()
+        .map(|call| {
+            let file = file_ids
+                .iter()
+                .position(|&file_id| file_id == call.item.target.file_id)
+                .unwrap();
+            format!(
+                "{}{} in {}: {} calls\n",
+                call.item.name,
+                if call.item.is_entry_point {
+                    " (entry point)"
+                } else {
+                    ""
+                },
+                files[file].0,
+                call.ranges.len()
+            )
+        })
+        .collect();
+    expect![[r#"
+        twice in /lighting.wgsl: 2 calls
+        main (entry point) in /main.wgsl: 1 calls
+    "#]]
+    .assert_eq(&actual);
+}
+
+#[test]
+fn incoming_calls_through_aliased_imports() {
+    let files = [
+        (
+            "/lighting.wgsl",
+            "#define_import_path lighting
+
+fn shade(x: f32) -> f32 { return x; }",
+        ),
+        (
+            "/effects.wgsl",
+            "#define_import_path effects
+#import lighting::{shade as light}",
+        ),
+        (
+            "/main.wgsl",
+            "#import effects
+
+@fragment
+fn main() -> @location(0) vec4<f32> { return vec4<f32>(light(0.5), light(1.0), 0.0, 1.0); }",
+        ),
+    ];
+    let (db, file_ids) = multi_file_db(&files);
+    let position = position_of(file_ids[0], files[0].1, "shade(x: f32)");
+
+    let calls = call_hierarchy::incoming_calls(&db, position).unwrap();
+    let actual: String = calls
+        .iter()
+        .map(|call| {
+            let ranges: Vec<_> = call
+                .ranges
+                .iter()
+                .map(|range| &files[2].1[range.range])
+                .collect();
+            format!("{}: {ranges:?}\n", call.item.name)
+        })
+        .collect();
+    expect![[r#"
+        main: ["light", "light"]
+    "#]]
+    .assert_eq(&actual);
+}
+
+#[test]
+fn outgoing_calls_list_builtins_separately() {
+    let source = "fn shade(color: vec3<f32>) -> vec3<f32> { return normalize(color); }
+@group(0) @binding(0) var t: texture_2d<f32>;
+@group(0) @binding(1) var s: sampler;
+@fragment
+fn main(@location(0) uv: vec2<f32>) -> @location(0) vec4<f32> {
+    let color = textureSample(t, s, uv);
+    return vec4<f32>(shade(color.rgb), textureSample(t, s, uv).a);
+}";
+    let (db, file_id) = single_file_db(source);
+    let position = position_of(file_id, source, "main(");
+
+    let calls = call_hierarchy::outgoing_calls(&db, position).unwrap();
+    let functions = calls
+        .functions
+        .iter()
+        .map(|call| format!("{}: {} calls\n", call.item.name, call.ranges.len()));
+    let builtins = calls
+        .builtins
+        .iter()
+        .map(|call| format!("builtin {}: {} calls\n", call.name, call.ranges.len()));
+    let actual: String = functions.chain(builtins).collect();
+    expect![[r#"
+        shade: 1 calls
+        builtin textureSample: 2 calls
+    "#]]
+    .assert_eq(&actual);
+}
+
+#[test]
+fn goto_definition_of_name_declared_by_two_imports() {
+    let files = [
+        (
+            "/a.wgsl",
+            "#define_import_path a\n\nfn helper() -> f32 { return 1.0; }",
+        ),
+        (
+            "/b.wgsl",
+            "#define_import_path b\n\nfn helper() -> f32 { return 2.0; }",
+        ),
+        (
+            "/main.wgsl",
+            "#import a\n#import b\n\nfn main() -> f32 { return helper(); }",
+        ),
+    ];
+    let (db, file_ids) = multi_file_db(&files);
+    let position = position_of(file_ids[2], files[2].1, "helper()");
+
+    let Some(GotoDefinition::Declarations(targets)) =
+        goto_definition::goto_definition(&db, position)
+    else {
+        panic!("expected declarations");
+    };
+    let actual: String = targets
+        .iter()
+        .map(|target| {
+            let file = file_ids
+                .iter()
+                .position(|&file_id| file_id == target.file_id)
+                .unwrap();
+            format!("{}\n", files[file].0)
+        })
+        .collect();
+    expect![[r#"
+        /b.wgsl
+        /a.wgsl
+    "#]]
+    .assert_eq(&actual);
+}
+
+/// The files of the declarations go to definition leads to from `marker` in the last file.
+fn check_definition_files(
+    files: &[(&str, &str)],
+    marker: &str,
+    expect: Expect,
+) {
+    let (db, file_ids) = multi_file_db(files);
+    let (_, source) = files[files.len() - 1];
+    let position = position_of(file_ids[files.len() - 1], source, marker);
+    let actual: String = match goto_definition::goto_definition(&db, position) {
+        Some(GotoDefinition::Declarations(targets)) => targets
+            .iter()
+            .map(|target| {
+                let file = file_ids
+                    .iter()
+                    .position(|&file_id| file_id == target.file_id)
+                    .unwrap();
+                format!("{}\n", files[file].0)
+            })
+            .collect(),
+        Some(GotoDefinition::Builtin(document)) => format!("builtin {}\n", document.name),
+        None => String::new(),
+    };
+    expect.assert_eq(&actual);
+}
+
+const LIBRARY: (&str, &str) = (
+    "/lib.wgsl",
+    "#define_import_path my::lib
+
+fn helper() -> f32 { return 1.0; }
+fn other() -> f32 { return 2.0; }",
+);
+
+#[test]
+fn import_item_list() {
+    let main = "#import my::lib::{helper, other as renamed}
+
+fn main() -> f32 { return helper() + renamed() + other(); }";
+    check_definition_files(
+        &[LIBRARY, ("/main.wgsl", main)],
+        "helper()",
+        expect![[r#"
+            /lib.wgsl
+        "#]],
+    );
+    check_definition_files(
+        &[LIBRARY, ("/main.wgsl", main)],
+        "renamed()",
+        expect![[r#"
+            /lib.wgsl
+        "#]],
+    );
+    // Only the alias makes `other` visible.
+    check_definition_files(&[LIBRARY, ("/main.wgsl", main)], "other()", expect![[""]]);
+}
+
+#[test]
+fn import_module_alias_with_qualified_use() {
+    let main = "#import my::lib as l
+
+fn main() -> f32 { return l::helper() + my::lib::other() + helper(); }";
+    check_definition_files(
+        &[LIBRARY, ("/main.wgsl", main)],
+        "helper()",
+        expect![[r#"
+            /lib.wgsl
+        "#]],
+    );
+    check_definition_files(
+        &[LIBRARY, ("/main.wgsl", main)],
+        "other()",
+        expect![[r#"
+            /lib.wgsl
+        "#]],
+    );
+    // An aliased module is only visible through its alias.
+    check_definition_files(&[LIBRARY, ("/main.wgsl", main)], "helper();", expect![[""]]);
+}
+
+#[test]
+fn import_cycle_terminates() {
+    check_definition_files(
+        &[
+            (
+                "/a.wgsl",
+                "#define_import_path a\n#import b\n\nfn from_a() -> f32 { return from_b(); }",
+            ),
+            (
+                "/b.wgsl",
+                "#define_import_path b\n#import a\n\nfn from_b() -> f32 { return from_a(); }",
+            ),
+            (
+                "/main.wgsl",
+                "#import a\n\nfn main() -> f32 { return from_a() + from_b(); }",
+            ),
+        ],
+        "from_b()",
+        expect![[r#"
+            /b.wgsl
+        "#]],
+    );
+}
+
+const WESL_PACKAGE: [(&str, &str); 4] = [
+    ("/shaders/main.wesl", "fn main() {}"),
+    ("/shaders/lighting.wesl", "fn shade() {}"),
+    ("/shaders/util/math.wesl", "const PI = 3.14159;"),
+    ("/shaders/util/color.wgsl", "fn srgb() {}"),
+];
+
+/// Resolves each WESL import path from the file at `importer`.
+fn check_wesl_imports(
+    importer: &str,
+    paths: &[&str],
+    expect: Expect,
+) {
+    let (db, file_ids) = multi_file_db(&WESL_PACKAGE);
+    let importer = WESL_PACKAGE
+        .iter()
+        .position(|(path, _)| *path == importer)
+        .unwrap();
+    let actual: String = paths
+        .iter()
+        .map(|path| {
+            let resolved =
+                hir_def::hir_file_id::resolve_wesl_import(&db, file_ids[importer].into(), path);
+            match resolved {
+                Some((file_id, item)) => {
+                    let file = file_ids.iter().position(|&id| id == file_id).unwrap();
+                    match item {
+                        Some(item) => {
+                            format!("{path}: {} {}\n", WESL_PACKAGE[file].0, item.as_str())
+                        },
+                        None => format!("{path}: {}\n", WESL_PACKAGE[file].0),
+                    }
+                },
+                None => format!("{path}: unresolved\n"),
+            }
+        })
+        .collect();
+    expect.assert_eq(&actual);
+}
+
+#[test]
+fn wesl_package_imports() {
+    check_wesl_imports(
+        "/shaders/util/math.wesl",
+        &[
+            "package::lighting",
+            "package::lighting::shade",
+            "package::util::math::PI",
+            "package::util::color::srgb",
+            "package::lighting::shade::extra",
+            "package::missing",
+            "dependency::module",
+        ],
+        expect![[r#"
+            package::lighting: /shaders/lighting.wesl
+            package::lighting::shade: /shaders/lighting.wesl shade
+            package::util::math::PI: /shaders/util/math.wesl PI
+            package::util::color::srgb: /shaders/util/color.wgsl srgb
+            package::lighting::shade::extra: unresolved
+            package::missing: unresolved
+            dependency::module: unresolved
+        "#]],
+    );
+}
+
+#[test]
+fn wesl_super_imports() {
+    check_wesl_imports(
+        "/shaders/util/math.wesl",
+        &[
+            "super::color::srgb",
+            "super::super::lighting",
+            "super::lighting",
+        ],
+        expect![[r#"
+            super::color::srgb: /shaders/util/color.wgsl srgb
+            super::super::lighting: /shaders/lighting.wesl
+            super::lighting: unresolved
+        "#]],
+    );
+    // There is no module above the package root.
+    check_wesl_imports(
+        "/shaders/main.wesl",
+        &["super::lighting", "super::super::lighting"],
+        expect![[r#"
+            super::lighting: /shaders/lighting.wesl
+            super::super::lighting: unresolved
+        "#]],
+    );
+}
+
+#[test]
+fn wesl_file_editions() {
+    let (db, file_ids) = multi_file_db(&[
+        ("/shaders/main.wesl", ""),
+        ("/shaders/main.wgsl", ""),
+        ("/shaders/main.txt", ""),
+    ]);
+    let editions: Vec<_> = file_ids
+        .iter()
+        .map(|&file_id| db.file_edition(file_id))
+        .collect();
+    assert_eq!(
+        editions,
+        [
+            syntax::Edition::Wesl0_0_1,
+            syntax::Edition::Wgsl,
+            syntax::Edition::DEFAULT
+        ]
+    );
+}
+
+#[test]
+fn wesl_import_of_conditional_item() {
+    let lighting = "@if(shadows) fn shadow() -> f32 { return 1.0; }";
+    let main = "import package::lighting::shadow;
+
+fn main() -> f32 { return shadow(); }";
+    check_definition_files(
+        &[("/lighting.wesl", lighting), ("/main.wesl", main)],
+        "shadow()",
+        expect![""],
+    );
+
+    let (mut db, file_ids) = multi_file_db(&[("/lighting.wesl", lighting), ("/main.wesl", main)]);
+    db.set_shader_defs(Arc::new(std::iter::once("shadows".to_owned()).collect()));
+    let position = position_of(file_ids[1], main, "shadow()");
+    let Some(GotoDefinition::Declarations(targets)) =
+        goto_definition::goto_definition(&db, position)
+    else {
+        panic!("expected a declaration");
+    };
+    assert_eq!(targets[0].file_id, file_ids[0]);
+}
+
+#[test]
+fn goto_definition_of_builtin() {
+    let source = "fn main() { let x = clamp(0.5, 0.0, 1.0); }";
+    let (db, file_id) = single_file_db(source);
+    let position = position_of(file_id, source, "clamp");
+
+    let Some(GotoDefinition::Builtin(document)) = goto_definition::goto_definition(&db, position)
+    else {
+        panic!("expected a builtin document");
+    };
+    assert_eq!(&document.text[document.focus_range], "clamp");
+    let head: String = document
+        .text
+        .lines()
+        .take(5)
+        .map(|line| format!("{line}\n"))
+        .collect();
+    expect![[r#"
+        // `clamp` is a builtin function.
+        // This document is generated and read-only.
+
+        fn clamp(f32, f32, f32) -> f32
+        fn clamp(vecN<f32>, vecN<f32>, vecN<f32>) -> vecN<f32>
+    "#]]
+    .assert_eq(&head);
+}
+
+/// The name of the declaration type definition leads to from the cursor at `$0`.
+fn check_type_definition(
+    source: &str,
+    expect: Expect,
+) {
+    let offset = source.find("$0").unwrap();
+    let source = source.replace("$0", "");
+    let (db, file_id) = single_file_db(&source);
+    let position = FilePosition {
+        file_id,
+        offset: TextSize::from(offset as u32),
+    };
+    let target = goto_type_definition::goto_type_definition(&db, position).unwrap();
+    expect.assert_eq(&source[target.focus_or_full_range()]);
+}
+
+#[test]
+fn type_definition_of_expression() {
+    check_type_definition(
+        "struct Light { color: vec3<f32> }
+fn light() -> Light { return Light(vec3(1.0)); }
+fn main() { let color = $0light().color; }",
+        expect!["Light"],
+    );
+}
+
+#[test]
+fn type_definition_of_local_through_pointer() {
+    check_type_definition(
+        "struct Light { color: vec3<f32> }
+fn shade(light: ptr<function, Light>) -> vec3<f32> { return (*$0light).color; }",
+        expect!["Light"],
+    );
+}
+
+#[test]
+fn type_definition_keeps_alias() {
+    check_type_definition(
+        "struct Light { color: vec3<f32> }
+alias Sun = Light;
+var<private> $0sun: Sun;",
+        expect!["Sun"],
+    );
+}
+
+/// Lists the ranges highlighted for the cursor at `$0`, with their line and column.
+fn check_highlights(
+    source: &str,
+    expect: Expect,
+) {
+    let offset = source.find("$0").unwrap();
+    let source = source.replace("$0", "");
+    let (db, file_id) = single_file_db(&source);
+    let position = FilePosition {
+        file_id,
+        offset: TextSize::from(offset as u32),
+    };
+    let mut highlights = highlight_related::highlight_related(&db, position).unwrap();
+    highlights.sort_by_key(|highlight| highlight.range.start());
+    let line_index = db.line_index(file_id);
+    let actual: String = highlights
+        .iter()
+        .map(|highlight| {
+            let position = line_index.line_col(highlight.range.start());
+            let text = &source[std::ops::Range::<usize>::from(highlight.range)];
+            let category = match highlight.category {
+                Some(ReferenceCategory::Read) => " (read)",
+                Some(ReferenceCategory::Write) => " (write)",
+                None => "",
+            };
+            format!("{}:{} {text}{category}\n", position.line, position.col)
+        })
+        .collect();
+    expect.assert_eq(&actual);
+}
+
+#[test]
+fn highlight_reads_and_writes() {
+    check_highlights(
+        "fn main() {
+    var total = 0;
+    for (var i = 0; i < 4; i++) {
+        total += i;
+    }
+    let result = $0total;
+}",
+        expect![[r#"
+            1:8 total
+            3:8 total (write)
+            5:17 total (read)
+        "#]],
+    );
+}
+
+#[test]
+fn highlight_exit_points() {
+    check_highlights(
+        "fn clip(x: f32) {
+    if x < 0.0 {
+        discard;
+    }
+    if x > 1.0 {
+        $0return;
+    }
+}",
+        expect![[r#"
+            2:8 discard
+            5:8 return
+            7:0 }
+        "#]],
+    );
+}
+
+#[test]
+fn highlight_loop_of_break() {
+    check_highlights(
+        "fn main() {
+    loop {
+        for (var i = 0; i < 4; i++) {
+            if i == 2 { continue; }
+            switch i { default { break; } }
+        }
+        $0break;
+    }
+}",
+        expect![[r#"
+            1:4 loop
+            6:8 break
+        "#]],
+    );
+}
+
+#[test]
+fn folding_ranges_of_blocks_comments_imports_and_regions() {
+    let source = "#import a
+#import b
+
+// one
+// two
+
+// alone
+struct S {
+    a: u32,
+}
+#ifdef FOO
+fn f() {
+    switch x {
+        default: {}
+    }
+    if true { return; }
+}
+#else
+const x = 1;
+#endif
+";
+    let (db, file_id) = single_file_db(source);
+    let folds = folding_ranges::folding_ranges(&db.parse_no_preprocessor(file_id).tree());
+    let line_index = db.line_index(file_id);
+    let actual: String = folds
+        .iter()
+        .map(|fold| {
+            let start = line_index.line_col(fold.range.start());
+            let end = line_index.line_col(fold.range.end());
+            format!(
+                "{:?} {}:{}..{}:{}\n",
+                fold.kind, start.line, start.col, end.line, end.col
+            )
+        })
+        .collect();
+    expect![[r#"
+        Imports 0:0..1:9
+        Comment 3:0..4:6
+        Block 7:9..9:1
+        Region 10:0..19:6
+        Block 11:7..16:1
+        Block 12:13..14:5
+    "#]]
+    .assert_eq(&actual);
+}
+
+#[test]
+fn selection_ranges_expand_to_ancestors() {
+    let source = "fn main() {
+    let x = 1 + foo * 2; // trailing
+}";
+    let (db, file_id) = single_file_db(source);
+    let position = FilePosition {
+        file_id,
+        offset: TextSize::from(source.find("foo").unwrap() as u32),
+    };
+    let actual: String = selection_ranges::selection_ranges(&db, position)
+        .iter()
+        .map(|&range| format!("{:?}\n", &source[std::ops::Range::<usize>::from(range)]))
+        .collect();
+    expect![[r#"
+        "foo"
+        "foo * 2"
+        "1 + foo * 2"
+        "let x = 1 + foo * 2"
+        "{\n    let x = 1 + foo * 2; // trailing\n}"
+        "fn main() {\n    let x = 1 + foo * 2; // trailing\n}"
+    "#]]
+    .assert_eq(&actual);
+}
+
+/// Formats the statements covering `selection` in `source`.
+fn check_range_formatting(
+    source: &str,
+    selection: &str,
+    expect: Expect,
+) {
+    let (db, file_id) = single_file_db(source);
+    let start = source.find(selection).unwrap();
+    let range = TextRange::at(TextSize::from(start as u32), TextSize::of(selection));
+    let formatted = formatting::format(
+        &db,
+        file_id,
+        Some(range),
+        &wgsl_formatter::FormattingOptions::default(),
+    )
+    .unwrap();
+    expect.assert_eq(&formatted.to_string());
+}
+
+/// `$0` marks the position after the typed newline.
+fn check_on_enter(
+    source: &str,
+    expect: Expect,
+) {
+    let offset = source.find("$0").unwrap();
+    let source = source.replace("$0", "");
+    let (db, file_id) = single_file_db(&source);
+    let position = FilePosition {
+        file_id,
+        offset: TextSize::from(offset as u32),
+    };
+    let edit =
+        formatting::on_enter(&db, position, &wgsl_formatter::FormattingOptions::default()).unwrap();
+    let mut result = source.clone();
+    result.replace_range(std::ops::Range::<usize>::from(edit.range), &edit.snippet);
+    expect.assert_eq(&result);
+}
+
+#[test]
+fn range_formatting_formats_covered_statements() {
+    check_range_formatting(
+        "fn main() {
+let a=1;
+    let   b  =  2;
+}
+fn other(  ) {}",
+        "a=1",
+        expect![[r#"
+            fn main() {
+                let a = 1;
+                let   b  =  2;
+            }
+            fn other(  ) {}"#]],
+    );
+}
+
+#[test]
+fn range_formatting_on_closing_brace() {
+    check_range_formatting(
+        "fn main() {
+    if a {
+    x=1;
+        }
+}",
+        "}\n}",
+        expect![[r#"
+            fn main() {
+                if a {
+                    x = 1;
+                }
+            }"#]],
+    );
+}
+
+#[test]
+fn on_enter_in_block() {
+    check_on_enter(
+        "fn main() {
+    if a {
+$0    }
+}",
+        expect![[r#"
+            fn main() {
+                if a {
+                    $0
+                }
+            }"#]],
+    );
+}
+
+#[test]
+fn on_enter_in_arguments() {
+    check_on_enter(
+        "fn main() {
+    let x = f(a,
+$0b);
+}",
+        expect![[r#"
+            fn main() {
+                let x = f(a,
+                    $0b);
+            }"#]],
+    );
+}
+
+/// Lists the hints other than types, parameter names and struct layouts, with the text they
+/// are attached to.
+fn check_inlay_hints(
+    source: &str,
+    expect: Expect,
+) {
+    let config = InlayHintsConfig {
+        enabled: true,
+        type_hints: false,
+        parameter_hints: false,
+        struct_layout_hints: Vec::new(),
+        const_value_hints: true,
+        conversion_hints: true,
+        access_mode_hints: true,
+        closing_brace_hints: Some(3),
+        type_verbosity: TypeVerbosity::Compact,
+    };
+    check_inlay_hints_with_config(source, &config, expect);
+}
+
+fn check_inlay_hints_with_config(
+    source: &str,
+    config: &InlayHintsConfig,
+    expect: Expect,
+) {
+    let (db, file_id) = single_file_db(source);
+    let mut hints = inlay_hints::inlay_hints(&db, file_id, None, config);
+    hints.sort_by_key(|hint| hint.range.start());
+    let actual: String = hints
+        .iter()
+        .map(|hint| {
+            let text = &source[std::ops::Range::<usize>::from(hint.range)];
+            let tooltip = hint
+                .tooltip
+                .as_ref()
+                .map(|tooltip| format!(" ({tooltip})"))
+                .unwrap_or_default();
+            format!("{text:?} {:?} {}{tooltip}\n", hint.kind, hint.label)
+        })
+        .collect();
+    expect.assert_eq(&actual);
+}
+
+#[test]
+fn inlay_hints_for_const_values() {
+    check_inlay_hints(
+        "const SIZE = 4;
+const AREA = SIZE * SIZE;
+const HALF: f32 = AREA / 2;
+override scale = 1.5 * 2.0;
+const cycle_a = cycle_b;
+const cycle_b = cycle_a;
+var<private> values: array<f32, AREA>;",
+        expect![[r#"
+            "SIZE * SIZE" ConstValueHint = 16
+            "AREA / 2" ConstValueHint = 8.0f
+            "1.5 * 2.0" ConstValueHint = 3.0 (The default value, which pipelines can override.)
+            "AREA" ConstValueHint = 16
+        "#]],
+    );
+}
+
+#[test]
+fn inlay_hints_for_const_values_of_literals() {
+    check_inlay_hints(
+        "const TENTH = 0.1;
+const COPY = TENTH;
+const SIGNED = 2i * 3;
+const UNSIGNED = 2u * 3;
+const SINGLE = 0.1f * 1;
+const HALF = 1.0h * 2.0;",
+        expect![[r#"
+            "TENTH" ConstValueHint = 0.1
+            "2i * 3" ConstValueHint = 6i
+            "2u * 3" ConstValueHint = 6u
+            "0.1f * 1" ConstValueHint = 0.1f
+        "#]],
+    );
+}
+
+#[test]
+fn inlay_hints_for_conversions_access_modes_and_closing_braces() {
+    check_inlay_hints(
+        "struct Light {
+    color: vec3<f32>,
+    intensity: u32,
+}
+fn scale(x: f32) -> f32 { return x; }
+fn main() {
+    var total = 0u;
+    let light = Light(vec3<f32>(1, 0.5, 0), 2);
+    let a = scale(1);
+    let b = max(total, 1);
+    let c = vec2(1, 2);
+    let d = f32(1);
+    let e = max(total, 0x10u);
+    let f = max(total, 0x10);
+    let p = &total;
+}",
+        expect![[r#"
+            "}" ClosingBraceHint struct Light
+            "1" ConversionHint as f32 (The abstract integer is converted to `f32`.)
+            "0" ConversionHint as f32 (The abstract integer is converted to `f32`.)
+            "2" ConversionHint as u32 (The abstract integer is converted to `u32`.)
+            "1" ConversionHint as f32 (The abstract integer is converted to `f32`.)
+            "1" ConversionHint as u32 (The abstract integer is converted to `u32`.)
+            "0x10" ConversionHint as u32 (The abstract integer is converted to `u32`.)
+            "p" AccessModeHint read_write (A pointer into the `function` address space with `read_write` access.)
+            "}" ClosingBraceHint fn main
+        "#]],
+    );
+}
+
+fn struct_layout_config() -> InlayHintsConfig {
+    InlayHintsConfig {
+        enabled: true,
+        type_hints: false,
+        parameter_hints: false,
+        struct_layout_hints: vec![
+            StructLayoutHints::Offset,
+            StructLayoutHints::Size,
+            StructLayoutHints::Align,
+            StructLayoutHints::Padding,
+        ],
+        const_value_hints: false,
+        conversion_hints: false,
+        access_mode_hints: false,
+        closing_brace_hints: None,
+        type_verbosity: TypeVerbosity::Compact,
+    }
+}
+
+#[test]
+fn inlay_hints_for_struct_layouts() {
+    let config = struct_layout_config();
+    check_inlay_hints_with_config(
+        "struct Light {
+    position: vec3<f32>,
+    intensity: f32,
+    color: vec3<f32>,
+}
+struct Params {
+    scale: f32,
+    light: Light,
+    count: u32,
+}
+struct Weight {
+    value: f32,
+}
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> lights: array<Light, 4>;
+@group(0) @binding(2) var<uniform> weight: Weight;",
+        &config,
+        expect![[r#"
+            "position: vec3<f32>," StructLayoutHint uniform: 0 (Offset 0, size 12 and alignment 16 in the `uniform` address space.)
+            "position: vec3<f32>," StructLayoutHint storage: 0 (Offset 0, size 12 and alignment 16 in the `storage` address space.)
+            "intensity: f32," StructLayoutHint uniform: 12 (Offset 12, size 4 and alignment 4 in the `uniform` address space.)
+            "intensity: f32," StructLayoutHint storage: 12 (Offset 12, size 4 and alignment 4 in the `storage` address space.)
+            "color: vec3<f32>," StructLayoutHint uniform: 16 (Offset 16, size 12 and alignment 16 in the `uniform` address space.)
+            "color: vec3<f32>," StructLayoutHint storage: 16 (Offset 16, size 12 and alignment 16 in the `storage` address space.)
+            "}" StructSizeHint uniform: size 32, align 16, 4 bytes padding (Size 32, array stride 32 and alignment 16 in the `uniform` address space. The last field is followed by 4 bytes of padding.)
+            "}" StructSizeHint storage: size 32, align 16, 4 bytes padding (Size 32, array stride 32 and alignment 16 in the `storage` address space. The last field is followed by 4 bytes of padding.)
+            "scale: f32," StructLayoutHint 0 (Offset 0, size 4 and alignment 4 in the `uniform` address space.)
+            "light: Light," StructLayoutHint 12 bytes padding (12 bytes of padding align the field to 16 bytes in the `uniform` address space.)
+            "light: Light," StructLayoutHint 16 (Offset 16, size 32 and alignment 16 in the `uniform` address space.)
+            "count: u32," StructLayoutHint 48 (Offset 48, size 4 and alignment 4 in the `uniform` address space.)
+            "}" StructSizeHint size 64, align 16, 12 bytes padding (Size 64, array stride 64 and alignment 16 in the `uniform` address space. The last field is followed by 12 bytes of padding.)
+            "value: f32," StructLayoutHint 0 (Offset 0, size 4 and alignment 4 in the `uniform` address space.)
+            "}" StructSizeHint size 4, stride 16, align 16 (Size 4, array stride 16 and alignment 16 in the `uniform` address space.)
+        "#]],
+    );
+}
+
+#[test]
+fn inlay_hints_for_matrix_nested_struct_and_explicit_layouts() {
+    check_inlay_hints_with_config(
+        "struct Inner {
+    value: f32,
+}
+struct Params {
+    wide: mat3x2<f32>,
+    tall: mat2x3<f32>,
+    inner: Inner,
+    next: f32,
+    @align(16) aligned: f32,
+    @size(32) sized: f32,
+    last: f32,
+}
+@group(0) @binding(0) var<uniform> params: Params;",
+        &struct_layout_config(),
+        expect![[r#"
+            "value: f32," StructLayoutHint 0 (Offset 0, size 4 and alignment 4 in the `uniform` address space.)
+            "}" StructSizeHint size 4, stride 16, align 16 (Size 4, array stride 16 and alignment 16 in the `uniform` address space.)
+            "wide: mat3x2<f32>," StructLayoutHint 0 (Offset 0, size 24 and alignment 8 in the `uniform` address space.)
+            "tall: mat2x3<f32>," StructLayoutHint 8 bytes padding (8 bytes of padding align the field to 16 bytes in the `uniform` address space.)
+            "tall: mat2x3<f32>," StructLayoutHint 32 (Offset 32, size 32 and alignment 16 in the `uniform` address space.)
+            "inner: Inner," StructLayoutHint 64 (Offset 64, size 4 and alignment 16 in the `uniform` address space.)
+            "next: f32," StructLayoutHint 12 bytes padding (12 bytes of padding follow the struct before the field, which takes a multiple of 16 bytes in the `uniform` address space.)
+            "next: f32," StructLayoutHint 80 (Offset 80, size 4 and alignment 4 in the `uniform` address space.)
+            "@align(16) aligned: f32," StructLayoutHint 12 bytes padding (12 bytes of padding align the field to 16 bytes in the `uniform` address space.)
+            "@align(16) aligned: f32," StructLayoutHint 96 (Offset 96, size 4 and alignment 16 in the `uniform` address space.)
+            "@size(32) sized: f32," StructLayoutHint 100 (Offset 100, size 32 and alignment 4 in the `uniform` address space.)
+            "last: f32," StructLayoutHint 132 (Offset 132, size 4 and alignment 4 in the `uniform` address space.)
+            "}" StructSizeHint size 144, align 16, 8 bytes padding (Size 144, array stride 144 and alignment 16 in the `uniform` address space. The last field is followed by 8 bytes of padding.)
+        "#]],
+    );
+}
+
+/// The Rust mirrors of the struct whose declaration contains `marker`, one per address space.
+fn check_rust_mirrors(
+    source: &str,
+    marker: &str,
+    expect: Expect,
+) {
+    let (db, file_id) = single_file_db(source);
+    let mirrors = rust_mirror::rust_mirrors(&db, position_of(file_id, source, marker));
+    let actual = mirrors
+        .iter()
+        .map(|mirror| mirror.text.as_str())
+        .collect::<Vec<_>>()
+        .join("\n// ---\n\n");
+    expect.assert_eq(&actual);
+}
+
+#[test]
+fn rust_mirror_of_uniform_struct() {
+    check_rust_mirrors(
+        "struct Light {
+    position: vec3<f32>,
+    intensity: f32,
+    color: vec3<f32>,
+}
+struct Params {
+    scale: f32,
+    light: Light,
+    count: u32,
+}
+@group(0) @binding(0) var<uniform> params: Params;",
+        "Params {",
+        expect![[r#"
+            /// The WGSL struct `Light` in the `uniform` address space.
+            #[repr(C)]
+            #[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+            pub struct Light {
+                pub position: [f32; 3],
+                pub intensity: f32,
+                pub color: [f32; 3],
+                pub _pad0: [u8; 4],
+            }
+
+            const _: () = assert!(std::mem::size_of::<Light>() == 32);
+
+            /// The WGSL struct `Params` in the `uniform` address space.
+            #[repr(C)]
+            #[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+            pub struct Params {
+                pub scale: f32,
+                pub _pad0: [u8; 12],
+                pub light: Light,
+                pub count: u32,
+                pub _pad1: [u8; 12],
+            }
+
+            const _: () = assert!(std::mem::size_of::<Params>() == 64);
+        "#]],
+    );
+}
+
+#[test]
+fn rust_mirrors_of_unused_struct() {
+    check_rust_mirrors(
+        "struct Material {
+    weights: array<f32, 2>,
+    transform: mat3x3<f32>,
+    flags: atomic<u32>,
+}",
+        "weights",
+        expect![[r#"
+            /// The WGSL struct `Material` in the `uniform` address space.
+            #[repr(C)]
+            #[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+            pub struct Material {
+                pub weights: [[f32; 4]; 2],
+                pub transform: [[f32; 4]; 3],
+                pub flags: u32,
+                pub _pad0: [u8; 12],
+            }
+
+            const _: () = assert!(std::mem::size_of::<Material>() == 96);
+
+            // ---
+
+            /// The WGSL struct `Material` in the `storage` address space.
+            #[repr(C)]
+            #[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+            pub struct Material {
+                pub weights: [f32; 2],
+                pub _pad0: [u8; 8],
+                pub transform: [[f32; 4]; 3],
+                pub flags: u32,
+                pub _pad1: [u8; 12],
+            }
+
+            const _: () = assert!(std::mem::size_of::<Material>() == 80);
+        "#]],
+    );
+}
+
+#[test]
+fn rust_mirror_with_explicit_alignment_and_size() {
+    check_rust_mirrors(
+        "struct Particle {
+    @align(16) mass: f32,
+    @size(32) velocity: vec3<f32>,
+    charge: f32,
+}
+@group(0) @binding(0) var<storage, read> particles: array<Particle>;",
+        "Particle {",
+        expect![[r#"
+            /// The WGSL struct `Particle` in the `storage` address space.
+            #[repr(C)]
+            #[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+            pub struct Particle {
+                pub mass: f32,
+                pub _pad0: [u8; 12],
+                pub velocity: [f32; 3],
+                pub _pad1: [u8; 20],
+                pub charge: f32,
+                pub _pad2: [u8; 12],
+            }
+
+            const _: () = assert!(std::mem::size_of::<Particle>() == 64);
+        "#]],
+    );
+}
+
+#[test]
+fn rust_mirror_pads_array_elements_to_the_stride() {
+    check_rust_mirrors(
+        "struct Weight {
+    value: f32,
+}
+struct Weights {
+    weights: array<Weight, 4>,
+    transforms: array<mat3x2<f32>, 2>,
+}
+@group(0) @binding(0) var<uniform> weights: Weights;",
+        "Weights {",
+        expect![[r#"
+            /// The WGSL struct `Weight` in the `uniform` address space.
+            #[repr(C)]
+            #[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+            pub struct Weight {
+                pub value: f32,
+            }
+
+            const _: () = assert!(std::mem::size_of::<Weight>() == 4);
+
+            /// `Weight` padded to the array stride of 16 bytes in the `uniform` address space.
+            #[repr(C)]
+            #[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+            pub struct PaddedWeight {
+                pub value: Weight,
+                pub _pad0: [u8; 12],
+            }
+
+            const _: () = assert!(std::mem::size_of::<PaddedWeight>() == 16);
+
+            /// `mat3x2<f32>` padded to the array stride of 32 bytes in the `uniform` address space.
+            #[repr(C)]
+            #[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+            pub struct PaddedMat3x2F32 {
+                pub value: [[f32; 2]; 3],
+                pub _pad0: [u8; 8],
+            }
+
+            const _: () = assert!(std::mem::size_of::<PaddedMat3x2F32>() == 32);
+
+            /// The WGSL struct `Weights` in the `uniform` address space.
+            #[repr(C)]
+            #[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+            pub struct Weights {
+                pub weights: [PaddedWeight; 4],
+                pub transforms: [PaddedMat3x2F32; 2],
+            }
+
+            const _: () = assert!(std::mem::size_of::<Weights>() == 128);
+        "#]],
+    );
+}
+
+#[test]
+fn no_rust_mirror_of_runtime_sized_struct() {
+    check_rust_mirrors(
+        "struct Lights {
+    count: u32,
+    lights: array<vec4<f32>>,
+}",
+        "count",
+        expect![[""]],
+    );
+}
+
+fn completion_config() -> CompletionConfig<'static> {
+    CompletionConfig {
+        enable_postfix_completions: true,
+        enable_imports_on_the_fly: true,
+        enable_self_on_the_fly: false,
+        enable_auto_iter: false,
+        enable_auto_await: false,
+        enable_private_editable: false,
+        enable_term_search: false,
+        term_search_fuel: 400,
+        full_function_signatures: false,
+        callable: Some(CallableSnippets::FillArguments),
+        add_semicolon_to_unit: false,
+        snippet_cap: SnippetCap::new(true),
+        prefer_no_std: false,
+        prefer_prelude: false,
+        prefer_absolute: false,
+        snippets: Vec::new(),
+        limit: None,
+        fields_to_resolve: CompletionFieldsToResolve::empty(),
+        exclude_flyimport: Vec::new(),
+        exclude_traits: &[],
+    }
+}
+
+/// Lists the completions of the given kinds at `$0`, the most relevant first.
+fn check_completions_with_config(
+    files: &[(&str, &str)],
+    config: &CompletionConfig<'_>,
+    kinds: &[CompletionItemKind],
+    expect: Expect,
+) {
+    let (source, offset) = files
+        .first()
+        .map(|(_, source)| (source.replace("$0", ""), source.find("$0").unwrap()))
+        .unwrap();
+    let mut files = files.to_vec();
+    files[0].1 = &source;
+    let (db, file_ids) = multi_file_db(&files);
+    let position = FilePosition {
+        file_id: file_ids[0],
+        offset: TextSize::from(offset as u32),
+    };
+    let mut items = ide_completion::completions2(&db, config, position, None).unwrap_or_default();
+    // Lower scores are more relevant.
+    items.sort_by_key(|item| item.relevance().score());
+    let actual: String = items
+        .iter()
+        .filter(|item| kinds.contains(&item.kind()))
+        .map(|item| {
+            let detail = item
+                .detail()
+                .map(|detail| format!(" ({detail})"))
+                .unwrap_or_default();
+            format!("{:?} {}{detail}\n", item.kind(), item.label())
+        })
+        .collect();
+    expect.assert_eq(&actual);
+}
+
+fn check_completions(
+    source: &str,
+    kinds: &[CompletionItemKind],
+    expect: Expect,
+) {
+    check_completions_with_config(
+        &[("/main.wgsl", source)],
+        &completion_config(),
+        kinds,
+        expect,
+    );
+}
+
+#[test]
+fn keyword_completions_at_item_and_statement_starts() {
+    check_completions(
+        "fn main() {}
+$0",
+        &[CompletionItemKind::Keyword],
+        expect![[r#"
+            Keyword fn
+            Keyword struct
+            Keyword var
+            Keyword override
+            Keyword const
+            Keyword alias
+            Keyword enable
+        "#]],
+    );
+    check_completions(
+        "fn main() {
+    let x = 1;
+    $0
+}",
+        &[CompletionItemKind::Keyword],
+        expect![[r#"
+            Keyword let
+            Keyword var
+            Keyword const
+            Keyword if
+            Keyword for
+            Keyword while
+            Keyword loop
+            Keyword switch
+            Keyword return
+            Keyword break
+            Keyword continue
+            Keyword continuing
+            Keyword discard
+            Keyword const_assert
+        "#]],
+    );
+    check_completions(
+        "fn main() {
+    let x = 1 + $0;
+}",
+        &[CompletionItemKind::Keyword],
+        expect![[r#""#]],
+    );
+}
+
+#[test]
+fn type_completions() {
+    check_completions(
+        "struct Light { color: vec3<f32> }
+alias Color = vec3<f32>;
+var<private> light: $0;",
+        &[
+            CompletionItemKind::Struct,
+            CompletionItemKind::TypeAlias,
+            CompletionItemKind::Keyword,
+        ],
+        expect![[r#"
+            Struct Light
+            TypeAlias Color (type Color = vec3<f32>;)
+        "#]],
+    );
+    check_completions(
+        "struct Light { color: vec3<f32> }
+fn main() {
+    let lights: array<L$0, 4>;
+}",
+        &[CompletionItemKind::Struct, CompletionItemKind::Variable],
+        expect![[r#"
+            Struct Light
+        "#]],
+    );
+    check_completions(
+        "struct Light { color: vec3<f32> }
+fn light() -> $0 {}",
+        &[CompletionItemKind::Struct, CompletionItemKind::Keyword],
+        expect![[r#"
+            Struct Light
+        "#]],
+    );
+}
+
+#[test]
+fn attribute_completions() {
+    check_completions(
+        "@$0
+fn main() {}",
+        &[CompletionItemKind::Attribute],
+        expect![[r#"
+            Attribute align
+            Attribute binding
+            Attribute builtin
+            Attribute compute
+            Attribute const
+            Attribute diagnostic
+            Attribute fragment
+            Attribute group
+            Attribute id
+            Attribute interpolate
+            Attribute invariant
+            Attribute location
+            Attribute must_use
+            Attribute size
+            Attribute vertex
+            Attribute workgroup_size
+        "#]],
+    );
+    check_completions(
+        "@fragment
+fn main(@builtin($0) position: vec4<f32>) {}",
+        &[CompletionItemKind::EnumMember],
+        expect![[r#"
+            EnumMember position
+            EnumMember vertex_index
+            EnumMember instance_index
+            EnumMember front_facing
+            EnumMember frag_depth
+            EnumMember sample_index
+            EnumMember sample_mask
+            EnumMember local_invocation_id
+            EnumMember local_invocation_index
+            EnumMember global_invocation_id
+            EnumMember workgroup_id
+            EnumMember num_workgroups
+        "#]],
+    );
+    check_completions(
+        "struct Output { @location(0) @interpolate(flat, $0) color: vec4<f32> }",
+        &[CompletionItemKind::EnumMember],
+        expect![[r#"
+            EnumMember center
+            EnumMember centroid
+            EnumMember sample
+            EnumMember first
+            EnumMember either
+        "#]],
+    );
+}
+
+#[test]
+fn template_argument_completions() {
+    check_completions(
+        "var<$0> light: f32;",
+        &[CompletionItemKind::EnumMember],
+        expect![[r#"
+            EnumMember function
+            EnumMember private
+            EnumMember workgroup
+            EnumMember uniform
+            EnumMember storage
+            EnumMember push_constant
+        "#]],
+    );
+    check_completions(
+        "var<storage, $0> lights: array<f32>;",
+        &[CompletionItemKind::EnumMember],
+        expect![[r#"
+            EnumMember read
+            EnumMember write
+            EnumMember read_write
+        "#]],
+    );
+    check_completions(
+        "var output: texture_storage_2d<$0, write>;",
+        &[CompletionItemKind::EnumMember],
+        expect![[r#"
+            EnumMember rgba8unorm
+            EnumMember rgba8snorm
+            EnumMember rgba8uint
+            EnumMember rgba8sint
+            EnumMember rgba16uint
+            EnumMember rgba16sint
+            EnumMember rgba16float
+            EnumMember rgba32uint
+            EnumMember rgba32sint
+            EnumMember rgba32float
+            EnumMember r32uint
+            EnumMember r32sint
+            EnumMember r32float
+            EnumMember rg32uint
+            EnumMember rg32sint
+            EnumMember rg32float
+        "#]],
+    );
+    check_completions(
+        "fn f(p: ptr<function, f32, $0>) {}",
+        &[CompletionItemKind::EnumMember],
+        expect![[r#"
+            EnumMember read
+            EnumMember write
+            EnumMember read_write
+        "#]],
+    );
+}
+
+/// Shows what the completions with the given labels insert at `$0`.
+fn check_completion_insertions(
+    source: &str,
+    config: &CompletionConfig<'_>,
+    labels: &[&str],
+    expect: Expect,
+) {
+    let offset = source.find("$0").unwrap();
+    let source = source.replace("$0", "");
+    let (db, file_id) = single_file_db(&source);
+    let position = FilePosition {
+        file_id,
+        offset: TextSize::from(offset as u32),
+    };
+    let items = ide_completion::completions2(&db, config, position, None).unwrap_or_default();
+    let actual: String = labels
+        .iter()
+        .map(|label| {
+            let inserted = items
+                .iter()
+                .find(|item| item.label() == *label)
+                .map(|item| {
+                    let inserted: String = item
+                        .text_edit()
+                        .iter()
+                        .map(|indel| indel.insert.as_str())
+                        .collect();
+                    let snippet = if item.is_snippet() { "snippet " } else { "" };
+                    format!("{snippet}{inserted:?}")
+                })
+                .unwrap_or_else(|| "not completed".to_owned());
+            format!("{label}: {inserted}\n")
+        })
+        .collect();
+    expect.assert_eq(&actual);
+}
+
+fn workgroup_snippet(scope: SnippetScope) -> Snippet {
+    Snippet::new(
+        &["workgroup".to_owned()],
+        &[],
+        &["var<workgroup> ${1:name}: ${2:array<f32, 64>};".to_owned()],
+        "A workgroup variable",
+        scope,
+    )
+    .unwrap()
+}
+
+#[test]
+fn snippets_at_item_and_statement_scope() {
+    let config = CompletionConfig {
+        snippets: vec![
+            workgroup_snippet(SnippetScope::Item),
+            Snippet::new(
+                &["unit".to_owned()],
+                &[],
+                &["vec3<f32>(0.0, 1.0, 0.0)".to_owned()],
+                "",
+                SnippetScope::Expression,
+            )
+            .unwrap(),
+        ],
+        ..completion_config()
+    };
+    let files = |source| [("/main.wgsl", source)];
+    check_completions_with_config(
+        &files("fn main() {}\n$0"),
+        &config,
+        &[CompletionItemKind::Snippet],
+        expect![[r#"
+            Snippet compute (compute shader entry point)
+            Snippet vertex (vertex shader entry point)
+            Snippet fragment (fragment shader entry point)
+            Snippet workgroup (A workgroup variable)
+        "#]],
+    );
+    check_completions_with_config(
+        &files("fn main() {\n    $0\n}"),
+        &config,
+        &[CompletionItemKind::Snippet],
+        expect![[r#"
+            Snippet for (for (var i = 0u; i < count; i++) {})
+            Snippet switch (switch selector { case 0: {} default: {} })
+            Snippet unit
+        "#]],
+    );
+    check_completions_with_config(
+        &files("fn main() {\n    let up = $0;\n}"),
+        &config,
+        &[CompletionItemKind::Snippet],
+        expect![[r#"
+            Snippet unit
+        "#]],
+    );
+}
+
+#[test]
+fn config_snippet_insertion() {
+    let config = CompletionConfig {
+        snippets: vec![workgroup_snippet(SnippetScope::Item)],
+        ..completion_config()
+    };
+    check_completion_insertions(
+        "fn main() {}\nwork$0",
+        &config,
+        &["workgroup"],
+        expect![[r#"
+            workgroup: snippet "var<workgroup> ${1:name}: ${2:array<f32, 64>};"
+        "#]],
+    );
+}
+
+#[test]
+fn snippets_need_snippet_support() {
+    let config = CompletionConfig {
+        snippet_cap: None,
+        snippets: vec![workgroup_snippet(SnippetScope::Item)],
+        ..completion_config()
+    };
+    check_completions_with_config(
+        &[("/main.wgsl", "fn main() {}\n$0")],
+        &config,
+        &[CompletionItemKind::Snippet],
+        expect![[r#""#]],
+    );
+    check_completion_insertions(
+        "struct Light { color: vec3<f32> }
+fn shade(light: Light, ambient: f32) -> f32 { return ambient; }
+fn main() {
+    let x = $0;
+}",
+        &config,
+        &["shade", "Light", "saturate"],
+        expect![[r#"
+            shade: "shade"
+            Light: "Light"
+            saturate: "saturate"
+        "#]],
+    );
+}
+
+#[test]
+fn call_snippets() {
+    let source = "struct Light { color: vec3<f32> }
+fn shade(light: Light, ambient: f32) -> f32 { return ambient; }
+fn now() -> f32 { return 0.0; }
+fn main() {
+    let x = $0;
+}";
+    let labels = ["shade", "now", "Light", "saturate", "textureSample"];
+    check_completion_insertions(
+        source,
+        &completion_config(),
+        &labels,
+        expect![[r#"
+        shade: snippet "shade(${1:light}, ${2:ambient})$0"
+        now: snippet "now()$0"
+        Light: snippet "Light(${1:color})$0"
+        saturate: snippet "saturate(${1:e})$0"
+        textureSample: snippet "textureSample($0)"
+    "#]],
+    );
+    let config = CompletionConfig {
+        callable: Some(CallableSnippets::AddParentheses),
+        ..completion_config()
+    };
+    check_completion_insertions(
+        source,
+        &config,
+        &labels,
+        expect![[r#"
+        shade: snippet "shade($0)"
+        now: snippet "now()$0"
+        Light: snippet "Light($0)"
+        saturate: snippet "saturate($0)"
+        textureSample: snippet "textureSample($0)"
+    "#]],
+    );
+    let config = CompletionConfig {
+        callable: None,
+        ..completion_config()
+    };
+    check_completion_insertions(
+        source,
+        &config,
+        &labels,
+        expect![[r#"
+        shade: "shade"
+        now: "now"
+        Light: "Light"
+        saturate: "saturate"
+        textureSample: "textureSample"
+    "#]],
+    );
+}
+
+#[test]
+fn importable_items() {
+    let (mut db, _) = multi_file_db(&[
+        LIBRARY,
+        (
+            "/shapes.wgsl",
+            "#define_import_path shapes
+#import my::lib::helper
+
+struct Circle { radius: f32 }
+alias Radius = f32;
+const PI = 3.14;
+override scale: f32;
+var<private> count: u32;",
+        ),
+        ("/main.wgsl", "fn main() {}"),
+    ]);
+    db.set_custom_imports(Arc::new(
+        [(
+            "noise".to_owned(),
+            "fn random(seed: u32) -> f32 { return 0.5; }".to_owned(),
+        )]
+        .into_iter()
+        .collect(),
+    ));
+    let actual: String = db
+        .importable_items()
+        .iter()
+        .map(|item| {
+            format!(
+                "{}::{} {:?} {:?}\n",
+                item.import_path,
+                item.name.as_str(),
+                item.kind,
+                item.file_id
+            )
+        })
+        .collect();
+    expect![[r#"
+        my::lib::helper Function Some(FileId(0))
+        my::lib::other Function Some(FileId(0))
+        noise::random Function None
+        shapes::Circle Struct Some(FileId(1))
+        shapes::Radius TypeAlias Some(FileId(1))
+        shapes::PI GlobalConstant Some(FileId(1))
+        shapes::scale Override Some(FileId(1))
+        shapes::count GlobalVariable Some(FileId(1))
+    "#]]
+    .assert_eq(&actual);
+}
+
+/// Applies the completion which imports the item `label` at `$0` in the first file.
+fn check_auto_import(
+    files: &[(&str, &str)],
+    label: &str,
+    expect: Expect,
+) {
+    let (path, source) = files[0];
+    let offset = source.find("$0").unwrap();
+    let source = source.replace("$0", "");
+    let mut files = files.to_vec();
+    files[0] = (path, &source);
+    let (db, file_ids) = multi_file_db(&files);
+    let position = FilePosition {
+        file_id: file_ids[0],
+        offset: TextSize::from(offset as u32),
+    };
+    let items =
+        ide_completion::completions2(&db, &completion_config(), position, None).unwrap_or_default();
+    let actual = match items.iter().find(|item| {
+        item.label() == label
+            && item
+                .detail()
+                .is_some_and(|detail| detail.starts_with("#import"))
+    }) {
+        Some(item) => {
+            let mut text = source.clone();
+            item.text_edit().apply(&mut text);
+            text
+        },
+        None => "not completed".to_owned(),
+    };
+    expect.assert_eq(&actual);
+}
+
+#[test]
+fn auto_import_after_the_last_import() {
+    check_auto_import(
+        &[
+            (
+                "/main.wgsl",
+                "#import my::lib::other
+#import noise::random
+
+fn main() { let x = hel$0; }",
+            ),
+            LIBRARY,
+            (
+                "/noise.wgsl",
+                "#define_import_path noise
+
+fn random() -> f32 { return 0.5; }",
+            ),
+        ],
+        "helper",
+        expect![[r#"
+            #import my::lib::other
+            #import noise::random
+            #import my::lib::{helper}
+
+            fn main() { let x = helper; }"#]],
+    );
+}
+
+#[test]
+fn auto_import_after_define_import_path() {
+    check_auto_import(
+        &[
+            (
+                "/main.wgsl",
+                "#define_import_path app
+
+fn main() { let x = hel$0; }",
+            ),
+            LIBRARY,
+        ],
+        "helper",
+        expect![[r#"
+            #define_import_path app
+            #import my::lib::{helper}
+
+            fn main() { let x = helper; }"#]],
+    );
+}
+
+#[test]
+fn auto_import_without_imports() {
+    check_auto_import(
+        &[("/main.wgsl", "fn main() { let x = hel$0; }"), LIBRARY],
+        "helper",
+        expect![[r#"
+            #import my::lib::{helper}
+            fn main() { let x = helper; }"#]],
+    );
+}
+
+#[test]
+fn auto_import_skips_imported_items() {
+    for import in [
+        "#import my::lib::{helper}",
+        "#import my::lib::{helper as renamed}",
+        "#import my::lib",
+        "#import my::lib as lib",
+    ] {
+        let main = format!("{import}\n\nfn main() {{ let x = hel$0; }}");
+        check_auto_import(
+            &[("/main.wgsl", &main), LIBRARY],
+            "helper",
+            expect!["not completed"],
+        );
+    }
+}
+
+#[test]
+fn completions_of_the_expected_type_come_first() {
+    let source = |position: &str| {
+        format!(
+            "struct Light {{ color: vec3<f32> }}
+fn shade(light: Light, intensity: f32) -> f32 {{ return intensity; }}
+fn main() -> f32 {{
+    let count = 1u;
+    let sun = Light(vec3(1.0));
+    let scale = 0.5;
+    {position}
+}}"
+        )
+    };
+    let kinds = [CompletionItemKind::Variable, CompletionItemKind::Struct];
+    check_completions(
+        &source("let a = shade($0);"),
+        &kinds,
+        expect![[r#"
+        Variable sun (Light)
+        Struct Light (Light(color))
+        Variable scale (f32)
+        Variable count (u32)
+    "#]],
+    );
+    check_completions(
+        &source("let a = shade(sun, $0);"),
+        &kinds,
+        expect![[r#"
+        Variable scale (f32)
+        Variable sun (Light)
+        Variable count (u32)
+        Struct Light (Light(color))
+    "#]],
+    );
+    check_completions(
+        &source("let a = shade(sun, s$0);"),
+        &kinds,
+        expect![[r#"
+        Variable scale (f32)
+        Variable sun (Light)
+        Variable count (u32)
+        Struct Light (Light(color))
+    "#]],
+    );
+    check_completions(
+        &source("let light: Light = $0;"),
+        &kinds,
+        expect![[r#"
+        Variable sun (Light)
+        Struct Light (Light(color))
+        Variable scale (f32)
+        Variable count (u32)
+    "#]],
+    );
+    check_completions(
+        &source("return $0;"),
+        &kinds,
+        expect![[r#"
+        Variable scale (f32)
+        Variable sun (Light)
+        Variable count (u32)
+        Struct Light (Light(color))
+    "#]],
+    );
+}
+
+#[test]
+fn postfix_completions_depend_on_the_receiver_type() {
+    let source = |position: &str| {
+        format!(
+            "struct Light {{ color: vec3<f32> }}
+fn main() -> f32 {{
+    let flag = true;
+    let scale = 0.5;
+    let count = 1u;
+    let direction = vec3(1.0, 0.0, 0.0);
+    let light = Light(direction);
+    {position}
+}}"
+        )
+    };
+    let kinds = [CompletionItemKind::Snippet];
+    check_completions(
+        &source("flag.$0"),
+        &kinds,
+        expect![[r#"
+        Snippet if (if expression {})
+        Snippet let (let name = expression;)
+        Snippet var (var name = expression;)
+        Snippet not (!expression)
+        Snippet f32 (f32(expression))
+        Snippet u32 (u32(expression))
+        Snippet i32 (i32(expression))
+    "#]],
+    );
+    check_completions(
+        &source("let a = flag.$0;"),
+        &kinds,
+        expect![[r#"
+        Snippet not (!expression)
+        Snippet f32 (f32(expression))
+        Snippet u32 (u32(expression))
+        Snippet i32 (i32(expression))
+    "#]],
+    );
+    check_completions(
+        &source("scale.$0"),
+        &kinds,
+        expect![[r#"
+        Snippet let (let name = expression;)
+        Snippet var (var name = expression;)
+        Snippet return (return expression;)
+        Snippet u32 (u32(expression))
+        Snippet i32 (i32(expression))
+        Snippet bitcast (bitcast<T>(expression))
+    "#]],
+    );
+    check_completions(
+        &source("let a = count.$0;"),
+        &kinds,
+        expect![[r#"
+        Snippet f32 (f32(expression))
+        Snippet i32 (i32(expression))
+        Snippet bitcast (bitcast<T>(expression))
+    "#]],
+    );
+    check_completions(
+        &source("let a = direction.$0;"),
+        &kinds,
+        expect![[r#"
+        Snippet normalize (normalize(expression))
+        Snippet length (length(expression))
+        Snippet bitcast (bitcast<T>(expression))
+    "#]],
+    );
+    check_completions(
+        &source("light.$0"),
+        &kinds,
+        expect![[r#"
+        Snippet let (let name = expression;)
+        Snippet var (var name = expression;)
+    "#]],
+    );
+}
+
+#[test]
+fn postfix_completion_insertions() {
+    let source = "fn main() {
+    let flag = true;
+    flag.$0
+}";
+    check_completion_insertions(
+        source,
+        &completion_config(),
+        &["if", "not", "let"],
+        expect![[r#"
+            if: snippet "if flag {\n\t$0\n}"
+            not: snippet "!flag$0"
+            let: snippet "let ${1:name} = flag;$0"
+        "#]],
+    );
+}
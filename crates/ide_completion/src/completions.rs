@@ -1,8 +1,15 @@
 use crate::item::{Builder, CompletionItem};
 
+pub(crate) mod attribute;
+pub(crate) mod auto_import;
 pub(crate) mod dot;
 pub(crate) mod expression;
 pub(crate) mod import;
+pub(crate) mod keyword;
+pub(crate) mod postfix;
+pub(crate) mod snippet;
+pub(crate) mod template;
+pub(crate) mod r#type;
 
 #[derive(Debug, Default)]
 pub struct Completions {
@@ -0,0 +1,73 @@
+use super::Completions;
+use crate::{
+    context::{CompletionContext, ImmediateLocation},
+    item::{CompletionItem, CompletionItemKind},
+};
+
+const ATTRIBUTES: &[&str] = &[
+    "align",
+    "binding",
+    "builtin",
+    "compute",
+    "const",
+    "diagnostic",
+    "fragment",
+    "group",
+    "id",
+    "interpolate",
+    "invariant",
+    "location",
+    "must_use",
+    "size",
+    "vertex",
+    "workgroup_size",
+];
+
+const BUILTIN_VALUES: &[&str] = &[
+    "position",
+    "vertex_index",
+    "instance_index",
+    "front_facing",
+    "frag_depth",
+    "sample_index",
+    "sample_mask",
+    "local_invocation_id",
+    "local_invocation_index",
+    "global_invocation_id",
+    "workgroup_id",
+    "num_workgroups",
+];
+
+const INTERPOLATION_TYPES: &[&str] = &["perspective", "linear", "flat"];
+
+const INTERPOLATION_SAMPLING: &[&str] = &["center", "centroid", "sample", "first", "either"];
+
+const DIAGNOSTIC_SEVERITIES: &[&str] = &["error", "warning", "info", "off"];
+
+pub(crate) fn complete_attributes(
+    accumulator: &mut Completions,
+    ctx: &CompletionContext,
+) -> Option<()> {
+    let (kind, names) = match &ctx.completion_location {
+        Some(ImmediateLocation::AttributeName) => (CompletionItemKind::Attribute, ATTRIBUTES),
+        Some(ImmediateLocation::AttributeArgument { attribute, index }) => {
+            let values = match (attribute.as_str(), index) {
+                ("builtin", 0) => BUILTIN_VALUES,
+                ("interpolate", 0) => INTERPOLATION_TYPES,
+                ("interpolate", 1) => INTERPOLATION_SAMPLING,
+                ("diagnostic", 0) => DIAGNOSTIC_SEVERITIES,
+                _ => return None,
+            };
+            (CompletionItemKind::EnumMember, values)
+        },
+        _ => return None,
+    };
+
+    accumulator.add_all(
+        names
+            .iter()
+            .map(|name| CompletionItem::new(kind, ctx.source_range(), *name).build()),
+    );
+
+    Some(())
+}
@@ -0,0 +1,140 @@
+use base_db::TextSize;
+use hir_def::{
+    import_index::{ImportableItem, ImportableItemKind},
+    module_data::{ImportValue, ModuleInfo, ModuleItem},
+};
+use rustc_hash::FxHashSet;
+use syntax::{AstNode, Edition, ast};
+use text_edit::{TextEdit, TextEditBuilder};
+
+use super::Completions;
+use crate::{
+    context::{CompletionContext, ImmediateLocation},
+    item::{CompletionItem, CompletionItemKind},
+};
+
+/// Completes the items of modules which are not imported yet, and adds the
+/// `#import` for the chosen item.
+pub(crate) fn complete_auto_import(
+    accumulator: &mut Completions,
+    ctx: &CompletionContext,
+) -> Option<()> {
+    if !ctx.config.enable_imports_on_the_fly
+        || ctx.db.file_edition(ctx.position.file_id) != Edition::Wgsl
+    {
+        return None;
+    }
+    let types_only = match ctx.completion_location {
+        Some(ImmediateLocation::InsideStatement | ImmediateLocation::StatementList) => false,
+        Some(ImmediateLocation::Type) => true,
+        _ => return None,
+    };
+
+    // Only search the index once something has been typed.
+    let source_range = ctx.source_range();
+    if source_range.is_empty() {
+        return None;
+    }
+    let typed = ctx.token.text().to_lowercase();
+
+    let mut in_scope = FxHashSet::default();
+    ctx.resolver.process_value_names(|name, _| {
+        in_scope.insert(name);
+    });
+    ctx.resolver.process_type_names(|name, _| {
+        in_scope.insert(name);
+    });
+
+    let module_info = ctx.db.module_info(ctx.file_id);
+    let text = ctx.db.file_text(ctx.position.file_id);
+    let importable_items = ctx.db.importable_items();
+    for item in importable_items.iter() {
+        let is_type = matches!(
+            item.kind,
+            ImportableItemKind::Struct | ImportableItemKind::TypeAlias
+        );
+        if (types_only && !is_type)
+            || item.file_id == Some(ctx.position.file_id)
+            || in_scope.contains(&item.name)
+            || is_imported(&module_info, item)
+            || !item.name.as_str().to_lowercase().starts_with(&typed)
+        {
+            continue;
+        }
+        let kind = match item.kind {
+            ImportableItemKind::Function => CompletionItemKind::Function,
+            ImportableItemKind::GlobalVariable => CompletionItemKind::Variable,
+            ImportableItemKind::GlobalConstant | ImportableItemKind::Override => {
+                CompletionItemKind::Constant
+            },
+            ImportableItemKind::Struct => CompletionItemKind::Struct,
+            ImportableItemKind::TypeAlias => CompletionItemKind::TypeAlias,
+        };
+        let import = format!("#import {}::{{{}}}", item.import_path, item.name.as_str());
+
+        let mut edit = TextEdit::builder();
+        edit.replace(source_range, item.name.as_str().to_owned());
+        insert_import(&mut edit, &ctx.file, &text, &import);
+
+        let mut completion = CompletionItem::new(kind, source_range, item.name.as_str());
+        completion.detail(import).text_edit(edit.finish());
+        completion.add_to(accumulator);
+    }
+
+    Some(())
+}
+
+/// Whether an import of the file already names the item, possibly under an alias, or imports its
+/// whole module.
+fn is_imported(
+    module_info: &ModuleInfo,
+    item: &ImportableItem,
+) -> bool {
+    module_info.items().iter().any(|module_item| {
+        let ModuleItem::Import(import) = module_item else {
+            return false;
+        };
+        let import = module_info.get(*import);
+        matches!(&import.value, ImportValue::Custom(key) if **key == *item.import_path)
+            && import
+                .items
+                .as_ref()
+                .is_none_or(|items| items.iter().any(|imported| imported.name == item.name))
+    })
+}
+
+/// Inserts the import after the last import of the file, or after
+/// `#define_import_path` if there is none yet.
+fn insert_import(
+    edit: &mut TextEditBuilder,
+    file: &ast::SourceFile,
+    text: &str,
+    import: &str,
+) {
+    let last_import = file
+        .items()
+        .filter_map(|item| match item {
+            ast::Item::Import(import) => Some(import),
+            _ => None,
+        })
+        .last();
+    if let Some(last_import) = last_import {
+        // The import node contains the trailing newline.
+        let text = last_import.syntax().text().to_string();
+        let end = last_import.syntax().text_range().start() + TextSize::of(text.trim_end());
+        edit.insert(end, format!("\n{import}"));
+        return;
+    }
+
+    // The preprocessor blanks out `#define_import_path`, so it is only in the text.
+    let mut line_start = TextSize::from(0);
+    for line in text.split_inclusive('\n') {
+        if line.trim_start().starts_with("#define_import_path") {
+            let end = line_start + TextSize::of(line.trim_end());
+            edit.insert(end, format!("\n{import}"));
+            return;
+        }
+        line_start += TextSize::of(line);
+    }
+    edit.insert(TextSize::from(0), format!("{import}\n"));
+}
@@ -1,13 +1,20 @@
 use hir_def::{
+    HirFileId,
+    db::{DefinitionWithBodyId, Location},
     module_data::{ModuleItem, Name, pretty::pretty_module_item},
     resolver::ScopeDef,
 };
-use hir_ty::{builtins::Builtin, ty::pretty::pretty_type};
+use hir_ty::{
+    builtins::Builtin,
+    ty::{TyKind, Type, pretty::pretty_type},
+};
+use itertools::Itertools;
 
 use super::Completions;
 use crate::{
+    config::CallableSnippets,
     context::{CompletionContext, ImmediateLocation},
-    item::{CompletionItem, CompletionItemKind, CompletionRelevance},
+    item::{Builder, CompletionItem, CompletionItemKind, CompletionRelevance},
 };
 
 pub(crate) fn complete_names_in_scope(
@@ -15,7 +22,7 @@ pub(crate) fn complete_names_in_scope(
     ctx: &CompletionContext,
 ) -> Option<()> {
     match ctx.completion_location {
-        Some(ImmediateLocation::InsideStatement) => {},
+        Some(ImmediateLocation::InsideStatement | ImmediateLocation::StatementList) => {},
         _ => return None,
     }
 
@@ -36,14 +43,15 @@ pub(crate) fn complete_names_in_scope(
             },
         };
 
+        let r#type = match item {
+            ScopeDef::Local(local) => ctx.container.and_then(|def| {
+                let inference = ctx.db.infer(def);
+                inference.type_of_binding.get(local).copied()
+            }),
+            ScopeDef::ModuleItem(file_id, item) => module_item_type(ctx, file_id, item),
+        };
         let detail = match item {
-            ScopeDef::Local(local) => ctx
-                .container
-                .and_then(|def| {
-                    let inference = ctx.db.infer(def);
-                    inference.type_of_binding.get(local).copied()
-                })
-                .map(|r#type| pretty_type(ctx.db, r#type)),
+            ScopeDef::Local(_) => r#type.map(|r#type| pretty_type(ctx.db, r#type)),
             ScopeDef::ModuleItem(file_id, item) => {
                 let module_info = ctx.db.module_info(file_id);
                 let detail = pretty_module_item(&item, &module_info, ctx.db.upcast());
@@ -54,28 +62,156 @@ pub(crate) fn complete_names_in_scope(
         let mut completion = CompletionItem::new(kind, ctx.source_range(), name.as_str());
         completion.set_relevance(CompletionRelevance {
             exact_name_match: false,
-            type_match: None,
+            type_match: r#type.and_then(|r#type| ctx.type_match(r#type)),
             is_local: matches!(item, ScopeDef::Local(_)),
             exact_postfix_snippet_match: false,
             is_builtin: false,
             swizzle_index: None,
         });
         completion.set_detail(detail);
+        if let ScopeDef::ModuleItem(file_id, ModuleItem::Function(function)) = item {
+            let module_info = ctx.db.module_info(file_id);
+            let parameters = module_info
+                .get(function)
+                .parameters
+                .clone()
+                .map(|index| module_info.data[index].name.as_str().to_owned())
+                .collect();
+            add_call(&mut completion, ctx, name.as_str(), Some(parameters));
+        }
         completion.add_to(accumulator);
     });
 
     accumulator.add_all(Builtin::ALL_BUILTINS.iter().map(|name| {
-        CompletionItem::new(CompletionItemKind::Function, ctx.source_range(), *name)
-            .with_relevance(CompletionRelevance {
-                exact_name_match: false,
-                type_match: None,
-                is_local: false,
-                exact_postfix_snippet_match: false,
-                is_builtin: true,
-                swizzle_index: None,
-            })
-            .build()
+        let mut completion =
+            CompletionItem::new(CompletionItemKind::Function, ctx.source_range(), *name);
+        completion.set_relevance(CompletionRelevance {
+            exact_name_match: false,
+            type_match: None,
+            is_local: false,
+            exact_postfix_snippet_match: false,
+            is_builtin: true,
+            swizzle_index: None,
+        });
+        if ctx.config.snippet_cap.is_some() && ctx.config.callable.is_some() {
+            add_call(&mut completion, ctx, name, builtin_parameters(ctx, name));
+        }
+        completion.build()
     }));
 
+    complete_struct_constructors(accumulator, ctx);
+
     None
 }
+
+/// Completes `MyStruct(field_a, field_b)` for the structs in scope.
+fn complete_struct_constructors(
+    accumulator: &mut Completions,
+    ctx: &CompletionContext,
+) {
+    ctx.resolver.process_type_names(|name, item| {
+        let ScopeDef::ModuleItem(file_id, ModuleItem::Struct(r#struct)) = item else {
+            return;
+        };
+        if name == Name::missing() {
+            return;
+        }
+        let r#struct = ctx.db.intern_struct(Location::new(file_id, r#struct));
+        let fields: Vec<String> = ctx
+            .db
+            .struct_data(r#struct)
+            .fields
+            .iter()
+            .map(|(_, field)| field.name.as_str().to_owned())
+            .collect();
+        let r#type = ctx.db.intern_ty(TyKind::Struct(r#struct));
+
+        let mut completion = CompletionItem::new(
+            CompletionItemKind::Struct,
+            ctx.source_range(),
+            name.as_str(),
+        );
+        completion
+            .set_relevance(CompletionRelevance {
+                type_match: ctx.type_match(r#type),
+                ..CompletionRelevance::default()
+            })
+            .detail(format!("{}({})", name.as_str(), fields.join(", ")));
+        add_call(&mut completion, ctx, name.as_str(), Some(fields));
+        completion.add_to(accumulator);
+    });
+}
+
+/// The type of a value declared at module scope, or the return type of a function.
+fn module_item_type(
+    ctx: &CompletionContext,
+    file_id: HirFileId,
+    item: ModuleItem,
+) -> Option<Type> {
+    let def = match item {
+        ModuleItem::Function(function) => {
+            let function = ctx.db.intern_function(Location::new(file_id, function));
+            return ctx.db.function_type(function).lookup(ctx.db).return_type;
+        },
+        ModuleItem::GlobalVariable(variable) => DefinitionWithBodyId::GlobalVariable(
+            ctx.db
+                .intern_global_variable(Location::new(file_id, variable)),
+        ),
+        ModuleItem::GlobalConstant(constant) => DefinitionWithBodyId::GlobalConstant(
+            ctx.db
+                .intern_global_constant(Location::new(file_id, constant)),
+        ),
+        ModuleItem::Override(override_declaration) => DefinitionWithBodyId::Override(
+            ctx.db
+                .intern_override(Location::new(file_id, override_declaration)),
+        ),
+        ModuleItem::Struct(_) | ModuleItem::TypeAlias(_) | ModuleItem::Import(_) => return None,
+    };
+    ctx.db.infer(def).return_type
+}
+
+/// Completes a call instead of the bare name, with a placeholder for every
+/// parameter if they are known.
+fn add_call(
+    completion: &mut Builder,
+    ctx: &CompletionContext,
+    name: &str,
+    parameters: Option<Vec<String>>,
+) {
+    let (Some(cap), Some(callable)) = (ctx.config.snippet_cap, &ctx.config.callable) else {
+        return;
+    };
+    let snippet = match (callable, parameters) {
+        (_, Some(parameters)) if parameters.is_empty() => format!("{name}()$0"),
+        (CallableSnippets::FillArguments, Some(parameters)) => {
+            let arguments = parameters
+                .iter()
+                .enumerate()
+                .map(|(index, parameter)| format!("${{{}:{parameter}}}", index + 1))
+                .join(", ");
+            format!("{name}({arguments})$0")
+        },
+        _ => format!("{name}($0)"),
+    };
+    completion.lookup_by(name).insert_snippet(cap, snippet);
+}
+
+/// The parameter names of a builtin, if all of its overloads agree on them.
+fn builtin_parameters(
+    ctx: &CompletionContext,
+    name: &str,
+) -> Option<Vec<String>> {
+    let builtin = Builtin::for_name(ctx.db, &Name::from(name))?;
+    let mut overloads = builtin.overloads().map(|(_, overload)| {
+        let details = overload.r#type.lookup(ctx.db);
+        details
+            .parameters
+            .iter()
+            .map(|(_, name)| (*name != Name::missing()).then(|| name.as_str().to_owned()))
+            .collect::<Option<Vec<_>>>()
+    });
+    let first = overloads.next()??;
+    overloads
+        .all(|parameters| parameters.as_ref() == Some(&first))
+        .then_some(first)
+}
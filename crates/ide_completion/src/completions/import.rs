@@ -14,11 +14,20 @@ pub(crate) fn complete_import(
     };
 
     let custom_imports = ctx.db.custom_imports();
-    let imports = custom_imports.keys().map(|import| {
+    let import_path_index = ctx.db.import_path_index();
+    let mut keys: Vec<&str> = custom_imports
+        .keys()
+        .map(String::as_str)
+        .chain(import_path_index.keys().map(AsRef::as_ref))
+        .collect();
+    keys.sort_unstable();
+    keys.dedup();
+
+    let imports = keys.into_iter().map(|import| {
         CompletionItem::new(
             CompletionItemKind::Module,
             ctx.source_range(),
-            import.to_string(),
+            import.to_owned(),
         )
         .build()
     });
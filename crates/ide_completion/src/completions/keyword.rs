@@ -0,0 +1,43 @@
+use super::Completions;
+use crate::{
+    context::{CompletionContext, ImmediateLocation},
+    item::{CompletionItem, CompletionItemKind},
+};
+
+const ITEM_KEYWORDS: &[&str] = &[
+    "fn", "struct", "var", "override", "const", "alias", "enable",
+];
+
+const STATEMENT_KEYWORDS: &[&str] = &[
+    "let",
+    "var",
+    "const",
+    "if",
+    "for",
+    "while",
+    "loop",
+    "switch",
+    "return",
+    "break",
+    "continue",
+    "continuing",
+    "discard",
+    "const_assert",
+];
+
+pub(crate) fn complete_keywords(
+    accumulator: &mut Completions,
+    ctx: &CompletionContext,
+) -> Option<()> {
+    let keywords = match ctx.completion_location {
+        Some(ImmediateLocation::ItemList) => ITEM_KEYWORDS,
+        Some(ImmediateLocation::StatementList) => STATEMENT_KEYWORDS,
+        _ => return None,
+    };
+
+    accumulator.add_all(keywords.iter().map(|keyword| {
+        CompletionItem::new(CompletionItemKind::Keyword, ctx.source_range(), *keyword).build()
+    }));
+
+    Some(())
+}
@@ -0,0 +1,212 @@
+use base_db::TextRange;
+use hir_ty::ty::{ScalarType, TyKind, Type};
+use syntax::{AstNode, ast};
+use text_edit::TextEdit;
+
+use super::Completions;
+use crate::{
+    config::SnippetCap,
+    context::{CompletionContext, ImmediateLocation},
+    item::{CompletionItem, CompletionItemKind, CompletionRelevance},
+    snippet::SnippetScope,
+};
+
+const CONVERSIONS: &[(&str, ScalarType)] = &[
+    ("f32", ScalarType::F32),
+    ("u32", ScalarType::U32),
+    ("i32", ScalarType::I32),
+];
+
+/// Completes templates like `expression.if` which rewrite the expression before the dot.
+pub(crate) fn complete_postfix(
+    accumulator: &mut Completions,
+    ctx: &CompletionContext,
+) -> Option<()> {
+    if !ctx.config.enable_postfix_completions {
+        return None;
+    }
+    let cap = ctx.config.snippet_cap?;
+    let field_expression = match &ctx.completion_location {
+        Some(ImmediateLocation::FieldAccess { expression }) => expression,
+        _ => return None,
+    };
+    let receiver = field_expression.expression()?;
+    let r#type = ctx
+        .sema
+        .analyze(ctx.container?)
+        .type_of_expression(&receiver)?
+        .unref(ctx.db);
+    if r#type.is_err(ctx.db) {
+        return None;
+    }
+
+    let receiver_text = receiver.syntax().text().to_string();
+    let receiver_text = receiver_text.trim();
+    let is_statement = field_expression
+        .syntax()
+        .parent()
+        .is_some_and(|parent| ast::ExpressionStatement::can_cast(parent.kind()));
+
+    // The text typed after the dot, which the client filters the templates with.
+    let period = field_expression.period_token()?;
+    let source_range = TextRange::new(period.text_range().end(), ctx.position.offset);
+    let typed = ctx.file.syntax().text().slice(source_range).to_string();
+    let postfix = Postfix {
+        cap,
+        source_range,
+        replace_range: TextRange::new(receiver.syntax().text_range().start(), source_range.end()),
+        typed: typed.trim(),
+    };
+
+    let kind = r#type.kind(ctx.db);
+    if is_statement {
+        if matches!(kind, TyKind::Scalar(ScalarType::Bool)) {
+            postfix.add(
+                accumulator,
+                "if",
+                "if expression {}",
+                &format!("if {receiver_text} {{\n\t$0\n}}"),
+            );
+        }
+        postfix.add(
+            accumulator,
+            "let",
+            "let name = expression;",
+            &format!("let ${{1:name}} = {receiver_text};$0"),
+        );
+        postfix.add(
+            accumulator,
+            "var",
+            "var name = expression;",
+            &format!("var ${{1:name}} = {receiver_text};$0"),
+        );
+        let return_type = ctx.db.infer(ctx.container?).return_type;
+        if return_type == Some(r#type) {
+            postfix.add(
+                accumulator,
+                "return",
+                "return expression;",
+                &format!("return {receiver_text};$0"),
+            );
+        }
+    }
+
+    if matches!(kind, TyKind::Scalar(ScalarType::Bool)) {
+        postfix.add(
+            accumulator,
+            "not",
+            "!expression",
+            &format!("!{receiver_text}$0"),
+        );
+    }
+
+    if let TyKind::Vector(vector) = &kind {
+        if matches!(vector.inner.kind(ctx.db), TyKind::Scalar(ScalarType::F32)) {
+            postfix.add(
+                accumulator,
+                "normalize",
+                "normalize(expression)",
+                &format!("normalize({receiver_text})$0"),
+            );
+            postfix.add(
+                accumulator,
+                "length",
+                "length(expression)",
+                &format!("length({receiver_text})$0"),
+            );
+        }
+    }
+
+    if let TyKind::Scalar(scalar) = &kind {
+        for (name, target) in CONVERSIONS {
+            if scalar != target {
+                postfix.add(
+                    accumulator,
+                    name,
+                    &format!("{name}(expression)"),
+                    &format!("{name}({receiver_text})$0"),
+                );
+            }
+        }
+    }
+
+    if let Some(target) = bitcast_target(ctx, r#type) {
+        postfix.add(
+            accumulator,
+            "bitcast",
+            "bitcast<T>(expression)",
+            &format!("bitcast<${{1:{target}}}>({receiver_text})$0"),
+        );
+    }
+
+    for (trigger, snippet) in ctx.config.postfix_snippets() {
+        let in_scope = match snippet.scope {
+            SnippetScope::Expression => true,
+            SnippetScope::Statement => is_statement,
+            SnippetScope::Item => false,
+        };
+        if in_scope {
+            postfix.add(
+                accumulator,
+                trigger,
+                snippet.description.as_deref().unwrap_or_default(),
+                &snippet.postfix_snippet(receiver_text),
+            );
+        }
+    }
+
+    Some(())
+}
+
+struct Postfix<'a> {
+    cap: SnippetCap,
+    source_range: TextRange,
+    replace_range: TextRange,
+    typed: &'a str,
+}
+
+impl Postfix<'_> {
+    fn add(
+        &self,
+        accumulator: &mut Completions,
+        label: &str,
+        detail: &str,
+        snippet: &str,
+    ) {
+        let mut item = CompletionItem::new(CompletionItemKind::Snippet, self.source_range, label);
+        item.insert_snippet(self.cap, snippet)
+            .text_edit(TextEdit::replace(self.replace_range, snippet.to_owned()))
+            .set_relevance(CompletionRelevance {
+                exact_postfix_snippet_match: self.typed == label,
+                ..CompletionRelevance::default()
+            });
+        if !detail.is_empty() {
+            item.detail(detail);
+        }
+        item.add_to(accumulator);
+    }
+}
+
+/// The type to suggest as the target of a `bitcast` of `r#type`, if it can be bitcast.
+fn bitcast_target(
+    ctx: &CompletionContext,
+    r#type: Type,
+) -> Option<String> {
+    let (scalar, size) = match r#type.kind(ctx.db) {
+        TyKind::Scalar(scalar) => (scalar, None),
+        TyKind::Vector(vector) => match vector.inner.kind(ctx.db) {
+            TyKind::Scalar(scalar) => (scalar, Some(vector.size.as_u8())),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    let target = match scalar {
+        ScalarType::F32 => "u32",
+        ScalarType::I32 | ScalarType::U32 => "f32",
+        ScalarType::Bool => return None,
+    };
+    Some(match size {
+        Some(size) => format!("vec{size}<{target}>"),
+        None => target.to_owned(),
+    })
+}
@@ -0,0 +1,120 @@
+use super::Completions;
+use crate::{
+    config::SnippetCap,
+    context::{CompletionContext, ImmediateLocation},
+    item::{CompletionItem, CompletionItemKind},
+    snippet::SnippetScope,
+};
+
+const ITEM_SNIPPETS: &[(&str, &str, &str)] = &[
+    (
+        "compute",
+        "compute shader entry point",
+        "@compute @workgroup_size(${1:64})
+fn ${2:main}(@builtin(global_invocation_id) ${3:id}: vec3<u32>) {
+\t$0
+}",
+    ),
+    (
+        "vertex",
+        "vertex shader entry point",
+        "struct ${1:VertexInput} {
+\t@location(0) position: vec3<f32>,
+}
+
+struct ${2:VertexOutput} {
+\t@builtin(position) position: vec4<f32>,
+}
+
+@vertex
+fn ${3:vs_main}(in: ${1:VertexInput}) -> ${2:VertexOutput} {
+\tvar out: ${2:VertexOutput};
+\tout.position = vec4<f32>(in.position, 1.0);$0
+\treturn out;
+}",
+    ),
+    (
+        "fragment",
+        "fragment shader entry point",
+        "struct ${1:FragmentOutput} {
+\t@location(0) color: vec4<f32>,
+}
+
+@fragment
+fn ${2:fs_main}(in: ${3:VertexOutput}) -> ${1:FragmentOutput} {
+\tvar out: ${1:FragmentOutput};
+\tout.color = vec4<f32>(1.0);$0
+\treturn out;
+}",
+    ),
+];
+
+const STATEMENT_SNIPPETS: &[(&str, &str, &str)] = &[
+    (
+        "for",
+        "for (var i = 0u; i < count; i++) {}",
+        "for (var ${1:i} = 0u; ${1:i} < ${2:count}; ${1:i}++) {
+\t$0
+}",
+    ),
+    (
+        "switch",
+        "switch selector { case 0: {} default: {} }",
+        "switch ${1:selector} {
+\tcase ${2:0}: {
+\t\t$3
+\t}
+\tdefault: {
+\t\t$0
+\t}
+}",
+    ),
+];
+
+pub(crate) fn complete_snippets(
+    accumulator: &mut Completions,
+    ctx: &CompletionContext,
+) -> Option<()> {
+    let cap = ctx.config.snippet_cap?;
+    let (builtin, scopes): (&[(&str, &str, &str)], &[SnippetScope]) = match ctx.completion_location
+    {
+        Some(ImmediateLocation::ItemList) => (ITEM_SNIPPETS, &[SnippetScope::Item]),
+        Some(ImmediateLocation::StatementList) => (
+            STATEMENT_SNIPPETS,
+            &[SnippetScope::Statement, SnippetScope::Expression],
+        ),
+        Some(ImmediateLocation::InsideStatement) => (&[], &[SnippetScope::Expression]),
+        _ => return None,
+    };
+
+    for &(label, detail, snippet) in builtin {
+        add_snippet(accumulator, ctx, cap, label, Some(detail), snippet);
+    }
+    for (trigger, snippet) in ctx.config.prefix_snippets() {
+        if scopes.contains(&snippet.scope) {
+            add_snippet(
+                accumulator,
+                ctx,
+                cap,
+                trigger,
+                snippet.description.as_deref(),
+                snippet.snippet(),
+            );
+        }
+    }
+
+    Some(())
+}
+
+fn add_snippet(
+    accumulator: &mut Completions,
+    ctx: &CompletionContext,
+    cap: SnippetCap,
+    label: &str,
+    detail: Option<&str>,
+    snippet: &str,
+) {
+    let mut item = CompletionItem::new(CompletionItemKind::Snippet, ctx.source_range(), label);
+    item.insert_snippet(cap, snippet).set_detail(detail);
+    item.add_to(accumulator);
+}
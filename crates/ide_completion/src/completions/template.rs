@@ -0,0 +1,43 @@
+use hir_ty::ty::TexelFormat;
+
+use super::Completions;
+use crate::{
+    context::{CompletionContext, ImmediateLocation},
+    item::{CompletionItem, CompletionItemKind},
+};
+
+const ADDRESS_SPACES: &[&str] = &[
+    "function",
+    "private",
+    "workgroup",
+    "uniform",
+    "storage",
+    "push_constant",
+];
+
+const ACCESS_MODES: &[&str] = &["read", "write", "read_write"];
+
+/// Completes the enumerants in `var<...>`, `ptr<...>` and storage texture types.
+pub(crate) fn complete_template_arguments(
+    accumulator: &mut Completions,
+    ctx: &CompletionContext,
+) -> Option<()> {
+    let names: Vec<String> = match ctx.completion_location {
+        Some(ImmediateLocation::AddressSpace) => {
+            ADDRESS_SPACES.iter().map(ToString::to_string).collect()
+        },
+        Some(ImmediateLocation::AccessMode) => {
+            ACCESS_MODES.iter().map(ToString::to_string).collect()
+        },
+        Some(ImmediateLocation::TexelFormat) => {
+            TexelFormat::ALL.iter().map(ToString::to_string).collect()
+        },
+        _ => return None,
+    };
+
+    accumulator.add_all(names.into_iter().map(|name| {
+        CompletionItem::new(CompletionItemKind::EnumMember, ctx.source_range(), name).build()
+    }));
+
+    Some(())
+}
@@ -0,0 +1,106 @@
+use hir_def::{
+    module_data::{ModuleItem, Name, pretty::pretty_module_item},
+    resolver::ScopeDef,
+};
+
+use super::Completions;
+use crate::{
+    context::{CompletionContext, ImmediateLocation},
+    item::{CompletionItem, CompletionItemKind},
+};
+
+const BUILTIN_TYPES: &[&str] = &[
+    "bool",
+    "i32",
+    "u32",
+    "f32",
+    "vec2",
+    "vec3",
+    "vec4",
+    "vec2i",
+    "vec3i",
+    "vec4i",
+    "vec2u",
+    "vec3u",
+    "vec4u",
+    "vec2f",
+    "vec3f",
+    "vec4f",
+    "mat2x2",
+    "mat2x3",
+    "mat2x4",
+    "mat3x2",
+    "mat3x3",
+    "mat3x4",
+    "mat4x2",
+    "mat4x3",
+    "mat4x4",
+    "array",
+    "binding_array",
+    "atomic",
+    "ptr",
+    "sampler",
+    "sampler_comparison",
+    "texture_1d",
+    "texture_2d",
+    "texture_2d_array",
+    "texture_3d",
+    "texture_cube",
+    "texture_cube_array",
+    "texture_multisampled_2d",
+    "texture_external",
+    "texture_storage_1d",
+    "texture_storage_2d",
+    "texture_storage_2d_array",
+    "texture_storage_3d",
+    "texture_depth_2d",
+    "texture_depth_2d_array",
+    "texture_depth_cube",
+    "texture_depth_cube_array",
+    "texture_depth_multisampled_2d",
+];
+
+pub(crate) fn complete_types(
+    accumulator: &mut Completions,
+    ctx: &CompletionContext,
+) -> Option<()> {
+    match ctx.completion_location {
+        Some(ImmediateLocation::Type) => {},
+        _ => return None,
+    }
+
+    ctx.resolver.process_type_names(|name, item| {
+        if name == Name::missing() {
+            return;
+        }
+        let ScopeDef::ModuleItem(file_id, item) = item else {
+            return;
+        };
+        let completion = match item {
+            ModuleItem::TypeAlias(_) => {
+                let module_info = ctx.db.module_info(file_id);
+                let detail = pretty_module_item(&item, &module_info, ctx.db.upcast());
+                let mut completion = CompletionItem::new(
+                    CompletionItemKind::TypeAlias,
+                    ctx.source_range(),
+                    name.as_str(),
+                );
+                completion.detail(detail);
+                completion
+            },
+            // The pretty-printed fields would not fit into a single line.
+            _ => CompletionItem::new(
+                CompletionItemKind::Struct,
+                ctx.source_range(),
+                name.as_str(),
+            ),
+        };
+        completion.add_to(accumulator);
+    });
+
+    accumulator.add_all(BUILTIN_TYPES.iter().map(|name| {
+        CompletionItem::new(CompletionItemKind::BuiltinType, ctx.source_range(), *name).build()
+    }));
+
+    Some(())
+}
@@ -5,12 +5,9 @@
 //! completions if we are allowed to.
 
 // use hir::ImportPathConfig;
-// use ide_db::{imports::insert_use::InsertUseConfig, SnippetCap};
+// use ide_db::imports::insert_use::InsertUseConfig;
 
-use crate::{
-    // snippet::Snippet,
-    CompletionFieldsToResolve,
-};
+use crate::{CompletionFieldsToResolve, snippet::Snippet};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct CompletionConfig<'a> {
@@ -25,18 +22,34 @@ pub struct CompletionConfig<'a> {
     pub full_function_signatures: bool,
     pub callable: Option<CallableSnippets>,
     pub add_semicolon_to_unit: bool,
-    // pub snippet_cap: Option<SnippetCap>,
+    pub snippet_cap: Option<SnippetCap>,
     // pub insert_use: InsertUseConfig,
     pub prefer_no_std: bool,
     pub prefer_prelude: bool,
     pub prefer_absolute: bool,
-    // pub snippets: Vec<Snippet>,
+    pub snippets: Vec<Snippet>,
     pub limit: Option<usize>,
     pub fields_to_resolve: CompletionFieldsToResolve,
     pub exclude_flyimport: Vec<(String, AutoImportExclusionType)>,
     pub exclude_traits: &'a [String],
 }
 
+/// Proof that the client supports snippets in completion items.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SnippetCap {
+    _private: (),
+}
+
+impl SnippetCap {
+    pub const fn new(allow_snippets: bool) -> Option<SnippetCap> {
+        if allow_snippets {
+            Some(SnippetCap { _private: () })
+        } else {
+            None
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub enum AutoImportExclusionType {
     Always,
@@ -50,17 +63,23 @@ pub enum CallableSnippets {
 }
 
 impl CompletionConfig<'_> {
-    // pub fn postfix_snippets(&self) -> impl Iterator<Item = (&str, &Snippet)> {
-    //     self.snippets
-    //         .iter()
-    //         .flat_map(|snip| snip.postfix_triggers.iter().map(move |trigger| (&**trigger, snip)))
-    // }
+    pub fn postfix_snippets(&self) -> impl Iterator<Item = (&str, &Snippet)> {
+        self.snippets.iter().flat_map(|snippet| {
+            snippet
+                .postfix_triggers
+                .iter()
+                .map(move |trigger| (&**trigger, snippet))
+        })
+    }
 
-    // pub fn prefix_snippets(&self) -> impl Iterator<Item = (&str, &Snippet)> {
-    //     self.snippets
-    //         .iter()
-    //         .flat_map(|snip| snip.prefix_triggers.iter().map(move |trigger| (&**trigger, snip)))
-    // }
+    pub fn prefix_snippets(&self) -> impl Iterator<Item = (&str, &Snippet)> {
+        self.snippets.iter().flat_map(|snippet| {
+            snippet
+                .prefix_triggers
+                .iter()
+                .map(move |trigger| (&**trigger, snippet))
+        })
+    }
 
     // pub fn import_path_config(&self, allow_unstable: bool) -> ImportPathConfig {
     //     ImportPathConfig {
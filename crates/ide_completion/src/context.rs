@@ -1,11 +1,17 @@
-use base_db::{FilePosition, TextRange};
+use base_db::{FilePosition, TextRange, TextSize};
 use either::Either;
 use hir::{HirDatabase, Semantics};
 use hir_def::{HirFileId, db::DefinitionWithBodyId, resolver::Resolver};
+use hir_ty::{
+    infer::{TypeExpectation, TypeExpectationInner},
+    ty::Type,
+};
 use rowan::NodeOrToken;
 use syntax::{AstNode, Direction, SyntaxKind, SyntaxToken, ast};
 
-use crate::{config::CompletionConfig, patterns::determine_location};
+use crate::{
+    config::CompletionConfig, item::CompletionRelevanceTypeMatch, patterns::determine_location,
+};
 
 type ExprOrStatement = Either<ast::Expression, ast::Statement>;
 
@@ -15,12 +21,16 @@ pub(crate) struct CompletionContext<'a> {
     pub sema: Semantics<'a>,
     pub file_id: HirFileId,
     pub db: &'a dyn HirDatabase,
+    pub config: &'a CompletionConfig<'a>,
     pub position: FilePosition,
     pub token: SyntaxToken,
     pub file: ast::SourceFile,
     pub container: Option<DefinitionWithBodyId>,
     pub completion_location: Option<ImmediateLocation>,
     pub resolver: Resolver,
+    /// The type expected for the expression being typed, e.g. the parameter type
+    /// when typing an argument.
+    pub expected_type: Option<TypeExpectation>,
 }
 
 impl<'a> CompletionContext<'a> {
@@ -64,16 +74,21 @@ impl<'a> CompletionContext<'a> {
             }
         }
 
+        let expected_type =
+            container.and_then(|def| expected_type(&sema, def, &token, position.offset));
+
         let ctx = Self {
             file_id,
             sema,
             db,
+            config,
             position,
             token,
             file,
             container,
             completion_location,
             resolver,
+            expected_type,
         };
         Some(ctx)
     }
@@ -88,13 +103,108 @@ impl<'a> CompletionContext<'a> {
             TextRange::empty(self.position.offset)
         }
     }
+
+    /// Whether a completion of type `r#type` fits the expected type.
+    pub(crate) fn type_match(
+        &self,
+        r#type: Type,
+    ) -> Option<CompletionRelevanceTypeMatch> {
+        let expected = self.expected_type.as_ref()?;
+        let r#type = r#type.unref(self.db);
+        if r#type.is_err(self.db) || !expected.matches(self.db, r#type) {
+            return None;
+        }
+        match expected {
+            TypeExpectation::Type(TypeExpectationInner::Exact(_)) => {
+                Some(CompletionRelevanceTypeMatch::Exact)
+            },
+            _ => Some(CompletionRelevanceTypeMatch::CouldUnify),
+        }
+    }
+}
+
+/// The type expected for the expression being typed, or for the one about to be typed right
+/// after `(`, `,`, `=` or `return`.
+fn expected_type(
+    sema: &Semantics,
+    def: DefinitionWithBodyId,
+    token: &SyntaxToken,
+    offset: TextSize,
+) -> Option<TypeExpectation> {
+    let analyzed = sema.analyze(def);
+    if token.kind() == SyntaxKind::Identifier {
+        let expression = token.parent_ancestors().find_map(ast::Expression::cast)?;
+        return analyzed.expected_type_of_expression(&expression);
+    }
+    let previous = if token.kind().is_trivia() {
+        std::iter::successors(token.prev_token(), SyntaxToken::prev_token)
+            .find(|token| !token.kind().is_trivia())?
+    } else {
+        token.clone()
+    };
+    let parent = previous.parent()?;
+    match previous.kind() {
+        SyntaxKind::ParenthesisLeft | SyntaxKind::Comma
+            if parent.kind() == SyntaxKind::FunctionParameterList =>
+        {
+            let call = ast::Expression::cast(parent.parent()?)?;
+            let index = parent
+                .children_with_tokens()
+                .filter(|element| {
+                    element.kind() == SyntaxKind::Comma && element.text_range().end() <= offset
+                })
+                .count();
+            analyzed.expected_type_of_argument(&call, index)
+        },
+        SyntaxKind::Equal => {
+            let r#type = if let Some(statement) = ast::VariableStatement::cast(parent.clone()) {
+                statement.ty()?;
+                analyzed.type_of_binding(&statement.binding()?)?
+            } else {
+                let statement = ast::AssignmentStatement::cast(parent)?;
+                analyzed.type_of_expression(&statement.left_side()?)?
+            };
+            exact_type(sema.db, r#type.unref(sema.db))
+        },
+        SyntaxKind::Return if parent.kind() == SyntaxKind::ReturnStatement => {
+            exact_type(sema.db, analyzed.infer.return_type?)
+        },
+        _ => None,
+    }
+}
+
+/// Expects exactly `r#type`, unless it is an error, which would match every completion.
+fn exact_type(
+    db: &dyn HirDatabase,
+    r#type: Type,
+) -> Option<TypeExpectation> {
+    (!r#type.is_err(db)).then_some(TypeExpectation::Type(TypeExpectationInner::Exact(r#type)))
 }
 
 #[derive(Debug)]
 pub(crate) enum ImmediateLocation {
+    /// Where a module-scope declaration can start.
     ItemList,
+    /// Where a statement can start.
     StatementList,
     InsideStatement,
     Import,
-    FieldAccess { expression: ast::FieldExpression },
+    FieldAccess {
+        expression: ast::FieldExpression,
+    },
+    /// Where a type is expected, e.g. after the colon of a declaration.
+    Type,
+    /// Right after `@`.
+    AttributeName,
+    /// Inside the parentheses of an attribute, with the index of the argument.
+    AttributeArgument {
+        attribute: String,
+        index: usize,
+    },
+    /// The address space of `var<...>` or `ptr<...>`.
+    AddressSpace,
+    /// The access mode of `var<...>`, `ptr<...>` or a storage texture.
+    AccessMode,
+    /// The first template argument of a storage texture.
+    TexelFormat,
 }
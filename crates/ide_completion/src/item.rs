@@ -2,6 +2,8 @@ use base_db::TextRange;
 use stdx::never;
 use text_edit::TextEdit;
 
+use crate::config::SnippetCap;
+
 /// `CompletionItem` describes a single completion variant in the editor pop-up.
 /// It is basically a POD with various properties. To construct a
 /// `CompletionItem`, use `new` method and the `Builder` struct.
@@ -62,6 +64,9 @@ pub enum CompletionItemKind {
     Struct,
     Module,
     TypeAlias,
+    BuiltinType,
+    Attribute,
+    EnumMember,
 }
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
@@ -129,7 +134,7 @@ pub enum CompletionRelevanceTypeMatch {
 }
 
 impl CompletionRelevance {
-    /// Provides a relevance score. Higher values are more relevant.
+    /// Provides a relevance score. Lower values are more relevant.
     ///
     /// The absolute value of the relevance score is not meaningful, for
     /// example a value of 0 does not mean "not relevant", rather
@@ -350,15 +355,16 @@ impl Builder {
         self
     }
 
-    // pub(crate) fn insert_snippet(
-    //     &mut self,
-    //     cap: SnippetCap,
-    //     snippet: impl Into<String>,
-    // ) -> &mut Builder {
-    //     let _ = cap;
-    //     self.is_snippet = true;
-    //     self.insert_text(snippet)
-    // }
+    pub(crate) fn insert_snippet(
+        &mut self,
+        cap: SnippetCap,
+        snippet: impl Into<String>,
+    ) -> &mut Builder {
+        let _ = cap;
+        self.is_snippet = true;
+        self.insert_text(snippet)
+    }
+
     pub(crate) fn text_edit(
         &mut self,
         edit: TextEdit,
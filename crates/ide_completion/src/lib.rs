@@ -6,8 +6,8 @@ mod context;
 pub mod item;
 mod patterns;
 // mod render;
+mod snippet;
 
-// mod snippet;
 // #[cfg(test)]
 // mod tests;
 
@@ -18,8 +18,9 @@ use rustc_hash::FxHashSet;
 use crate::{completions::Completions, context::CompletionContext};
 
 pub use crate::{
-    config::{AutoImportExclusionType, CallableSnippets, CompletionConfig},
+    config::{AutoImportExclusionType, CallableSnippets, CompletionConfig, SnippetCap},
     item::{CompletionItem, CompletionItemKind, CompletionRelevance, CompletionRelevanceTypeMatch},
+    snippet::{Snippet, SnippetScope},
 };
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -261,7 +262,14 @@ pub fn completions2(
     let ctx = CompletionContext::new(db, position, config)?;
     completions::import::complete_import(&mut accumulator, &ctx);
     completions::dot::complete_dot(&mut accumulator, &ctx);
+    completions::postfix::complete_postfix(&mut accumulator, &ctx);
     completions::expression::complete_names_in_scope(&mut accumulator, &ctx);
+    completions::auto_import::complete_auto_import(&mut accumulator, &ctx);
+    completions::keyword::complete_keywords(&mut accumulator, &ctx);
+    completions::snippet::complete_snippets(&mut accumulator, &ctx);
+    completions::r#type::complete_types(&mut accumulator, &ctx);
+    completions::attribute::complete_attributes(&mut accumulator, &ctx);
+    completions::template::complete_template_arguments(&mut accumulator, &ctx);
 
     Some(accumulator.into())
 }
@@ -1,29 +1,216 @@
 use base_db::TextSize;
 use hir::Semantics;
-use syntax::{AstNode, SyntaxKind, SyntaxNode, SyntaxToken, ast};
+use syntax::{AstNode, Direction, SyntaxKind, SyntaxNode, SyntaxToken, ast};
 
 use crate::context::ImmediateLocation;
 
 pub(crate) fn determine_location(
     _sema: &Semantics,
     _file: &SyntaxNode,
-    _offset: TextSize,
+    offset: TextSize,
     token: SyntaxToken,
 ) -> Option<ImmediateLocation> {
     let node = token.parent()?;
-    let parent = node.parent()?;
+    let parent = node.parent();
 
     if let Some(expression) = ast::FieldExpression::cast(node.clone()) {
         Some(ImmediateLocation::FieldAccess { expression })
-    } else if let Some(expression) = ast::FieldExpression::cast(parent.clone()) {
+    } else if let Some(expression) = parent.clone().and_then(ast::FieldExpression::cast) {
         Some(ImmediateLocation::FieldAccess { expression })
-    } else if node.kind() == SyntaxKind::SourceFile {
+    } else if let Some(expression) = keyword_after_period(&node) {
+        Some(ImmediateLocation::FieldAccess { expression })
+    } else if let Some(block) = block_before(&token) {
+        // Trailing whitespace is part of the block it follows.
+        if block
+            .ancestors()
+            .skip(1)
+            .any(|node| node.kind() == SyntaxKind::CompoundStatement)
+        {
+            Some(ImmediateLocation::StatementList)
+        } else {
+            Some(ImmediateLocation::ItemList)
+        }
+    } else if let Some(location) = attribute_location(&token, offset) {
+        Some(location)
+    } else if let Some(location) = template_location(&token, offset) {
+        Some(location)
+    } else if node
+        .ancestors()
+        .take(2)
+        .any(|node| node.kind() == SyntaxKind::PathType)
+        || is_type_start(&token)
+    {
+        Some(ImmediateLocation::Type)
+    } else if is_item_start(&token, &node) {
         Some(ImmediateLocation::ItemList)
-    } else if node.kind() == SyntaxKind::Import || parent.kind() == SyntaxKind::Import {
+    } else if node.kind() == SyntaxKind::Import
+        || parent.is_some_and(|parent| parent.kind() == SyntaxKind::Import)
+    {
         Some(ImmediateLocation::Import)
+    } else if is_statement_start(&token, &node) {
+        Some(ImmediateLocation::StatementList)
     } else if node.ancestors().find_map(ast::Statement::cast).is_some() {
         Some(ImmediateLocation::InsideStatement)
     } else {
         None
     }
 }
+
+/// A keyword after the period, as in `condition.if`, is an error inside the name reference.
+fn keyword_after_period(node: &SyntaxNode) -> Option<ast::FieldExpression> {
+    if node.kind() != SyntaxKind::Error {
+        return None;
+    }
+    let name_reference = node.parent()?;
+    if name_reference.kind() != SyntaxKind::NameReference {
+        return None;
+    }
+    ast::FieldExpression::cast(name_reference.parent()?)
+}
+
+/// The block whose closing brace `token` follows, if `token` is trivia.
+fn block_before(token: &SyntaxToken) -> Option<SyntaxNode> {
+    if !token.kind().is_trivia() {
+        return None;
+    }
+    let previous = token
+        .siblings_with_tokens(Direction::Prev)
+        .skip(1)
+        .find(|element| !element.kind().is_trivia())?;
+    if previous.kind() == SyntaxKind::BraceRight {
+        token.parent()
+    } else {
+        None
+    }
+}
+
+fn attribute_location(
+    token: &SyntaxToken,
+    offset: TextSize,
+) -> Option<ImmediateLocation> {
+    if token.kind() == SyntaxKind::AttributeOperator {
+        return Some(ImmediateLocation::AttributeName);
+    }
+    let attribute = token
+        .parent_ancestors()
+        .find(|node| node.kind() == SyntaxKind::Attribute)?;
+    let name = attribute.first_token()?;
+    match token
+        .parent_ancestors()
+        .find(|node| node.kind() == SyntaxKind::AttributeParameters)
+    {
+        Some(parameters) if token.kind() != SyntaxKind::ParenthesisRight => {
+            Some(ImmediateLocation::AttributeArgument {
+                attribute: name.text().to_owned(),
+                index: argument_index(&parameters, offset),
+            })
+        },
+        Some(_) => None,
+        None => (name == *token).then_some(ImmediateLocation::AttributeName),
+    }
+}
+
+/// Locations inside `var<...>` and the template arguments of types.
+fn template_location(
+    token: &SyntaxToken,
+    offset: TextSize,
+) -> Option<ImmediateLocation> {
+    if token.kind() == SyntaxKind::GreaterThan {
+        return None;
+    }
+    let list = token.parent_ancestors().find(|node| {
+        matches!(
+            node.kind(),
+            SyntaxKind::VariableQualifier | SyntaxKind::GenericArgumentList
+        )
+    })?;
+    let index = argument_index(&list, offset);
+    let kind = if list.kind() == SyntaxKind::VariableQualifier {
+        SyntaxKind::VariableQualifier
+    } else {
+        list.parent()?.kind()
+    };
+    Some(match (kind, index) {
+        (SyntaxKind::VariableQualifier | SyntaxKind::Pointer, 0) => ImmediateLocation::AddressSpace,
+        (SyntaxKind::VariableQualifier, _) | (SyntaxKind::Pointer, 2) => {
+            ImmediateLocation::AccessMode
+        },
+        (
+            SyntaxKind::TextureStorage1d
+            | SyntaxKind::TextureStorage2d
+            | SyntaxKind::TextureStorage2dArray
+            | SyntaxKind::TextureStorage3d,
+            0,
+        ) => ImmediateLocation::TexelFormat,
+        (
+            SyntaxKind::TextureStorage1d
+            | SyntaxKind::TextureStorage2d
+            | SyntaxKind::TextureStorage2dArray
+            | SyntaxKind::TextureStorage3d,
+            _,
+        ) => ImmediateLocation::AccessMode,
+        _ => ImmediateLocation::Type,
+    })
+}
+
+/// The number of commas in `list` before `offset`.
+fn argument_index(
+    list: &SyntaxNode,
+    offset: TextSize,
+) -> usize {
+    list.children_with_tokens()
+        .filter(|element| {
+            element.kind() == SyntaxKind::Comma && element.text_range().end() <= offset
+        })
+        .count()
+}
+
+/// Right after the colon of a declaration or the arrow of a return type, before anything is typed.
+fn is_type_start(token: &SyntaxToken) -> bool {
+    let previous = if token.kind().is_trivia() {
+        token
+            .siblings_with_tokens(Direction::Prev)
+            .skip(1)
+            .find(|element| !element.kind().is_trivia())
+            .and_then(|element| element.into_token())
+    } else {
+        Some(token.clone())
+    };
+    previous.is_some_and(|previous| {
+        matches!(previous.kind(), SyntaxKind::Colon | SyntaxKind::Arrow)
+            && previous
+                .parent()
+                .is_some_and(|parent| parent.kind() != SyntaxKind::SwitchBodyCase)
+    })
+}
+
+/// An unfinished declaration at module scope is parsed into error nodes.
+fn is_item_start(
+    token: &SyntaxToken,
+    node: &SyntaxNode,
+) -> bool {
+    match node.kind() {
+        SyntaxKind::SourceFile => true,
+        SyntaxKind::Error => {
+            node.ancestors()
+                .take_while(|node| node.kind() != SyntaxKind::SourceFile)
+                .all(|node| node.kind() == SyntaxKind::Error)
+                && !token.kind().is_trivia()
+        },
+        _ => false,
+    }
+}
+
+/// A partially typed statement is parsed as an expression statement.
+fn is_statement_start(
+    token: &SyntaxToken,
+    node: &SyntaxNode,
+) -> bool {
+    if node.kind() == SyntaxKind::CompoundStatement {
+        return token.kind().is_trivia()
+            || matches!(token.kind(), SyntaxKind::BraceLeft | SyntaxKind::Semicolon);
+    }
+    node.ancestors()
+        .find_map(ast::ExpressionStatement::cast)
+        .is_some_and(|statement| statement.syntax().first_token().as_ref() == Some(token))
+}
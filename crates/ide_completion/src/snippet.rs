@@ -0,0 +1,80 @@
+//! User defined snippets.
+//!
+//! The `body` of a snippet uses the LSP snippet syntax, e.g. `${1:name}` for a
+//! placeholder and `$0` for the final cursor position. Postfix snippets refer to
+//! the expression before the dot as `${receiver}`.
+//!
+//! A snippet with a prefix trigger of `workgroup` could look like this in the
+//! configuration:
+//!
+//! ```json
+//! {
+//!     "workgroup var": {
+//!         "prefix": "workgroup",
+//!         "body": "var<workgroup> ${1:name}: ${2:array<f32, 64>};",
+//!         "description": "A workgroup variable",
+//!         "scope": "item"
+//!     }
+//! }
+//! ```
+
+/// Where a snippet can be completed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SnippetScope {
+    /// At module scope.
+    Item,
+    /// At the start of a statement.
+    Statement,
+    /// Anywhere an expression is expected.
+    Expression,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Snippet {
+    pub prefix_triggers: Box<[Box<str>]>,
+    pub postfix_triggers: Box<[Box<str>]>,
+    pub description: Option<Box<str>>,
+    pub scope: SnippetScope,
+    snippet: String,
+}
+
+impl Snippet {
+    /// Returns `None` if the snippet has no trigger or no body.
+    pub fn new(
+        prefix_triggers: &[String],
+        postfix_triggers: &[String],
+        snippet: &[String],
+        description: &str,
+        scope: SnippetScope,
+    ) -> Option<Self> {
+        if (prefix_triggers.is_empty() && postfix_triggers.is_empty()) || snippet.is_empty() {
+            return None;
+        }
+        let description = (!description.is_empty()).then(|| description.into());
+        Some(Snippet {
+            prefix_triggers: prefix_triggers
+                .iter()
+                .map(|trigger| trigger.as_str().into())
+                .collect(),
+            postfix_triggers: postfix_triggers
+                .iter()
+                .map(|trigger| trigger.as_str().into())
+                .collect(),
+            description,
+            scope,
+            snippet: snippet.join("\n"),
+        })
+    }
+
+    pub fn snippet(&self) -> &str {
+        &self.snippet
+    }
+
+    /// The snippet with `${receiver}` replaced by `receiver`.
+    pub fn postfix_snippet(
+        &self,
+        receiver: &str,
+    ) -> String {
+        self.snippet.replace("${receiver}", receiver)
+    }
+}
@@ -31,6 +31,8 @@ fn item(parser: &mut Parser) {
     attribute_list_opt(parser);
     if parser.at(SyntaxKind::UnofficialPreprocessorImport) {
         import(parser, marker);
+    } else if parser.at(SyntaxKind::ImportKeyword) && parser.edition().at_least_wesl_0_0_1() {
+        wesl_import(parser, marker);
     } else if parser.at(SyntaxKind::Fn) {
         function(parser, marker);
     } else if parser.at(SyntaxKind::Struct) {
@@ -70,16 +72,61 @@ fn import(
         parser.bump();
         marker.complete(parser, SyntaxKind::ImportPath);
     } else if parser.at(SyntaxKind::Identifier) {
-        let marker = parser.start();
-        while parser.at(SyntaxKind::Identifier) || parser.at(SyntaxKind::ColonColon) {
-            parser.bump();
-        }
-        marker.complete(parser, SyntaxKind::ImportCustom);
+        import_custom(parser);
     }
 
     marker.complete(parser, SyntaxKind::Import);
 }
 
+/// A WESL `import package::module::{item};` statement.
+fn wesl_import(
+    parser: &mut Parser,
+    marker: Marker,
+) {
+    parser.expect(SyntaxKind::ImportKeyword);
+    if parser.at(SyntaxKind::Identifier) {
+        import_custom(parser);
+    } else {
+        parser.error_expected_no_bump(&[SyntaxKind::Identifier]);
+    }
+    parser.expect(SyntaxKind::Semicolon);
+    marker.complete(parser, SyntaxKind::Import);
+}
+
+fn import_custom(parser: &mut Parser) {
+    let marker = parser.start();
+    while parser.at(SyntaxKind::Identifier) || parser.at(SyntaxKind::ColonColon) {
+        parser.bump();
+    }
+    if parser.at(SyntaxKind::BraceLeft) {
+        list(
+            parser,
+            SyntaxKind::BraceLeft,
+            SyntaxKind::BraceRight,
+            SyntaxKind::Comma,
+            SyntaxKind::ImportItemList,
+            import_item,
+        );
+    } else if parser.at(SyntaxKind::As) {
+        import_alias(parser);
+    }
+    marker.complete(parser, SyntaxKind::ImportCustom);
+}
+
+fn import_item(parser: &mut Parser) {
+    let marker = parser.start();
+    name_ref(parser);
+    if parser.at(SyntaxKind::As) {
+        import_alias(parser);
+    }
+    marker.complete(parser, SyntaxKind::ImportItem);
+}
+
+fn import_alias(parser: &mut Parser) {
+    parser.expect(SyntaxKind::As);
+    name(parser);
+}
+
 fn override_declaration(
     parser: &mut Parser,
     marker: Marker,
@@ -450,9 +497,7 @@ pub(crate) fn type_declaration(parser: &mut Parser) -> Option<CompletedMarker> {
         Some(marker_ty.complete(parser, r#type))
     } else if parser.at(SyntaxKind::Identifier) {
         let marker_ty = parser.start();
-        let marker_name_reference = parser.start();
-        parser.bump();
-        marker_name_reference.complete(parser, SyntaxKind::NameReference);
+        name_ref(parser);
         Some(marker_ty.complete(parser, SyntaxKind::PathType))
     } else {
         parser.error();
@@ -507,6 +552,13 @@ const STATEMENT_RECOVER_SET: &[SyntaxKind] = &[
     SyntaxKind::BraceRight,
 ];
 
+/// `break`, `continue` and `discard`, wrapped in a node of the same kind as the keyword.
+fn keyword_statement(parser: &mut Parser) {
+    let marker = parser.start();
+    let kind = parser.bump();
+    marker.complete(parser, kind);
+}
+
 pub(crate) fn statement(parser: &mut Parser) {
     /*
     | [x] return_statement SEMICOLON
@@ -524,7 +576,10 @@ pub(crate) fn statement(parser: &mut Parser) {
     | [x] compound_statement
      */
 
-    if parser.at_set(&[SyntaxKind::Constant, SyntaxKind::Let, SyntaxKind::Var]) {
+    if parser.at(SyntaxKind::AttributeOperator) && parser.edition().at_least_wesl_0_0_1() {
+        // WESL allows attributes like `@if(feature)` on statements
+        attribute_list_modern(parser).attach_to_next(parser, statement);
+    } else if parser.at_set(&[SyntaxKind::Constant, SyntaxKind::Let, SyntaxKind::Var]) {
         variable_statement(parser);
     } else if parser.at(SyntaxKind::Return) {
         return_statement(parser);
@@ -540,12 +595,12 @@ pub(crate) fn statement(parser: &mut Parser) {
         while_statement(parser);
     } else if parser.at(SyntaxKind::For) {
         for_statement(parser);
-    } else if parser.at(SyntaxKind::Break) {
-        parser.bump();
-    } else if parser.at(SyntaxKind::Continue) {
-        parser.bump();
-    } else if parser.at(SyntaxKind::Discard) {
-        parser.bump();
+    } else if parser.at_set(&[
+        SyntaxKind::Break,
+        SyntaxKind::Continue,
+        SyntaxKind::Discard,
+    ]) {
+        keyword_statement(parser);
     } else if parser.at(SyntaxKind::Fallthrough) {
         parser.bump();
     } else if parser.at(SyntaxKind::Continuing) {
@@ -938,13 +993,13 @@ pub(crate) fn attribute_list(parser: &mut Parser) {
     }
 }
 
-fn attribute_list_modern(parser: &mut Parser) {
+fn attribute_list_modern(parser: &mut Parser) -> CompletedMarker {
     let marker = parser.start();
     while parser.at(SyntaxKind::AttributeOperator) {
         parser.bump();
         attribute(parser);
     }
-    marker.complete(parser, SyntaxKind::AttributeList);
+    marker.complete(parser, SyntaxKind::AttributeList)
 }
 
 fn attribute_list_legacy(parser: &mut Parser) {
@@ -959,6 +1014,11 @@ fn attribute_list_legacy(parser: &mut Parser) {
 }
 
 fn attribute(parser: &mut Parser) {
+    if parser.at(SyntaxKind::If) && parser.edition().at_least_wesl_0_0_1() {
+        if_attribute(parser);
+        return;
+    }
+
     let marker = parser.start();
     if parser.at(SyntaxKind::Identifier) {
         parser.bump();
@@ -988,8 +1048,23 @@ fn attribute(parser: &mut Parser) {
     marker.complete(parser, SyntaxKind::Attribute);
 }
 
+/// A WESL `@if(feature && !other)` attribute.
+fn if_attribute(parser: &mut Parser) {
+    let marker = parser.start();
+    parser.expect(SyntaxKind::If);
+    parser.expect(SyntaxKind::ParenthesisLeft);
+    expression(parser);
+    parser.expect(SyntaxKind::ParenthesisRight);
+    marker.complete(parser, SyntaxKind::IfAttribute);
+}
+
 fn name_ref(parser: &mut Parser) {
     let marker = parser.start();
     parser.expect(SyntaxKind::Identifier);
+    // qualified paths, e.g. `module::item`
+    while parser.at(SyntaxKind::ColonColon) {
+        parser.bump();
+        parser.expect(SyntaxKind::Identifier);
+    }
     marker.complete(parser, SyntaxKind::NameReference);
 }
@@ -21,11 +21,12 @@ pub use edition::Edition;
 
 pub fn parse<F: Fn(&mut Parser)>(
     input: &str,
+    edition: Edition,
     f: F,
 ) -> Parse {
     let tokens: Vec<_> = Lexer::<SyntaxKind>::new(input).collect();
     let source = Source::new(&tokens);
-    let parser = Parser::new(source);
+    let parser = Parser::new(source, edition);
     let events = parser.parse(f);
     let sink = Sink::new(&tokens, events);
 
@@ -130,16 +131,29 @@ pub enum ParseEntryPoint {
 pub fn parse_entrypoint(
     input: &str,
     entrypoint: ParseEntryPoint,
+) -> Parse {
+    parse_entrypoint_with_edition(input, entrypoint, Edition::DEFAULT)
+}
+
+/// Parses `input` with the grammar of the given edition.
+///
+/// WESL editions accept extensions such as `import` statements, which are errors in plain WGSL.
+pub fn parse_entrypoint_with_edition(
+    input: &str,
+    entrypoint: ParseEntryPoint,
+    edition: Edition,
 ) -> Parse {
     match entrypoint {
-        ParseEntryPoint::File => parse::<_>(input, grammar::file),
-        ParseEntryPoint::Expression => parse::<_>(input, grammar::expression),
-        ParseEntryPoint::Statement => parse::<_>(input, grammar::statement),
-        ParseEntryPoint::Type => parse::<_>(input, |p| {
+        ParseEntryPoint::File => parse::<_>(input, edition, grammar::file),
+        ParseEntryPoint::Expression => parse::<_>(input, edition, grammar::expression),
+        ParseEntryPoint::Statement => parse::<_>(input, edition, grammar::statement),
+        ParseEntryPoint::Type => parse::<_>(input, edition, |p| {
             grammar::type_declaration(p);
         }),
-        ParseEntryPoint::AttributeList => parse::<_>(input, grammar::attribute_list),
-        ParseEntryPoint::FunctionParameterList => parse::<_>(input, grammar::inner_parameter_list),
+        ParseEntryPoint::AttributeList => parse::<_>(input, edition, grammar::attribute_list),
+        ParseEntryPoint::FunctionParameterList => {
+            parse::<_>(input, edition, grammar::inner_parameter_list)
+        },
     }
 }
 
@@ -153,7 +167,17 @@ fn check_entrypoint(
     entry_point: ParseEntryPoint,
     expected_tree: expect_test::Expect,
 ) {
-    let parse = crate::parse_entrypoint(input, entry_point);
+    check_entrypoint_with_edition(input, entry_point, Edition::DEFAULT, expected_tree);
+}
+
+#[cfg(test)]
+fn check_entrypoint_with_edition(
+    input: &str,
+    entry_point: ParseEntryPoint,
+    edition: Edition,
+    expected_tree: expect_test::Expect,
+) {
+    let parse = crate::parse_entrypoint_with_edition(input, entry_point, edition);
     expected_tree.assert_eq(&parse.debug_tree());
 }
 
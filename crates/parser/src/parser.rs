@@ -6,7 +6,7 @@ use std::marker::PhantomData;
 use marker::Marker;
 pub use parse_error::ParseError;
 
-use crate::SyntaxKind;
+use crate::{Edition, SyntaxKind};
 
 use super::{event::Event, lexer::Token, source::Source};
 
@@ -14,15 +14,20 @@ pub struct Parser<'t, 'input> {
     source: Source<'t, 'input>,
     events: Vec<Event>,
     pub(crate) expected_kinds: Vec<SyntaxKind>,
+    edition: Edition,
     _marker: PhantomData<SyntaxKind>,
 }
 
 impl<'t, 'input> Parser<'t, 'input> {
-    pub(crate) fn new(source: Source<'t, 'input>) -> Self {
+    pub(crate) fn new(
+        source: Source<'t, 'input>,
+        edition: Edition,
+    ) -> Self {
         Self {
             source,
             events: Vec::new(),
             expected_kinds: Vec::new(),
+            edition,
             _marker: PhantomData,
         }
     }
@@ -35,6 +40,11 @@ impl<'t, 'input> Parser<'t, 'input> {
         self.events
     }
 
+    /// The edition whose grammar is being parsed.
+    pub fn edition(&self) -> Edition {
+        self.edition
+    }
+
     pub fn start(&mut self) -> Marker {
         let pos = self.events.len();
         self.events.push(Event::Placeholder);
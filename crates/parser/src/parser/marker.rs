@@ -50,6 +50,31 @@ pub struct CompletedMarker {
 }
 
 impl CompletedMarker {
+    /// Parses with `f` and makes this node the first child of the node `f` starts with.
+    /// This is used for prefixes like attributes, which are parsed before it is known what
+    /// they belong to.
+    ///
+    /// The node stays where it is if `f` does not start with a node.
+    pub fn attach_to_next(
+        self,
+        p: &mut Parser,
+        f: impl FnOnce(&mut Parser),
+    ) {
+        let next = p.events.len();
+        f(p);
+
+        if !matches!(p.events.get(next), Some(Event::StartNode { .. })) {
+            return;
+        }
+        if let Event::StartNode {
+            ref mut forward_parent,
+            ..
+        } = p.events[self.pos]
+        {
+            *forward_parent = Some(next - self.pos);
+        }
+    }
+
     pub fn precede(
         self,
         p: &mut Parser,
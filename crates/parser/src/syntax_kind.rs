@@ -96,6 +96,8 @@ pub enum SyntaxKind {
     Attribute,
     /// `(0, 1, ident)`
     AttributeParameters,
+    /// `if(feature && !other)`, WESL conditional compilation
+    IfAttribute,
     /// the definition of a struct
     StructDeclaration,
     /// the members of a struct definition inside of braces
@@ -117,6 +119,10 @@ pub enum SyntaxKind {
     Import,
     ImportPath,
     ImportCustom,
+    /// the `{x, y as z}` of `#import a::b::{x, y as z}`
+    ImportItemList,
+    /// the `y as z` of `#import a::b::{x, y as z}`
+    ImportItem,
 
     #[regex("[ \n\r\t]+")]
     Whitespace,
@@ -259,6 +265,8 @@ pub enum SyntaxKind {
     // other keywords
     #[token("alias")]
     Alias,
+    #[token("as")]
+    As,
     #[token("bitcast")]
     Bitcast,
     // #[token("block")]
@@ -293,6 +301,9 @@ pub enum SyntaxKind {
     FunctionClass,
     #[token("if")]
     If,
+    /// The WESL `import` keyword
+    #[token("import")]
+    ImportKeyword,
     #[token("let")]
     Let,
     #[token("loop")]
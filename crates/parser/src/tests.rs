@@ -1,7 +1,7 @@
 #![cfg_attr(not(test), allow(unused))]
 use expect_test::{Expect, expect};
 
-use crate::ParseEntryPoint;
+use crate::{Edition, ParseEntryPoint};
 
 fn check(
     input: &str,
@@ -10,6 +10,18 @@ fn check(
     crate::check_entrypoint(input, ParseEntryPoint::File, expected_tree);
 }
 
+fn check_wesl(
+    input: &str,
+    expected_tree: Expect,
+) {
+    crate::check_entrypoint_with_edition(
+        input,
+        ParseEntryPoint::File,
+        Edition::Wesl0_0_1,
+        expected_tree,
+    );
+}
+
 fn check_type(
     input: &str,
     expected_tree: Expect,
@@ -959,10 +971,12 @@ fn for_statement_continue_break() {
           CompoundStatement@8..43
             BraceLeft@8..9 "{"
             Whitespace@9..10 " "
-            Continue@10..18 "continue"
+            Continue@10..18
+              Continue@10..18 "continue"
             Semicolon@18..19 ";"
             Whitespace@19..20 " "
-            Break@20..25 "break"
+            Break@20..25
+              Break@20..25 "break"
             Semicolon@25..26 ";"
             Whitespace@26..27 " "
             ContinuingStatement@27..40
@@ -1272,7 +1286,7 @@ fn unfinished_attr() {
                 BracketRight@17..18 "]"
 
             error at 17..18: expected Identifier, but found BracketRight
-            error at 17..18: expected Comma, AttributeRight, Identifier, or ParenthesisLeft, but found BracketRight
+            error at 17..18: expected Comma, AttributeRight, If, Identifier, or ParenthesisLeft, but found BracketRight
             error at 17..18: expected Comma or AttributeRight"#]],
     );
 }
@@ -2540,6 +2554,345 @@ fn parse_import_colon() {
     );
 }
 
+#[test]
+fn parse_import_item_list() {
+    check(
+        "#import bevy_pbr::mesh_functions::{get_model_matrix, mesh_position_local_to_world as to_world}",
+        expect![[r##"
+            SourceFile@0..94
+              Import@0..94
+                UnofficialPreprocessorImport@0..7 "#import"
+                Whitespace@7..8 " "
+                ImportCustom@8..94
+                  Identifier@8..16 "bevy_pbr"
+                  ColonColon@16..18 "::"
+                  Identifier@18..32 "mesh_functions"
+                  ColonColon@32..34 "::"
+                  ImportItemList@34..94
+                    BraceLeft@34..35 "{"
+                    ImportItem@35..51
+                      NameReference@35..51
+                        Identifier@35..51 "get_model_matrix"
+                    Comma@51..52 ","
+                    Whitespace@52..53 " "
+                    ImportItem@53..93
+                      NameReference@53..82
+                        Identifier@53..81 "mesh_position_local_t ..."
+                        Whitespace@81..82 " "
+                      As@82..84 "as"
+                      Whitespace@84..85 " "
+                      Name@85..93
+                        Identifier@85..93 "to_world"
+                    BraceRight@93..94 "}""##]],
+    );
+}
+
+#[test]
+fn parse_import_alias() {
+    check(
+        "#import bevy_pbr::mesh_functions as mesh",
+        expect![[r##"
+            SourceFile@0..40
+              Import@0..40
+                UnofficialPreprocessorImport@0..7 "#import"
+                Whitespace@7..8 " "
+                ImportCustom@8..40
+                  Identifier@8..16 "bevy_pbr"
+                  ColonColon@16..18 "::"
+                  Identifier@18..32 "mesh_functions"
+                  Whitespace@32..33 " "
+                  As@33..35 "as"
+                  Whitespace@35..36 " "
+                  Name@36..40
+                    Identifier@36..40 "mesh""##]],
+    );
+}
+
+#[test]
+fn parse_wesl_import() {
+    check_wesl(
+        "import package::lighting::{pbr, shadows as shadow};\nimport super::util;",
+        expect![[r#"
+            SourceFile@0..71
+              Import@0..52
+                ImportKeyword@0..6 "import"
+                Whitespace@6..7 " "
+                ImportCustom@7..50
+                  Identifier@7..14 "package"
+                  ColonColon@14..16 "::"
+                  Identifier@16..24 "lighting"
+                  ColonColon@24..26 "::"
+                  ImportItemList@26..50
+                    BraceLeft@26..27 "{"
+                    ImportItem@27..30
+                      NameReference@27..30
+                        Identifier@27..30 "pbr"
+                    Comma@30..31 ","
+                    Whitespace@31..32 " "
+                    ImportItem@32..49
+                      NameReference@32..40
+                        Identifier@32..39 "shadows"
+                        Whitespace@39..40 " "
+                      As@40..42 "as"
+                      Whitespace@42..43 " "
+                      Name@43..49
+                        Identifier@43..49 "shadow"
+                    BraceRight@49..50 "}"
+                Semicolon@50..51 ";"
+                Whitespace@51..52 "\n"
+              Import@52..71
+                ImportKeyword@52..58 "import"
+                Whitespace@58..59 " "
+                ImportCustom@59..70
+                  Identifier@59..64 "super"
+                  ColonColon@64..66 "::"
+                  Identifier@66..70 "util"
+                Semicolon@70..71 ";""#]],
+    );
+}
+
+#[test]
+fn parse_wesl_import_missing_path() {
+    check_wesl(
+        "import ;",
+        expect![[r#"
+        SourceFile@0..8
+          Import@0..8
+            ImportKeyword@0..6 "import"
+            Whitespace@6..7 " "
+            Error@7..7
+            Semicolon@7..8 ";"
+
+        error at 7..8: expected Identifier, but found Semicolon"#]],
+    );
+}
+
+#[test]
+fn parse_wesl_import_in_wgsl() {
+    check(
+        "import package::util;",
+        expect![[r#"
+        SourceFile@0..21
+          Error@0..7
+            Error@0..7
+              ImportKeyword@0..6 "import"
+              Whitespace@6..7 " "
+          Error@7..14
+            Error@7..14
+              Identifier@7..14 "package"
+          Error@14..16
+            Error@14..16
+              ColonColon@14..16 "::"
+          Error@16..20
+            Error@16..20
+              Identifier@16..20 "util"
+          Error@20..21
+            Error@20..21
+              Semicolon@20..21 ";"
+
+        error at 0..6: expected Fn, Struct, Var, Let, Constant, Alias, or Override, but found ImportKeyword
+        error at 7..14: expected Fn, Struct, Var, Let, Constant, Alias, or Override, but found Identifier
+        error at 14..16: expected Fn, Struct, Var, Let, Constant, Alias, or Override, but found ColonColon
+        error at 16..20: expected Fn, Struct, Var, Let, Constant, Alias, or Override, but found Identifier
+        error at 20..21: expected Fn, Struct, Var, Let, Constant, Alias, or Override, but found Semicolon"#]],
+    );
+}
+
+#[test]
+fn parse_wesl_if_attribute() {
+    check_wesl(
+        "@if(feature && !other) fn a() {}\nstruct S { @if(x) a: f32 }",
+        expect![[r#"
+            SourceFile@0..59
+              Function@0..33
+                AttributeList@0..23
+                  AttributeOperator@0..1 "@"
+                  IfAttribute@1..23
+                    If@1..3 "if"
+                    ParenthesisLeft@3..4 "("
+                    InfixExpression@4..21
+                      PathExpression@4..12
+                        NameReference@4..12
+                          Identifier@4..11 "feature"
+                          Whitespace@11..12 " "
+                      AndAnd@12..14 "&&"
+                      Whitespace@14..15 " "
+                      PrefixExpression@15..21
+                        Bang@15..16 "!"
+                        PathExpression@16..21
+                          NameReference@16..21
+                            Identifier@16..21 "other"
+                    ParenthesisRight@21..22 ")"
+                    Whitespace@22..23 " "
+                Fn@23..25 "fn"
+                Whitespace@25..26 " "
+                Name@26..27
+                  Identifier@26..27 "a"
+                ParameterList@27..30
+                  ParenthesisLeft@27..28 "("
+                  ParenthesisRight@28..29 ")"
+                  Whitespace@29..30 " "
+                CompoundStatement@30..33
+                  BraceLeft@30..31 "{"
+                  BraceRight@31..32 "}"
+                  Whitespace@32..33 "\n"
+              StructDeclaration@33..59
+                Struct@33..39 "struct"
+                Whitespace@39..40 " "
+                Name@40..42
+                  Identifier@40..41 "S"
+                  Whitespace@41..42 " "
+                StructDeclBody@42..59
+                  BraceLeft@42..43 "{"
+                  Whitespace@43..44 " "
+                  StructDeclarationField@44..58
+                    AttributeList@44..51
+                      AttributeOperator@44..45 "@"
+                      IfAttribute@45..51
+                        If@45..47 "if"
+                        ParenthesisLeft@47..48 "("
+                        PathExpression@48..49
+                          NameReference@48..49
+                            Identifier@48..49 "x"
+                        ParenthesisRight@49..50 ")"
+                        Whitespace@50..51 " "
+                    VariableIdentDeclaration@51..58
+                      Binding@51..52
+                        Name@51..52
+                          Identifier@51..52 "a"
+                      Colon@52..53 ":"
+                      Whitespace@53..54 " "
+                      Float32@54..58
+                        Float32@54..57 "f32"
+                        Whitespace@57..58 " "
+                  BraceRight@58..59 "}""#]],
+    );
+}
+
+#[test]
+fn parse_wesl_if_attribute_on_statement() {
+    check_wesl(
+        "fn a() { @if(feature) let x = 1; @if(!feature) { x = 2; } }",
+        expect![[r#"
+            SourceFile@0..59
+              Function@0..59
+                Fn@0..2 "fn"
+                Whitespace@2..3 " "
+                Name@3..4
+                  Identifier@3..4 "a"
+                ParameterList@4..7
+                  ParenthesisLeft@4..5 "("
+                  ParenthesisRight@5..6 ")"
+                  Whitespace@6..7 " "
+                CompoundStatement@7..59
+                  BraceLeft@7..8 "{"
+                  Whitespace@8..9 " "
+                  VariableStatement@9..31
+                    AttributeList@9..22
+                      AttributeOperator@9..10 "@"
+                      IfAttribute@10..22
+                        If@10..12 "if"
+                        ParenthesisLeft@12..13 "("
+                        PathExpression@13..20
+                          NameReference@13..20
+                            Identifier@13..20 "feature"
+                        ParenthesisRight@20..21 ")"
+                        Whitespace@21..22 " "
+                    Let@22..25 "let"
+                    Whitespace@25..26 " "
+                    Binding@26..28
+                      Name@26..28
+                        Identifier@26..27 "x"
+                        Whitespace@27..28 " "
+                    Equal@28..29 "="
+                    Whitespace@29..30 " "
+                    Literal@30..31
+                      DecimalIntLiteral@30..31 "1"
+                  Semicolon@31..32 ";"
+                  Whitespace@32..33 " "
+                  CompoundStatement@33..58
+                    AttributeList@33..47
+                      AttributeOperator@33..34 "@"
+                      IfAttribute@34..47
+                        If@34..36 "if"
+                        ParenthesisLeft@36..37 "("
+                        PrefixExpression@37..45
+                          Bang@37..38 "!"
+                          PathExpression@38..45
+                            NameReference@38..45
+                              Identifier@38..45 "feature"
+                        ParenthesisRight@45..46 ")"
+                        Whitespace@46..47 " "
+                    BraceLeft@47..48 "{"
+                    Whitespace@48..49 " "
+                    AssignmentStatement@49..54
+                      PathExpression@49..51
+                        NameReference@49..51
+                          Identifier@49..50 "x"
+                          Whitespace@50..51 " "
+                      Equal@51..52 "="
+                      Whitespace@52..53 " "
+                      Literal@53..54
+                        DecimalIntLiteral@53..54 "2"
+                    Semicolon@54..55 ";"
+                    Whitespace@55..56 " "
+                    BraceRight@56..57 "}"
+                    Whitespace@57..58 " "
+                  BraceRight@58..59 "}""#]],
+    );
+}
+
+#[test]
+fn parse_qualified_path_expression() {
+    check_statement(
+        "let a = mesh::get_model_matrix(0u) * mesh::scale;",
+        expect![[r#"
+        VariableStatement@0..48
+          Let@0..3 "let"
+          Whitespace@3..4 " "
+          Binding@4..6
+            Name@4..6
+              Identifier@4..5 "a"
+              Whitespace@5..6 " "
+          Equal@6..7 "="
+          Whitespace@7..8 " "
+          InfixExpression@8..48
+            FunctionCall@8..35
+              NameReference@8..30
+                Identifier@8..12 "mesh"
+                ColonColon@12..14 "::"
+                Identifier@14..30 "get_model_matrix"
+              FunctionParameterList@30..35
+                ParenthesisLeft@30..31 "("
+                Literal@31..33
+                  UnsignedIntLiteral@31..33 "0u"
+                ParenthesisRight@33..34 ")"
+                Whitespace@34..35 " "
+            Star@35..36 "*"
+            Whitespace@36..37 " "
+            PathExpression@37..48
+              NameReference@37..48
+                Identifier@37..41 "mesh"
+                ColonColon@41..43 "::"
+                Identifier@43..48 "scale""#]],
+    );
+}
+
+#[test]
+fn parse_qualified_path_type() {
+    check_type(
+        "bevy_pbr::mesh_types::Mesh",
+        expect![[r#"
+        PathType@0..26
+          NameReference@0..26
+            Identifier@0..8 "bevy_pbr"
+            ColonColon@8..10 "::"
+            Identifier@10..20 "mesh_types"
+            ColonColon@20..22 "::"
+            Identifier@22..26 "Mesh""#]],
+    );
+}
+
 #[test]
 
 fn parse_string_import() {
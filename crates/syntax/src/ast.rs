@@ -207,13 +207,18 @@ ast_node!(SourceFile:
 
 ast_node!(Import:
     import_token: Option<SyntaxToken UnofficialPreprocessorImport>;
+    import_keyword_token: Option<SyntaxToken ImportKeyword>;
     import: Option<ImportKind>;
+    semicolon_token: Option<SyntaxToken Semicolon>;
 );
 
 ast_node!(ImportPath:
     string_literal: Option<SyntaxToken StringLiteral>;
 );
-ast_node!(ImportCustom);
+ast_node!(ImportCustom:
+    item_list: Option<ImportItemList>;
+    alias: Option<Name>;
+);
 impl ImportCustom {
     pub fn segments(&self) -> impl Iterator<Item = ImportCustomSegment> {
         self.syntax
@@ -234,9 +239,20 @@ impl ImportCustom {
                 };
                 accumulator
             })
+            .trim_end_matches("::")
+            .to_owned()
     }
 }
 
+ast_node!(ImportItemList:
+    items: AstChildren<ImportItem>;
+);
+
+ast_node!(ImportItem:
+    name_ref: Option<NameReference>;
+    alias: Option<Name>;
+);
+
 ast_token_enum! {
     enum ImportCustomSegment {
         Identifier,
@@ -304,6 +320,8 @@ ast_node!(OverrideDeclaration:
     init: Option<Expression>;
 );
 
+impl HasAttributes for GlobalConstantDeclaration {}
+
 ast_node!(TypeAliasDeclaration:
     alias_token: Option<SyntaxToken Alias>;
     name: Option<Name>;
@@ -311,6 +329,8 @@ ast_node!(TypeAliasDeclaration:
     type_declaration: Option<Type>;
 );
 
+impl HasAttributes for TypeAliasDeclaration {}
+
 ast_enum! {
     enum Item {
         Function,
@@ -323,6 +343,8 @@ ast_enum! {
     }
 }
 
+impl HasAttributes for Item {}
+
 ast_node!(Name:
     ident_token: Option<SyntaxToken Identifier>;
     text: TokenText<'_>;
@@ -333,6 +355,8 @@ ast_node!(Parameter:
     import: Option<Import>;
 );
 
+impl HasAttributes for Parameter {}
+
 ast_node!(ParameterList:
     left_parenthesis_token: Option<SyntaxToken ParenthesisLeft>;
     right_parenthesis_token: Option<SyntaxToken ParenthesisRight>;
@@ -513,6 +537,27 @@ ast_node!(PathExpression:
 ast_node!(NameReference:
     text: TokenText<'_>;
 );
+impl NameReference {
+    /// The identifiers of a possibly qualified path like `module::item`.
+    pub fn segments(&self) -> impl Iterator<Item = SyntaxToken> {
+        self.syntax
+            .children_with_tokens()
+            .filter_map(NodeOrToken::into_token)
+            .filter(|token| token.kind() == SyntaxKind::Identifier)
+    }
+
+    /// The full path, with segments joined by `::`.
+    pub fn path(&self) -> String {
+        let mut path = String::new();
+        for (index, segment) in self.segments().enumerate() {
+            if index != 0 {
+                path.push_str("::");
+            }
+            path.push_str(segment.text());
+        }
+        path
+    }
+}
 ast_node!(ParenthesisExpression:
     left_parenthesis_token: Option<SyntaxToken ParenthesisLeft>;
     right_parenthesis_token: Option<SyntaxToken ParenthesisRight>;
@@ -527,6 +572,7 @@ ast_node!(BitcastExpression:
 );
 ast_node!(FieldExpression:
     expression: Option<Expression>;
+    period_token: Option<SyntaxToken Period>;
     name_ref: Option<NameReference>;
 );
 ast_node!(FunctionCall:
@@ -550,6 +596,7 @@ impl IndexExpression {
 
 ast_node!(AttributeList:
     attributes: AstChildren<Attribute>;
+    if_attributes: AstChildren<IfAttribute>;
 );
 ast_node!(Attribute:
     ident_token: Option<SyntaxToken Identifier>;
@@ -558,6 +605,54 @@ ast_node!(Attribute:
 ast_node!(AttributeParameters:
     values: AstChildren<IdentOrLiteral>;
 );
+ast_node!(IfAttribute:
+    if_token: Option<SyntaxToken If>;
+    condition: Option<Expression>;
+);
+
+impl IfAttribute {
+    /// Evaluates the condition, given which features are enabled.
+    ///
+    /// Returns `None` for conditions which are not made up of feature names, `true`, `false`,
+    /// `!`, `&&`, `||` and parentheses.
+    pub fn evaluate(
+        &self,
+        is_enabled: &dyn Fn(&str) -> bool,
+    ) -> Option<bool> {
+        evaluate_condition(&self.condition()?, is_enabled)
+    }
+}
+
+fn evaluate_condition(
+    condition: &Expression,
+    is_enabled: &dyn Fn(&str) -> bool,
+) -> Option<bool> {
+    match condition {
+        Expression::PathExpression(path) => Some(is_enabled(&path.name_ref()?.path())),
+        Expression::Literal(literal) => match literal.kind() {
+            LiteralKind::True(_) => Some(true),
+            LiteralKind::False(_) => Some(false),
+            _ => None,
+        },
+        Expression::ParenthesisExpression(expression) => {
+            evaluate_condition(&expression.inner()?, is_enabled)
+        },
+        Expression::PrefixExpression(expression) => match expression.op_kind()? {
+            UnaryOperator::Not => Some(!evaluate_condition(&expression.expression()?, is_enabled)?),
+            _ => None,
+        },
+        Expression::InfixExpression(expression) => {
+            let left = evaluate_condition(&expression.left_side()?, is_enabled)?;
+            let right = evaluate_condition(&expression.right_side()?, is_enabled)?;
+            match expression.op_kind()? {
+                BinaryOperation::Logical(LogicOperation::And) => Some(left && right),
+                BinaryOperation::Logical(LogicOperation::Or) => Some(left || right),
+                _ => None,
+            }
+        },
+        _ => None,
+    }
+}
 
 ast_node!(Identifier:
     text: TokenText<'_>;
@@ -772,6 +867,7 @@ ast_node!(Discard);
 ast_node!(Break);
 ast_node!(Continue);
 ast_node!(ContinuingStatement:
+    continuing_token: Option<SyntaxToken Continuing>;
     block: Option<CompoundStatement>;
 );
 
@@ -796,6 +892,8 @@ ast_enum! {
     }
 }
 
+impl HasAttributes for Statement {}
+
 ast_enum! {
     enum Expression {
         InfixExpression,
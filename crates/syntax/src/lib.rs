@@ -6,8 +6,8 @@ use std::{marker::PhantomData, ops::Deref, sync::Arc};
 
 use either::Either;
 pub use parser::{
-    ParseEntryPoint, ParseError, SyntaxElement, SyntaxKind, SyntaxNode, SyntaxNodeChildren,
-    SyntaxToken,
+    Edition, ParseEntryPoint, ParseError, SyntaxElement, SyntaxKind, SyntaxNode,
+    SyntaxNodeChildren, SyntaxToken,
 };
 pub use rowan::Direction;
 
@@ -50,7 +50,23 @@ pub fn parse_entrypoint(
     input: &str,
     parse_entrypoint: ParseEntryPoint,
 ) -> Parse {
-    let (green_node, errors) = parser::parse_entrypoint(input, parse_entrypoint).into_parts();
+    parse_entrypoint_with_edition(input, parse_entrypoint, Edition::DEFAULT)
+}
+
+pub fn parse_with_edition(
+    input: &str,
+    edition: Edition,
+) -> Parse {
+    parse_entrypoint_with_edition(input, ParseEntryPoint::File, edition)
+}
+
+pub fn parse_entrypoint_with_edition(
+    input: &str,
+    parse_entrypoint: ParseEntryPoint,
+    edition: Edition,
+) -> Parse {
+    let (green_node, errors) =
+        parser::parse_entrypoint_with_edition(input, parse_entrypoint, edition).into_parts();
     Parse {
         green_node,
         errors: Arc::new(errors),
@@ -225,6 +241,17 @@ pub trait HasAttributes: AstNode {
             None => Either::Right(std::iter::empty()),
         }
     }
+    /// Whether all `@if` attributes hold, given which features are enabled.
+    /// Malformed conditions are treated as satisfied.
+    fn is_configured(
+        &self,
+        is_enabled: &dyn Fn(&str) -> bool,
+    ) -> bool {
+        self.attribute_list().is_none_or(|list| {
+            list.if_attributes()
+                .all(|attribute| attribute.evaluate(is_enabled).unwrap_or(true))
+        })
+    }
 }
 
 #[macro_export]
@@ -77,6 +77,7 @@ fn main() -> Result<ExitCode> {
                 run_server,
             )?;
         },
+        flags::WgslAnalyzerCmd::Explain(command) => command.run()?,
         // flags::WgslAnalyzerCmd::Parse(cmd) => cmd.run()?,
         // flags::WgslAnalyzerCmd::Symbols(cmd) => cmd.run()?,
         // flags::WgslAnalyzerCmd::Highlight(cmd) => cmd.run()?,
@@ -2,6 +2,7 @@
 
 // mod analysis_stats;
 // mod diagnostics;
+mod explain;
 pub mod flags;
 // mod highlight;
 // mod lsif;
@@ -0,0 +1,24 @@
+//! Prints the explanation of a diagnostic code.
+
+use ide::diagnostics::explanations::{self, EXPLANATIONS};
+use itertools::Itertools as _;
+
+use crate::cli::flags;
+
+impl flags::Explain {
+    #[expect(clippy::print_stdout, reason = "CLI feature")]
+    pub fn run(self) -> anyhow::Result<()> {
+        let Some(explanation) = explanations::explanation(&self.code) else {
+            anyhow::bail!(
+                "unknown diagnostic code `{}`, expected one of: {}",
+                self.code,
+                EXPLANATIONS
+                    .iter()
+                    .map(|explanation| explanation.code.as_str())
+                    .join(", ")
+            );
+        };
+        print!("{}", explanation.to_markdown());
+        Ok(())
+    }
+}
@@ -47,6 +47,11 @@ xflags::xflags! {
         /// Parse stdin and print the list of symbols.
         cmd symbols {}
 
+        /// Explain a diagnostic code, such as `type-mismatch`.
+        cmd explain {
+            required code: String
+        }
+
         /// Highlight stdin as html.
         cmd highlight {
             /// Enable rainbow highlighting of identifiers.
@@ -195,6 +200,7 @@ pub enum WgslAnalyzerCmd {
     LspServer(LspServer),
     Parse(Parse),
     Symbols(Symbols),
+    Explain(Explain),
     Highlight(Highlight),
     AnalysisStats(AnalysisStats),
     RunTests(RunTests),
@@ -221,6 +227,11 @@ pub struct Parse {
 #[derive(Debug)]
 pub struct Symbols;
 
+#[derive(Debug)]
+pub struct Explain {
+    pub code: String,
+}
+
 #[derive(Debug)]
 pub struct Highlight {
     pub rainbow: bool,
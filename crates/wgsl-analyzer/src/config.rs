@@ -4,7 +4,10 @@ use base_db::input::SourceRootId;
 use hir::diagnostics::{DiagnosticsConfig, NagaVersion};
 use hir_ty::ty::pretty::TypeVerbosity;
 use ide::inlay_hints::{self, StructLayoutHints};
-use ide_completion::{CompletionConfig, CompletionFieldsToResolve};
+use ide_completion::{
+    CallableSnippets, CompletionConfig, CompletionFieldsToResolve, Snippet, SnippetCap,
+    SnippetScope,
+};
 // use ide::{
 //     AssistConfig, CallHierarchyConfig, CallableSnippets, CompletionConfig,
 //     CompletionFieldsToResolve, DiagnosticsConfig, ExprFillDefaultMode, GenericParameterHints,
@@ -49,9 +52,44 @@ pub struct InlayHintsConfig {
     pub type_hints: bool,
     pub parameter_hints: bool,
     pub struct_layout_hints: bool,
+    #[serde(default = "default_struct_layout_hint_kinds")]
+    pub struct_layout_hint_kinds: Vec<StructLayoutHintKind>,
+    #[serde(default)]
+    pub const_value_hints: bool,
+    #[serde(default)]
+    pub conversion_hints: bool,
+    #[serde(default)]
+    pub access_mode_hints: bool,
+    #[serde(default)]
+    pub closing_brace_hints: ClosingBraceHintsConfig,
     pub type_verbosity: InlayHintsTypeVerbosity,
 }
 
+#[derive(Default, Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ClosingBraceHintsConfig {
+    pub enable: bool,
+    pub min_lines: usize,
+}
+
+/// A detail of the memory layout which struct layout hints show.
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StructLayoutHintKind {
+    Offset,
+    Size,
+    Align,
+    Padding,
+}
+
+fn default_struct_layout_hint_kinds() -> Vec<StructLayoutHintKind> {
+    vec![
+        StructLayoutHintKind::Offset,
+        StructLayoutHintKind::Size,
+        StructLayoutHintKind::Padding,
+    ]
+}
+
 #[derive(Clone, Copy, Debug, Deserialize)]
 #[serde(rename_all = "lowercase")]
 #[derive(Default)]
@@ -62,6 +100,128 @@ pub enum InlayHintsTypeVerbosity {
     Inner, // f32
 }
 
+#[derive(Default, Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CompletionConfigData {
+    pub autoimport: AutoImportConfig,
+    pub callable: CallableConfig,
+    pub postfix: PostfixConfig,
+    pub snippets: SnippetsConfig,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct AutoImportConfig {
+    /// Whether to complete items of modules which are not imported yet.
+    pub enable: bool,
+}
+
+impl Default for AutoImportConfig {
+    fn default() -> Self {
+        Self { enable: true }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct PostfixConfig {
+    /// Whether to complete templates like `expression.if`.
+    pub enable: bool,
+}
+
+impl Default for PostfixConfig {
+    fn default() -> Self {
+        Self { enable: true }
+    }
+}
+
+#[derive(Default, Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct CallableConfig {
+    pub snippets: CallableCompletionDef,
+}
+
+/// How functions are completed.
+#[derive(Clone, Copy, Debug, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CallableCompletionDef {
+    /// Add call parentheses and a placeholder for every argument.
+    #[default]
+    FillArguments,
+    /// Add call parentheses.
+    AddParentheses,
+    /// Complete only the name.
+    None,
+}
+
+#[derive(Default, Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct SnippetsConfig {
+    /// User snippets by name.
+    pub custom: FxHashMap<String, SnippetDef>,
+}
+
+#[derive(Default, Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct SnippetDef {
+    pub prefix: SingleOrVec,
+    pub postfix: SingleOrVec,
+    pub body: SingleOrVec,
+    pub description: Option<String>,
+    pub scope: SnippetScopeDef,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+pub enum SingleOrVec {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
+impl Default for SingleOrVec {
+    fn default() -> Self {
+        Self::Multiple(Vec::new())
+    }
+}
+
+impl SingleOrVec {
+    fn as_slice(&self) -> &[String] {
+        match self {
+            Self::Single(single) => std::slice::from_ref(single),
+            Self::Multiple(multiple) => multiple,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SnippetScopeDef {
+    Item,
+    Statement,
+    #[default]
+    Expression,
+}
+
+/// The edition of files whose extension does not determine one.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EditionConfig {
+    #[default]
+    Wgsl,
+    Wesl,
+}
+
+impl EditionConfig {
+    #[must_use]
+    #[inline]
+    pub const fn to_edition(self) -> syntax::Edition {
+        match self {
+            Self::Wgsl => syntax::Edition::Wgsl,
+            Self::Wesl => syntax::Edition::Wesl0_0_1,
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 struct ClientInfo {
     name: String,
@@ -134,9 +294,13 @@ pub struct DiscoverWorkspaceConfig {
 pub struct ConfigData {
     pub custom_imports: FxHashMap<String, String>,
     pub shader_defs: FxHashSet<String>,
+    #[serde(default)]
+    pub edition: EditionConfig,
     pub trace: TraceConfig,
     pub inlay_hints: InlayHintsConfig,
     pub diagnostics: DiagnosticsConfig,
+    #[serde(default)]
+    pub completion: CompletionConfigData,
 
     /// How many worker threads to handle priming caches. The default `0` means to pick automatically.
     pub cache_priming_num_threads: NumThreads,
@@ -209,9 +373,11 @@ impl Config {
             data: ConfigData {
                 custom_imports: FxHashMap::default(),
                 shader_defs: FxHashSet::default(),
+                edition: EditionConfig::default(),
                 trace: TraceConfig::default(),
                 inlay_hints: InlayHintsConfig::default(),
                 diagnostics: DiagnosticsConfig::default(),
+                completion: CompletionConfigData::default(),
                 cache_priming_num_threads: NumThreads::Physical,
                 num_threads: None,
             },
@@ -303,8 +469,8 @@ impl Config {
     ) -> CompletionConfig<'_> {
         let client_capability_fields = self.completion_resolve_support_properties();
         CompletionConfig {
-            enable_postfix_completions: false,
-            enable_imports_on_the_fly: false,
+            enable_postfix_completions: self.data.completion.postfix.enable,
+            enable_imports_on_the_fly: self.data.completion.autoimport.enable,
             enable_self_on_the_fly: false,
             enable_auto_iter: false,
             enable_auto_await: false,
@@ -312,14 +478,18 @@ impl Config {
             enable_term_search: false,
             term_search_fuel: 400,
             full_function_signatures: false,
-            callable: None,
+            callable: match self.data.completion.callable.snippets {
+                CallableCompletionDef::FillArguments => Some(CallableSnippets::FillArguments),
+                CallableCompletionDef::AddParentheses => Some(CallableSnippets::AddParentheses),
+                CallableCompletionDef::None => None,
+            },
             add_semicolon_to_unit: false,
-            // pub snippet_cap: Option<SnippetCap>,
+            snippet_cap: SnippetCap::new(self.caps.completion_snippet()),
             // pub insert_use: InsertUseConfig,
             prefer_no_std: false,
             prefer_prelude: false,
             prefer_absolute: false,
-            // pub snippets: Vec<Snippet>,
+            snippets: self.data.snippets(),
             limit: None,
             fields_to_resolve: CompletionFieldsToResolve::from_client_capabilities(
                 &client_capability_fields,
@@ -405,7 +575,7 @@ impl ConfigData {
 
     #[inline]
     #[must_use]
-    pub const fn diagnostics(
+    pub fn diagnostics(
         &self,
         source_root: Option<SourceRootId>,
     ) -> DiagnosticsConfig {
@@ -420,6 +590,45 @@ impl ConfigData {
                 NagaVersion::Naga22 => NagaVersion::Naga22,
                 NagaVersion::NagaMain => NagaVersion::NagaMain,
             },
+            severity: self.diagnostics.severity.clone(),
+        }
+    }
+
+    /// The valid user snippets.
+    #[must_use]
+    pub fn snippets(&self) -> Vec<Snippet> {
+        self.completion
+            .snippets
+            .custom
+            .iter()
+            .filter_map(|(name, definition)| {
+                let snippet = Snippet::new(
+                    definition.prefix.as_slice(),
+                    definition.postfix.as_slice(),
+                    definition.body.as_slice(),
+                    definition.description.as_deref().unwrap_or_default(),
+                    match definition.scope {
+                        SnippetScopeDef::Item => SnippetScope::Item,
+                        SnippetScopeDef::Statement => SnippetScope::Statement,
+                        SnippetScopeDef::Expression => SnippetScope::Expression,
+                    },
+                );
+                if snippet.is_none() {
+                    tracing::warn!("Snippet `{name}` has no trigger or no body");
+                }
+                snippet
+            })
+            .collect()
+    }
+
+    #[inline]
+    #[must_use]
+    pub(crate) fn diagnostics_map(&self) -> DiagnosticsMapConfig {
+        DiagnosticsMapConfig {
+            remap_prefix: FxHashMap::default(),
+            warnings_as_info: self.diagnostics.warnings_as_info.clone(),
+            warnings_as_hint: self.diagnostics.warnings_as_hint.clone(),
+            check_ignore: FxHashSet::default(),
         }
     }
 
@@ -430,10 +639,28 @@ impl ConfigData {
             enabled: self.inlay_hints.enabled,
             type_hints: self.inlay_hints.type_hints,
             parameter_hints: self.inlay_hints.parameter_hints,
-            struct_layout_hints: self
+            struct_layout_hints: if self.inlay_hints.struct_layout_hints {
+                self.inlay_hints
+                    .struct_layout_hint_kinds
+                    .iter()
+                    .map(|kind| match kind {
+                        StructLayoutHintKind::Offset => StructLayoutHints::Offset,
+                        StructLayoutHintKind::Size => StructLayoutHints::Size,
+                        StructLayoutHintKind::Align => StructLayoutHints::Align,
+                        StructLayoutHintKind::Padding => StructLayoutHints::Padding,
+                    })
+                    .collect()
+            } else {
+                Vec::new()
+            },
+            const_value_hints: self.inlay_hints.const_value_hints,
+            conversion_hints: self.inlay_hints.conversion_hints,
+            access_mode_hints: self.inlay_hints.access_mode_hints,
+            closing_brace_hints: self
                 .inlay_hints
-                .struct_layout_hints
-                .then_some(StructLayoutHints::Offset),
+                .closing_brace_hints
+                .enable
+                .then_some(self.inlay_hints.closing_brace_hints.min_lines),
             type_verbosity: match self.inlay_hints.type_verbosity {
                 InlayHintsTypeVerbosity::Full => TypeVerbosity::Full,
                 InlayHintsTypeVerbosity::Compact => TypeVerbosity::Compact,
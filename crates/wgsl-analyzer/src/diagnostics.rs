@@ -6,7 +6,7 @@ use std::mem;
 
 use cargo_metadata::PackageId;
 type FileId = vfs::FileId;
-use ide::diagnostics::Diagnostic;
+use ide::diagnostics::{Diagnostic, Severity};
 use itertools::Itertools as _;
 use lsp_types::DiagnosticSeverity;
 use nohash_hasher::{IntMap, IntSet};
@@ -261,6 +261,7 @@ pub(crate) fn fetch_native_diagnostics(
             let source_root = snapshot.analysis.source_root_id(file_id).ok()?;
 
             let config = &snapshot.config.data().diagnostics(Some(source_root));
+            let map_config = snapshot.config.data().diagnostics_map();
             let diagnostics = match kind {
                 NativeDiagnosticsFetchKind::Syntax => {
                     snapshot.analysis.syntax_diagnostics(config, file_id).ok()?
@@ -276,27 +277,48 @@ pub(crate) fn fetch_native_diagnostics(
             };
             let diagnostics = diagnostics
                 .into_iter()
-                .map(|diagnostic| convert_diagnostic(&line_index, diagnostic))
+                .map(|diagnostic| convert_diagnostic(&line_index, &map_config, diagnostic))
                 .collect::<Vec<_>>();
             Some((file_id, diagnostics))
         })
         .collect()
 }
 
+/// Maps the severity of a native diagnostic, honoring `warningsAsInfo` and `warningsAsHint`.
+pub(crate) fn diagnostic_severity(
+    config: &DiagnosticsMapConfig,
+    diagnostic: &Diagnostic,
+) -> DiagnosticSeverity {
+    let code = diagnostic.code.as_str();
+    match diagnostic.severity {
+        Severity::Error => DiagnosticSeverity::ERROR,
+        Severity::Warning if config.warnings_as_hint.iter().any(|it| it == code) => {
+            DiagnosticSeverity::HINT
+        },
+        Severity::Warning if config.warnings_as_info.iter().any(|it| it == code) => {
+            DiagnosticSeverity::INFORMATION
+        },
+        Severity::Warning => DiagnosticSeverity::WARNING,
+        Severity::Info => DiagnosticSeverity::INFORMATION,
+        Severity::WeakWarning => DiagnosticSeverity::HINT,
+    }
+}
+
 pub(crate) fn convert_diagnostic(
     line_index: &crate::line_index::LineIndex,
+    config: &DiagnosticsMapConfig,
     diagnostic: Diagnostic,
 ) -> lsp_types::Diagnostic {
     lsp_types::Diagnostic {
         range: lsp::to_proto::range(line_index, diagnostic.range),
-        severity: Some(DiagnosticSeverity::ERROR),
+        severity: Some(diagnostic_severity(config, &diagnostic)),
         code: Some(lsp_types::NumberOrString::String(
             diagnostic.code.as_str().to_owned(),
         )),
-        code_description: Some(lsp_types::CodeDescription {
-            href: lsp_types::Url::parse(&diagnostic.code.url()).unwrap(),
-        }),
-        source: Some("rust-analyzer".to_owned()),
+        code_description: lsp_types::Url::parse(&diagnostic.code.url())
+            .ok()
+            .map(|href| lsp_types::CodeDescription { href }),
+        source: Some("wgsl-analyzer".to_owned()),
         message: diagnostic.message,
         related_information: None,
         tags: diagnostic
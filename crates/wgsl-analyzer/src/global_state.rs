@@ -1,5 +1,6 @@
 use std::{
-    sync::{Arc, RwLock},
+    path::PathBuf,
+    sync::{Arc, Mutex, RwLock},
     time::Instant,
 };
 
@@ -10,7 +11,8 @@ use lsp_types::Url;
 use nohash_hasher::IntMap;
 use parking_lot::RwLockWriteGuard;
 use rustc_hash::FxHashMap;
-use vfs::{AbsPathBuf, FileId, Vfs};
+use vfs::{AbsPathBuf, FileId, Vfs, VfsPath};
+use wgsl_formatter::{CONFIG_FILE_NAMES, FormattingOptions};
 
 use crate::{
     Result,
@@ -80,6 +82,9 @@ pub(crate) struct GlobalState {
     pub(crate) config: Arc<Config>,
     pub(crate) config_errors: Option<ConfigErrors>,
     pub(crate) source_root_config: SourceRootConfig,
+    /// The options of the `wgslfmt.toml` which applies to each directory, so that formatting
+    /// does not read the file system. Cleared when a configuration file changes.
+    pub(crate) formatting_options: FormattingOptionsCache,
     // `workspaces` field stores the data we actually use, while the `OperationQueue`
     // stores the result of the last fetch.
     // If the fetch (partially) fails, we do not update the current value.
@@ -105,6 +110,8 @@ pub(crate) struct GlobalState {
     pub(crate) deferred_task_queue: TaskQueue,
 }
 
+pub(crate) type FormattingOptionsCache = Arc<Mutex<FxHashMap<PathBuf, Arc<FormattingOptions>>>>;
+
 /// An immutable snapshot of the world's state at a point in time.
 pub(crate) struct GlobalStateSnapshot {
     pub config: Arc<Config>,
@@ -114,6 +121,7 @@ pub(crate) struct GlobalStateSnapshot {
     // pub(crate) semantic_tokens_cache: Arc<Mutex<FxHashMap<Url, SemanticTokens>>>,
     pub vfs: Arc<RwLock<(vfs::Vfs, IntMap<FileId, LineEndings>)>>,
     pub workspaces: Arc<[ProjectWorkspace]>,
+    pub(crate) formatting_options: FormattingOptionsCache,
     // used to signal semantic highlighting to fall back to syntax based highlighting until
     // proc-macros have been loaded
     // FIXME: Can we derive this from somewhere else?
@@ -197,6 +205,7 @@ impl GlobalState {
             // discover_sender,
             // discover_receiver,
             vfs: Arc::new(RwLock::new((vfs::Vfs::default(), IntMap::default()))),
+            formatting_options: FormattingOptionsCache::default(),
             vfs_config_version: 0,
             vfs_progress_config_version: 0,
             vfs_span: None,
@@ -242,6 +251,9 @@ impl GlobalState {
                     None
                 };
                 let path = vfs.file_path(file.file_id);
+                if is_formatter_config(path) {
+                    self.formatting_options.lock().unwrap().clear();
+                }
                 change.change_file(file.file_id, text, path.clone());
             }
 
@@ -261,6 +273,7 @@ impl GlobalState {
             workspaces: Arc::clone(&self.workspaces),
             analysis: self.analysis_host.analysis(),
             vfs: Arc::clone(&self.vfs),
+            formatting_options: Arc::clone(&self.formatting_options),
             // check_fixes: Arc::clone(&self.diagnostics.check_fixes),
             // mem_docs: self.mem_docs.clone(),
             // semantic_tokens_cache: Arc::clone(&self.semantic_tokens_cache),
@@ -387,3 +400,14 @@ pub(crate) fn url_to_file_id(
     vfs.file_id(&path)
         .ok_or_else(|| anyhow::anyhow!("file not found: {}", path))
 }
+
+/// Whether the file is a `wgslfmt.toml`, whose changes invalidate the cached formatting options.
+pub(crate) fn is_formatter_config(path: &VfsPath) -> bool {
+    path.name_and_extension().is_some_and(|(name, extension)| {
+        let file_name = match extension {
+            Some(extension) => format!("{name}.{extension}"),
+            None => name.to_owned(),
+        };
+        CONFIG_FILE_NAMES.contains(&file_name.as_str())
+    })
+}
@@ -18,7 +18,7 @@ use crate::{
     Result, // target_spec::TargetSpec,
     // try_default,
     config::{Config, ConfigChange},
-    global_state::GlobalState,
+    global_state::{GlobalState, is_formatter_config},
     in_memory_documents::DocumentData,
     lsp::{from_proto, utilities::apply_document_changes},
     reload,
@@ -181,6 +181,10 @@ pub(crate) fn handle_did_change_watched_files(
     parameters: DidChangeWatchedFilesParams,
 ) -> anyhow::Result<()> {
     for change in parameters.changes.iter().unique_by(|&it| &it.uri) {
+        if from_proto::vfs_path(&change.uri).is_ok_and(|path| is_formatter_config(&path)) {
+            state.formatting_options.lock().unwrap().clear();
+            continue;
+        }
         if let Ok(path) = from_proto::absolute_path(&change.uri) {
             state.loader.handle.invalidate(path);
         }
@@ -3,17 +3,21 @@
     reason = "handlers should have a specific signature"
 )]
 
-use base_db::{FileRange, TextRange};
+use std::sync::Arc;
+
+use base_db::{FilePosition, FileRange, TextRange, TextSize};
 use hir::diagnostics::DiagnosticsConfig;
-use ide::{HoverResult, diagnostics::Severity};
+use ide::{FormattingOptions, GotoDefinition, HoverResult};
 use lsp_types::{
-    DiagnosticRelatedInformation, DiagnosticTag, GotoDefinitionResponse, LanguageString,
-    MarkedString, TextDocumentIdentifier,
+    DiagnosticRelatedInformation, DiagnosticTag, GotoDefinitionResponse, InsertTextFormat,
+    LanguageString, MarkedString, TextDocumentIdentifier,
 };
+use text_edit::Indel;
 use vfs::FileId;
 
 use crate::{
     Result,
+    diagnostics::diagnostic_severity,
     global_state::GlobalStateSnapshot,
     lsp::{extensions, from_proto, to_proto},
     try_default,
@@ -24,19 +28,203 @@ pub(crate) fn handle_goto_definition(
     parameters: lsp_types::GotoDefinitionParams,
 ) -> Result<Option<GotoDefinitionResponse>> {
     let position = from_proto::file_position(&snap, &parameters.text_document_position_params)?;
-    let file_id = position.file_id;
-    let Some(nav_target) = snap.analysis.goto_definition(position)? else {
+    let Some(definition) = snap.analysis.goto_definition(position)? else {
+        return Ok(None);
+    };
+
+    let response = match definition {
+        GotoDefinition::Declarations(targets) => {
+            let mut locations = targets
+                .iter()
+                .map(|target| {
+                    let range = FileRange {
+                        file_id: target.file_id,
+                        range: target.focus_or_full_range(),
+                    };
+                    to_proto::location(&snap, range)
+                })
+                .collect::<Result<Vec<_>>>()?;
+            if locations.len() == 1 {
+                GotoDefinitionResponse::Scalar(locations.remove(0))
+            } else {
+                GotoDefinitionResponse::Array(locations)
+            }
+        },
+        GotoDefinition::Builtin(document) => {
+            GotoDefinitionResponse::Scalar(to_proto::builtin_document_location(&snap, &document))
+        },
+    };
+    Ok(Some(response))
+}
+
+pub(crate) fn handle_goto_type_definition(
+    snap: GlobalStateSnapshot,
+    parameters: lsp_types::request::GotoTypeDefinitionParams,
+) -> Result<Option<lsp_types::request::GotoTypeDefinitionResponse>> {
+    let position = from_proto::file_position(&snap, &parameters.text_document_position_params)?;
+    let Some(target) = snap.analysis.goto_type_definition(position)? else {
         return Ok(None);
     };
 
     let range = FileRange {
-        file_id: nav_target.file_id,
-        range: nav_target.focus_or_full_range(),
+        file_id: target.file_id,
+        range: target.focus_or_full_range(),
     };
     let location = to_proto::location(&snap, range)?;
-    let response = GotoDefinitionResponse::Scalar(location);
+    Ok(Some(GotoDefinitionResponse::Scalar(location)))
+}
 
-    Ok(Some(response))
+pub(crate) fn handle_code_action(
+    snap: GlobalStateSnapshot,
+    parameters: lsp_types::CodeActionParams,
+) -> Result<Option<Vec<extensions::CodeAction>>> {
+    let file_id = from_proto::file_id(&snap, &parameters.text_document.uri)?;
+    let line_index = snap.file_line_index(file_id)?;
+    let range = from_proto::text_range(&line_index, parameters.range)?;
+    let position = FilePosition {
+        file_id,
+        offset: range.start(),
+    };
+    let actions = snap
+        .analysis
+        .rust_mirrors(position)?
+        .into_iter()
+        .map(to_proto::rust_mirror_code_action)
+        .collect();
+    Ok(Some(actions))
+}
+
+pub(crate) fn handle_call_hierarchy_prepare(
+    snap: GlobalStateSnapshot,
+    parameters: lsp_types::CallHierarchyPrepareParams,
+) -> Result<Option<Vec<lsp_types::CallHierarchyItem>>> {
+    let position = from_proto::file_position(&snap, &parameters.text_document_position_params)?;
+    let Some(item) = snap.analysis.prepare_call_hierarchy(position)? else {
+        return Ok(None);
+    };
+    Ok(Some(vec![to_proto::call_hierarchy_item(&snap, &item)?]))
+}
+
+pub(crate) fn handle_call_hierarchy_incoming(
+    snap: GlobalStateSnapshot,
+    parameters: lsp_types::CallHierarchyIncomingCallsParams,
+) -> Result<Option<Vec<lsp_types::CallHierarchyIncomingCall>>> {
+    let position = call_hierarchy_item_position(&snap, parameters.item)?;
+    let Some(calls) = snap.analysis.incoming_calls(position)? else {
+        return Ok(None);
+    };
+
+    let mut result = Vec::with_capacity(calls.len());
+    for call in calls {
+        result.push(lsp_types::CallHierarchyIncomingCall {
+            from: to_proto::call_hierarchy_item(&snap, &call.item)?,
+            from_ranges: to_proto::call_ranges(&snap, &call.ranges)?,
+        });
+    }
+    Ok(Some(result))
+}
+
+/// Lists the calls of builtin functions after the calls of user functions.
+pub(crate) fn handle_call_hierarchy_outgoing(
+    snap: GlobalStateSnapshot,
+    parameters: lsp_types::CallHierarchyOutgoingCallsParams,
+) -> Result<Option<Vec<lsp_types::CallHierarchyOutgoingCall>>> {
+    let position = call_hierarchy_item_position(&snap, parameters.item)?;
+    let Some(calls) = snap.analysis.outgoing_calls(position)? else {
+        return Ok(None);
+    };
+
+    let mut result = Vec::with_capacity(calls.functions.len() + calls.builtins.len());
+    for call in calls.functions {
+        result.push(lsp_types::CallHierarchyOutgoingCall {
+            to: to_proto::call_hierarchy_item(&snap, &call.item)?,
+            from_ranges: to_proto::call_ranges(&snap, &call.ranges)?,
+        });
+    }
+    for call in calls.builtins {
+        let Some(&first) = call.ranges.first() else {
+            continue;
+        };
+        result.push(lsp_types::CallHierarchyOutgoingCall {
+            to: to_proto::builtin_call_hierarchy_item(&snap, &call.name, first)?,
+            from_ranges: to_proto::call_ranges(&snap, &call.ranges)?,
+        });
+    }
+    Ok(Some(result))
+}
+
+fn call_hierarchy_item_position(
+    snap: &GlobalStateSnapshot,
+    item: lsp_types::CallHierarchyItem,
+) -> Result<FilePosition> {
+    let document_position = lsp_types::TextDocumentPositionParams::new(
+        TextDocumentIdentifier::new(item.uri),
+        item.selection_range.start,
+    );
+    from_proto::file_position(snap, &document_position)
+}
+
+pub(crate) fn handle_folding_range(
+    snap: GlobalStateSnapshot,
+    parameters: lsp_types::FoldingRangeParams,
+) -> Result<Option<Vec<lsp_types::FoldingRange>>> {
+    let file_id = from_proto::file_id(&snap, &parameters.text_document.uri)?;
+    let line_index = snap.file_line_index(file_id)?;
+    let line_folding_only = snap.config.line_folding_only();
+    let folds = snap
+        .analysis
+        .folding_ranges(file_id)?
+        .iter()
+        .map(|fold| to_proto::folding_range(&line_index, line_folding_only, fold))
+        .collect();
+    Ok(Some(folds))
+}
+
+pub(crate) fn handle_selection_range(
+    snap: GlobalStateSnapshot,
+    parameters: lsp_types::SelectionRangeParams,
+) -> Result<Option<Vec<lsp_types::SelectionRange>>> {
+    let file_id = from_proto::file_id(&snap, &parameters.text_document.uri)?;
+    let line_index = snap.file_line_index(file_id)?;
+    let mut selection_ranges = Vec::with_capacity(parameters.positions.len());
+    for position in parameters.positions {
+        let offset = from_proto::offset(&line_index, position)?;
+        let ranges = snap
+            .analysis
+            .selection_ranges(FilePosition { file_id, offset })?;
+        // Starting from the outermost range, every range is the parent of the next one.
+        let selection_range = ranges.iter().rev().fold(None, |parent, &range| {
+            Some(lsp_types::SelectionRange {
+                range: to_proto::range(&line_index, range),
+                parent: parent.map(Box::new),
+            })
+        });
+        selection_ranges.push(selection_range.unwrap_or(lsp_types::SelectionRange {
+            range: lsp_types::Range::new(position, position),
+            parent: None,
+        }));
+    }
+    Ok(Some(selection_ranges))
+}
+
+pub(crate) fn handle_document_highlight(
+    snap: GlobalStateSnapshot,
+    parameters: lsp_types::DocumentHighlightParams,
+) -> Result<Option<Vec<lsp_types::DocumentHighlight>>> {
+    let position = from_proto::file_position(&snap, &parameters.text_document_position_params)?;
+    let line_index = snap.file_line_index(position.file_id)?;
+    let Some(highlights) = snap.analysis.highlight_related(position)? else {
+        return Ok(None);
+    };
+
+    let highlights = highlights
+        .into_iter()
+        .map(|highlight| lsp_types::DocumentHighlight {
+            range: to_proto::range(&line_index, highlight.range),
+            kind: Some(to_proto::document_highlight_kind(highlight.category)),
+        })
+        .collect();
+    Ok(Some(highlights))
 }
 
 pub(crate) fn handle_completion(
@@ -66,7 +254,83 @@ pub(crate) fn handle_formatting(
     parameters: lsp_types::DocumentFormattingParams,
 ) -> Result<Option<Vec<lsp_types::TextEdit>>> {
     let file_id = from_proto::file_id(&snap, &parameters.text_document.uri)?;
-    let Some(node) = snap.analysis.format(file_id, None)? else {
+    formatting_edits(&snap, file_id, None)
+}
+
+pub(crate) fn handle_range_formatting(
+    snap: GlobalStateSnapshot,
+    parameters: lsp_types::DocumentRangeFormattingParams,
+) -> Result<Option<Vec<lsp_types::TextEdit>>> {
+    let file_id = from_proto::file_id(&snap, &parameters.text_document.uri)?;
+    let line_index = snap.file_line_index(file_id)?;
+    let range = from_proto::text_range(&line_index, parameters.range)?;
+    formatting_edits(&snap, file_id, Some(range))
+}
+
+pub(crate) fn handle_on_type_formatting(
+    snap: GlobalStateSnapshot,
+    parameters: lsp_types::DocumentOnTypeFormattingParams,
+) -> Result<Option<Vec<extensions::SnippetTextEdit>>> {
+    let position = from_proto::file_position(&snap, &parameters.text_document_position)?;
+    let line_index = snap.file_line_index(position.file_id)?;
+
+    let edits = match parameters.ch.as_str() {
+        "\n" => {
+            let options = formatting_options(&snap, position.file_id)?;
+            let Some(edit) = snap.analysis.on_enter(position, &options)? else {
+                return Ok(None);
+            };
+            let snippet_support = snap.config.caps().snippet_text_edit();
+            let new_text = if snippet_support {
+                edit.snippet
+            } else {
+                // Without snippets, the closing delimiter cannot be moved below the cursor.
+                edit.snippet
+                    .split("$0")
+                    .next()
+                    .unwrap_or_default()
+                    .to_owned()
+            };
+            let text_edit = to_proto::text_edit(&line_index, Indel::replace(edit.range, new_text));
+            vec![extensions::SnippetTextEdit {
+                range: text_edit.range,
+                new_text: text_edit.new_text,
+                insert_text_format: snippet_support.then_some(InsertTextFormat::SNIPPET),
+                annotation_id: None,
+            }]
+        },
+        // Reformat the block or statement which was just closed.
+        "}" | ";" => {
+            let Some(start) = position.offset.checked_sub(TextSize::of(';')) else {
+                return Ok(None);
+            };
+            let range = TextRange::new(start, position.offset);
+            let Some(edits) = formatting_edits(&snap, position.file_id, Some(range))? else {
+                return Ok(None);
+            };
+            edits
+                .into_iter()
+                .map(|edit| extensions::SnippetTextEdit {
+                    range: edit.range,
+                    new_text: edit.new_text,
+                    insert_text_format: None,
+                    annotation_id: None,
+                })
+                .collect()
+        },
+        _ => return Ok(None),
+    };
+    Ok(Some(edits))
+}
+
+/// The minimal edits which format the whole file, or the statements covering `range`.
+fn formatting_edits(
+    snap: &GlobalStateSnapshot,
+    file_id: FileId,
+    range: Option<TextRange>,
+) -> Result<Option<Vec<lsp_types::TextEdit>>> {
+    let options = formatting_options(snap, file_id)?;
+    let Some(node) = snap.analysis.format(file_id, range, &options)? else {
         return Ok(None);
     };
     let line_index = snap.file_line_index(file_id)?;
@@ -79,6 +343,29 @@ pub(crate) fn handle_formatting(
     Ok(Some(edits))
 }
 
+/// The options of the `wgslfmt.toml` which applies to the file, so that the
+/// editor formats exactly like `wgslfmt`. The file system is only searched once
+/// per directory.
+fn formatting_options(
+    snap: &GlobalStateSnapshot,
+    file_id: FileId,
+) -> Result<Arc<FormattingOptions>> {
+    let Ok(path) = snap.file_id_to_url(file_id).to_file_path() else {
+        return Ok(Arc::default());
+    };
+    let Some(directory) = path.parent() else {
+        return Ok(Arc::new(wgsl_formatter::options_for_file(&path)?));
+    };
+    // Holding the lock while reading the file keeps a concurrent invalidation from being lost.
+    let mut cache = snap.formatting_options.lock().unwrap();
+    if let Some(options) = cache.get(directory) {
+        return Ok(Arc::clone(options));
+    }
+    let options = Arc::new(wgsl_formatter::options_for_file(&path)?);
+    cache.insert(directory.to_path_buf(), Arc::clone(&options));
+    Ok(options)
+}
+
 pub(crate) fn handle_hover(
     snap: GlobalStateSnapshot,
     parameters: lsp_types::HoverParams,
@@ -91,7 +378,10 @@ pub(crate) fn handle_hover(
         range,
     };
 
-    let Some(result) = snap.analysis.hover(file_range)? else {
+    let source_root = snap.analysis.source_root_id(position.file_id)?;
+    let config = snap.config.data().diagnostics(Some(source_root));
+
+    let Some(result) = snap.analysis.hover(&config, file_range)? else {
         return Ok(None);
     };
 
@@ -133,6 +423,15 @@ pub(crate) fn full_source(
     Ok(source)
 }
 
+pub(crate) fn builtin_document(
+    snap: GlobalStateSnapshot,
+    parameters: extensions::BuiltinDocumentParameters,
+) -> Result<String> {
+    let name = from_proto::builtin_name(&parameters.uri)?;
+    let document = snap.analysis.builtin_document(&name)?;
+    Ok(document.map(|document| document.text).unwrap_or_default())
+}
+
 pub(crate) fn show_syntax_tree(
     snap: GlobalStateSnapshot,
     parameters: extensions::SyntaxTreeParameters,
@@ -172,26 +471,59 @@ pub(crate) fn handle_inlay_hints(
         parameters.range,
     );
 
+    let resolve_in = snap
+        .config
+        .caps()
+        .inlay_hints_resolve_provider()
+        .then_some(file_id);
+
     Ok(Some(
         snap.analysis
             .inlay_hints(&snap.config.data().inlay_hints(), file_id, range.ok())?
             .iter()
-            .map(|it| to_proto::inlay_hint(true, &line_index, it))
+            .map(|it| to_proto::inlay_hint(true, &line_index, resolve_in, it))
             .collect(),
     ))
 }
 
+pub(crate) fn handle_inlay_hint_resolve(
+    snap: GlobalStateSnapshot,
+    mut parameters: lsp_types::InlayHint,
+) -> Result<lsp_types::InlayHint> {
+    let Some(data) = parameters.data.take() else {
+        return Ok(parameters);
+    };
+    let resolve_data: extensions::InlayHintResolveData = serde_json::from_value(data)?;
+    let file_id = FileId::from_raw(resolve_data.file_id);
+    let line_index = snap.file_line_index(file_id)?;
+    let range = from_proto::text_range(&line_index, resolve_data.resolve_range)?;
+    let hash: u64 = resolve_data.hash.parse()?;
+
+    // The hints are computed again, as the file may have changed since.
+    let resolved = snap
+        .analysis
+        .inlay_hints(&snap.config.data().inlay_hints(), file_id, None)?
+        .into_iter()
+        .find(|hint| hint.range == range && to_proto::inlay_hint_hash(hint) == hash);
+    Ok(match resolved {
+        Some(hint) => to_proto::inlay_hint(true, &line_index, None, &hint),
+        None => parameters,
+    })
+}
+
 pub(crate) fn publish_diagnostics(
     snap: &GlobalStateSnapshot,
     config: &DiagnosticsConfig,
     file_id: FileId,
 ) -> Result<Vec<lsp_types::Diagnostic>> {
     let line_index = snap.file_line_index(file_id)?;
+    let map_config = snap.config.data().diagnostics_map();
     let diagnostics = snap.analysis.diagnostics(config, file_id)?;
 
     diagnostics
         .into_iter()
         .map(|diagnostic| {
+            let severity = diagnostic_severity(&map_config, &diagnostic);
             let related = diagnostic
                 .related
                 .into_iter()
@@ -204,10 +536,14 @@ pub(crate) fn publish_diagnostics(
                 .collect::<Result<Vec<_>>>()?;
             Ok(lsp_types::Diagnostic {
                 range: to_proto::range(&line_index, diagnostic.range),
-                severity: Some(diagnostic_severity(diagnostic.severity)),
-                code: None,
-                code_description: None,
-                source: None,
+                severity: Some(severity),
+                code: Some(lsp_types::NumberOrString::String(
+                    diagnostic.code.as_str().to_owned(),
+                )),
+                code_description: lsp_types::Url::parse(&diagnostic.code.url())
+                    .ok()
+                    .map(|href| lsp_types::CodeDescription { href }),
+                source: Some("wgsl-analyzer".to_owned()),
                 message: diagnostic.message,
                 related_information: (!related.is_empty()).then_some(related),
                 tags: diagnostic.unused.then(|| vec![DiagnosticTag::UNNECESSARY]),
@@ -217,13 +553,6 @@ pub(crate) fn publish_diagnostics(
         .collect()
 }
 
-const fn diagnostic_severity(severity: Severity) -> lsp_types::DiagnosticSeverity {
-    match severity {
-        Severity::Error => lsp_types::DiagnosticSeverity::ERROR,
-        Severity::WeakWarning => lsp_types::DiagnosticSeverity::HINT,
-    }
-}
-
 mod diff {
     //! Generate minimal `TextEdit`s from different text versions
     use dissimilar::Chunk;
@@ -30,6 +30,7 @@ pub fn server_capabilities(config: &Config) -> ServerCapabilities {
             TextDocumentSyncKind::INCREMENTAL,
         )),
         definition_provider: Some(OneOf::Left(true)),
+        type_definition_provider: Some(TypeDefinitionProviderCapability::Simple(true)),
         completion_provider: Some(CompletionOptions {
             completion_item: None,
             resolve_provider: None,
@@ -40,10 +41,27 @@ pub fn server_capabilities(config: &Config) -> ServerCapabilities {
             },
         }),
         document_formatting_provider: Some(OneOf::Left(true)),
+        document_range_formatting_provider: Some(OneOf::Left(true)),
+        document_on_type_formatting_provider: Some(DocumentOnTypeFormattingOptions {
+            first_trigger_character: "}".to_owned(),
+            more_trigger_character: Some(vec![";".to_owned(), "\n".to_owned()]),
+        }),
         hover_provider: Some(HoverProviderCapability::Simple(true)),
+        document_highlight_provider: Some(OneOf::Left(true)),
+        folding_range_provider: Some(FoldingRangeProviderCapability::Simple(true)),
+        selection_range_provider: Some(SelectionRangeProviderCapability::Simple(true)),
+        code_action_provider: Some(config.code_action_capabilities()),
         // rename_provider: Some(OneOf::Left(true)),
         // definition_provider: Some(OneOf::Left(true)),
-        inlay_hint_provider: Some(OneOf::Left(true)),
+        inlay_hint_provider: Some(OneOf::Right(InlayHintServerCapabilities::Options(
+            InlayHintOptions {
+                work_done_progress_options: WorkDoneProgressOptions {
+                    work_done_progress: None,
+                },
+                resolve_provider: Some(config.inlay_hints_resolve_provider()),
+            },
+        ))),
+        call_hierarchy_provider: Some(CallHierarchyServerCapability::Simple(true)),
         experimental: Some(json!({ "inlayHints": true })),
         ..Default::default()
     }
@@ -64,12 +82,10 @@ impl ClientCapabilities {
         fields_to_resolve != CompletionFieldsToResolve::empty()
     }
 
-    fn inlay_hints_resolve_provider(&self) -> bool {
-        let client_capabilities = self.inlay_hint_resolve_support_properties();
-        // let fields_to_resolve =
-        //     InlayFieldsToResolve::from_client_capabilities(&client_capabilities);
-        // fields_to_resolve != InlayFieldsToResolve::empty()
-        false
+    /// Only tooltips are resolved lazily.
+    pub fn inlay_hints_resolve_provider(&self) -> bool {
+        self.inlay_hint_resolve_support_properties()
+            .contains("tooltip")
     }
 
     fn experimental_bool(
@@ -144,7 +160,8 @@ impl ClientCapabilities {
                         CodeActionKind::REFACTOR_INLINE,
                         CodeActionKind::REFACTOR_REWRITE,
                     ]),
-                    resolve_provider: Some(true),
+                    // Code actions are complete, they do not need to be resolved.
+                    resolve_provider: None,
                     work_done_progress_options: WorkDoneProgressOptions::default(),
                 })
             })
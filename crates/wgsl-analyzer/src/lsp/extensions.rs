@@ -70,6 +70,24 @@ pub struct FullSourceParameters {
     pub text_document: TextDocumentIdentifier,
 }
 
+/// The scheme of the URLs of the generated documents of builtin functions.
+pub const BUILTIN_DOCUMENT_SCHEME: &str = "wgsl-analyzer-builtin";
+
+pub enum BuiltinDocument {}
+
+impl Request for BuiltinDocument {
+    type Params = BuiltinDocumentParameters;
+    type Result = String;
+
+    const METHOD: &'static str = "wgsl-analyzer/builtinDocument";
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct BuiltinDocumentParameters {
+    pub uri: Url,
+}
+
 pub enum RequestConfiguration {}
 
 impl Request for RequestConfiguration {
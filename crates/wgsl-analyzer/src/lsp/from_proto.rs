@@ -8,6 +8,7 @@ use crate::{
     Result,
     global_state::GlobalStateSnapshot,
     line_index::{LineIndex, OffsetEncoding, PositionEncoding},
+    lsp::extensions,
 };
 
 pub(crate) fn absolute_path(url: &lsp_types::Url) -> anyhow::Result<AbsPathBuf> {
@@ -21,6 +22,19 @@ pub(crate) fn vfs_path(url: &lsp_types::Url) -> Result<vfs::VfsPath> {
     absolute_path(url).map(vfs::VfsPath::from)
 }
 
+/// The name of the builtin function of a generated document.
+pub(crate) fn builtin_name(url: &lsp_types::Url) -> Result<String> {
+    if url.scheme() != extensions::BUILTIN_DOCUMENT_SCHEME {
+        return Err(format_err!("not a builtin document: {url}"));
+    }
+    let name = url
+        .path()
+        .trim_start_matches('/')
+        .strip_suffix(".wgsl")
+        .ok_or_else(|| format_err!("not a builtin document: {url}"))?;
+    Ok(name.to_owned())
+}
+
 pub(crate) fn offset(
     line_index: &LineIndex,
     position: lsp_types::Position,
@@ -1,10 +1,18 @@
-use std::path;
+use std::{
+    hash::{Hash as _, Hasher as _},
+    path,
+    sync::Arc,
+};
 
 use base_db::{FileRange, TextRange, TextSize};
-use ide::inlay_hints::{InlayHint, InlayKind};
+use ide::{
+    BuiltinDocument, CallHierarchyItem, Fold, FoldKind, ReferenceCategory, RustMirror,
+    inlay_hints::{InlayHint, InlayKind},
+};
 use ide_completion::item::{CompletionItem, CompletionItemKind, CompletionRelevance};
 use itertools::Itertools as _;
 use paths::{AbsPath, Utf8Component, Utf8Prefix};
+use rustc_hash::FxHasher;
 use text_edit::{Indel, TextEdit};
 use vfs::FileId;
 
@@ -12,6 +20,7 @@ use crate::{
     Result,
     global_state::GlobalStateSnapshot,
     line_index::{LineEndings, LineIndex, OffsetEncoding, PositionEncoding},
+    lsp::extensions,
 };
 
 /// Returns a `Url` object from a given path, will lowercase drive letters if present.
@@ -90,6 +99,150 @@ pub(crate) fn location(
     Ok(loc)
 }
 
+/// The URL of the generated document of a builtin function, which clients show with a
+/// `wgsl-analyzer/builtinDocument` request.
+pub(crate) fn builtin_document_url(name: &str) -> lsp_types::Url {
+    let scheme = extensions::BUILTIN_DOCUMENT_SCHEME;
+    lsp_types::Url::parse(&format!("{scheme}:///{name}.wgsl")).unwrap()
+}
+
+pub(crate) fn builtin_document_location(
+    snap: &GlobalStateSnapshot,
+    document: &BuiltinDocument,
+) -> lsp_types::Location {
+    let line_index = LineIndex {
+        index: Arc::new(ide::LineIndex::new(&document.text)),
+        endings: LineEndings::Unix,
+        encoding: snap.config.caps().negotiated_encoding(),
+    };
+    lsp_types::Location::new(
+        builtin_document_url(&document.name),
+        range(&line_index, document.focus_range),
+    )
+}
+
+pub(crate) fn call_hierarchy_item(
+    snap: &GlobalStateSnapshot,
+    item: &CallHierarchyItem,
+) -> Result<lsp_types::CallHierarchyItem> {
+    let target = &item.target;
+    let line_index = snap.file_line_index(target.file_id)?;
+    Ok(lsp_types::CallHierarchyItem {
+        name: item.name.clone(),
+        kind: lsp_types::SymbolKind::FUNCTION,
+        tags: None,
+        detail: item.is_entry_point.then(|| "entry point".to_owned()),
+        uri: url(snap, target.file_id),
+        range: range(&line_index, target.full_range),
+        selection_range: range(&line_index, target.focus_or_full_range()),
+        data: None,
+    })
+}
+
+/// Builtins have no declaration, so their item points at the first call.
+pub(crate) fn builtin_call_hierarchy_item(
+    snap: &GlobalStateSnapshot,
+    name: &str,
+    call: FileRange,
+) -> Result<lsp_types::CallHierarchyItem> {
+    let line_index = snap.file_line_index(call.file_id)?;
+    let range = range(&line_index, call.range);
+    Ok(lsp_types::CallHierarchyItem {
+        name: name.to_owned(),
+        kind: lsp_types::SymbolKind::FUNCTION,
+        tags: None,
+        detail: Some("builtin".to_owned()),
+        uri: url(snap, call.file_id),
+        range,
+        selection_range: range,
+        data: None,
+    })
+}
+
+/// The ranges of the calls, which are all in the same file.
+pub(crate) fn call_ranges(
+    snap: &GlobalStateSnapshot,
+    calls: &[FileRange],
+) -> Result<Vec<lsp_types::Range>> {
+    let Some(first) = calls.first() else {
+        return Ok(Vec::new());
+    };
+    let line_index = snap.file_line_index(first.file_id)?;
+    Ok(calls
+        .iter()
+        .map(|call| range(&line_index, call.range))
+        .collect())
+}
+
+/// Clients which only fold whole lines get block folds ending on the line before the closing
+/// brace, so that the brace stays visible.
+pub(crate) fn folding_range(
+    line_index: &LineIndex,
+    line_folding_only: bool,
+    fold: &Fold,
+) -> lsp_types::FoldingRange {
+    let kind = match fold.kind {
+        FoldKind::Comment => Some(lsp_types::FoldingRangeKind::Comment),
+        FoldKind::Imports => Some(lsp_types::FoldingRangeKind::Imports),
+        FoldKind::Region => Some(lsp_types::FoldingRangeKind::Region),
+        FoldKind::Block => None,
+    };
+    let range = range(line_index, fold.range);
+    if line_folding_only {
+        let end_line = if fold.kind == FoldKind::Block && range.end.line > range.start.line {
+            range.end.line - 1
+        } else {
+            range.end.line
+        };
+        lsp_types::FoldingRange {
+            start_line: range.start.line,
+            start_character: None,
+            end_line,
+            end_character: None,
+            kind,
+            collapsed_text: None,
+        }
+    } else {
+        lsp_types::FoldingRange {
+            start_line: range.start.line,
+            start_character: Some(range.start.character),
+            end_line: range.end.line,
+            end_character: Some(range.end.character),
+            kind,
+            collapsed_text: None,
+        }
+    }
+}
+
+/// Opens the mirror in a new Rust document, through a command of the client.
+pub(crate) fn rust_mirror_code_action(mirror: RustMirror) -> extensions::CodeAction {
+    let title = format!(
+        "Generate Rust mirror of `{}` ({} layout)",
+        mirror.name,
+        mirror.address_space.as_str()
+    );
+    extensions::CodeAction {
+        command: Some(lsp_types::Command {
+            title: title.clone(),
+            command: "wgsl-analyzer.openRustMirror".to_owned(),
+            arguments: Some(vec![serde_json::Value::String(mirror.text)]),
+        }),
+        title,
+        kind: Some(lsp_types::CodeActionKind::REFACTOR),
+        ..Default::default()
+    }
+}
+
+pub(crate) fn document_highlight_kind(
+    category: Option<ReferenceCategory>
+) -> lsp_types::DocumentHighlightKind {
+    match category {
+        Some(ReferenceCategory::Read) => lsp_types::DocumentHighlightKind::READ,
+        Some(ReferenceCategory::Write) => lsp_types::DocumentHighlightKind::WRITE,
+        None => lsp_types::DocumentHighlightKind::TEXT,
+    }
+}
+
 pub(crate) fn completion_items(
     // config: &Config,
     line_index: &LineIndex,
@@ -244,9 +397,11 @@ pub(crate) const fn completion_item_kind(
         CompletionItemKind::Snippet => lsp_types::CompletionItemKind::SNIPPET,
         CompletionItemKind::Constant => lsp_types::CompletionItemKind::CONSTANT,
         CompletionItemKind::Module => lsp_types::CompletionItemKind::MODULE,
-        CompletionItemKind::TypeAlias | CompletionItemKind::Struct => {
-            lsp_types::CompletionItemKind::STRUCT
-        },
+        CompletionItemKind::TypeAlias
+        | CompletionItemKind::Struct
+        | CompletionItemKind::BuiltinType => lsp_types::CompletionItemKind::STRUCT,
+        CompletionItemKind::Attribute => lsp_types::CompletionItemKind::PROPERTY,
+        CompletionItemKind::EnumMember => lsp_types::CompletionItemKind::ENUM_MEMBER,
     }
 }
 
@@ -292,40 +447,96 @@ pub(crate) fn completion_text_edit(
     }
 }
 
+/// When `resolve_in` is the file of the hint, the tooltip is left for the client to resolve.
 pub(crate) fn inlay_hint(
     render_colons: bool,
     line_index: &LineIndex,
+    resolve_in: Option<FileId>,
     inlay_hint: &InlayHint,
 ) -> lsp_types::InlayHint {
+    let (tooltip, data) = match (&inlay_hint.tooltip, resolve_in) {
+        (Some(_), Some(file_id)) => {
+            let data = extensions::InlayHintResolveData {
+                file_id: file_id.index(),
+                hash: inlay_hint_hash(inlay_hint).to_string(),
+                resolve_range: range(line_index, inlay_hint.range),
+                version: None,
+            };
+            (None, Some(serde_json::to_value(data).unwrap()))
+        },
+        (Some(tooltip), None) => (
+            Some(lsp_types::InlayHintTooltip::MarkupContent(
+                lsp_types::MarkupContent {
+                    kind: lsp_types::MarkupKind::Markdown,
+                    value: tooltip.clone(),
+                },
+            )),
+            None,
+        ),
+        (None, _) => (None, None),
+    };
+
     lsp_types::InlayHint {
         label: lsp_types::InlayHintLabel::String(match inlay_hint.kind {
             InlayKind::ParameterHint if render_colons => format!("{}:", inlay_hint.label),
             InlayKind::TypeHint if render_colons => format!(": {}", inlay_hint.label),
-            InlayKind::TypeHint | InlayKind::ParameterHint | InlayKind::StructLayoutHint => {
-                inlay_hint.label.to_string()
-            },
+            InlayKind::TypeHint
+            | InlayKind::ParameterHint
+            | InlayKind::StructLayoutHint
+            | InlayKind::StructSizeHint
+            | InlayKind::ConstValueHint
+            | InlayKind::ConversionHint
+            | InlayKind::ClosingBraceHint
+            | InlayKind::AccessModeHint => inlay_hint.label.to_string(),
         }),
         position: match inlay_hint.kind {
-            InlayKind::TypeHint => position(line_index, inlay_hint.range.end()),
-            InlayKind::StructLayoutHint | InlayKind::ParameterHint => {
+            InlayKind::TypeHint
+            | InlayKind::StructSizeHint
+            | InlayKind::ConstValueHint
+            | InlayKind::ConversionHint
+            | InlayKind::ClosingBraceHint => position(line_index, inlay_hint.range.end()),
+            InlayKind::StructLayoutHint | InlayKind::ParameterHint | InlayKind::AccessModeHint => {
                 position(line_index, inlay_hint.range.start())
             },
         },
-        data: None,
+        data,
         text_edits: None,
         kind: match inlay_hint.kind {
             InlayKind::ParameterHint => Some(lsp_types::InlayHintKind::PARAMETER),
-            InlayKind::TypeHint => Some(lsp_types::InlayHintKind::TYPE),
-            InlayKind::StructLayoutHint => None,
+            InlayKind::TypeHint | InlayKind::ConversionHint => Some(lsp_types::InlayHintKind::TYPE),
+            InlayKind::StructLayoutHint
+            | InlayKind::StructSizeHint
+            | InlayKind::ConstValueHint
+            | InlayKind::ClosingBraceHint
+            | InlayKind::AccessModeHint => None,
         },
-        tooltip: None,
+        tooltip,
         padding_left: Some(match inlay_hint.kind {
             InlayKind::TypeHint => !render_colons,
-            InlayKind::ParameterHint | InlayKind::StructLayoutHint => false,
+            InlayKind::StructSizeHint
+            | InlayKind::ConstValueHint
+            | InlayKind::ConversionHint
+            | InlayKind::ClosingBraceHint => true,
+            InlayKind::ParameterHint | InlayKind::StructLayoutHint | InlayKind::AccessModeHint => {
+                false
+            },
         }),
         padding_right: Some(match inlay_hint.kind {
-            InlayKind::TypeHint => false,
-            InlayKind::ParameterHint | InlayKind::StructLayoutHint => true,
+            InlayKind::TypeHint
+            | InlayKind::StructSizeHint
+            | InlayKind::ConstValueHint
+            | InlayKind::ConversionHint
+            | InlayKind::ClosingBraceHint => false,
+            InlayKind::ParameterHint | InlayKind::StructLayoutHint | InlayKind::AccessModeHint => {
+                true
+            },
         }),
     }
 }
+
+/// Identifies a hint when its tooltip is resolved.
+pub(crate) fn inlay_hint_hash(inlay_hint: &InlayHint) -> u64 {
+    let mut hasher = FxHasher::default();
+    inlay_hint.hash(&mut hasher);
+    hasher.finish()
+}
@@ -746,16 +746,50 @@ impl GlobalState {
             .on::<NO_RETRY, lsp_types::request::GotoDefinition>(
                 handlers::request::handle_goto_definition,
             )
+            .on::<NO_RETRY, lsp_types::request::GotoTypeDefinition>(
+                handlers::request::handle_goto_type_definition,
+            )
+            .on::<NO_RETRY, lsp::extensions::CodeActionRequest>(
+                handlers::request::handle_code_action,
+            )
             .on::<RETRY, lsp_types::request::Completion>(handlers::request::handle_completion)
+            .on::<NO_RETRY, lsp_types::request::CallHierarchyPrepare>(
+                handlers::request::handle_call_hierarchy_prepare,
+            )
+            .on::<NO_RETRY, lsp_types::request::CallHierarchyIncomingCalls>(
+                handlers::request::handle_call_hierarchy_incoming,
+            )
+            .on::<NO_RETRY, lsp_types::request::CallHierarchyOutgoingCalls>(
+                handlers::request::handle_call_hierarchy_outgoing,
+            )
             .on_fmt_thread::<lsp_types::request::Formatting>(handlers::request::handle_formatting)
+            .on_fmt_thread::<lsp_types::request::RangeFormatting>(
+                handlers::request::handle_range_formatting,
+            )
+            .on_fmt_thread::<lsp::extensions::OnTypeFormatting>(
+                handlers::request::handle_on_type_formatting,
+            )
             .on::<NO_RETRY, lsp_types::request::HoverRequest>(handlers::request::handle_hover)
+            .on::<NO_RETRY, lsp_types::request::DocumentHighlightRequest>(
+                handlers::request::handle_document_highlight,
+            )
+            .on::<NO_RETRY, lsp_types::request::FoldingRangeRequest>(
+                handlers::request::handle_folding_range,
+            )
+            .on::<NO_RETRY, lsp_types::request::SelectionRangeRequest>(
+                handlers::request::handle_selection_range,
+            )
             .on::<NO_RETRY, lsp_types::request::Shutdown>(handlers::request::handle_shutdown)
             .on::<NO_RETRY, lsp_types::request::InlayHintRequest>(
                 handlers::request::handle_inlay_hints,
             )
+            .on::<NO_RETRY, lsp_types::request::InlayHintResolveRequest>(
+                handlers::request::handle_inlay_hint_resolve,
+            )
             .on::<NO_RETRY, lsp::extensions::SyntaxTree>(handlers::request::show_syntax_tree)
             .on::<NO_RETRY, lsp::extensions::DebugCommand>(handlers::request::debug_command)
             .on::<NO_RETRY, lsp::extensions::FullSource>(handlers::request::full_source)
+            .on::<NO_RETRY, lsp::extensions::BuiltinDocument>(handlers::request::builtin_document)
             .finish();
     }
 
@@ -904,5 +938,14 @@ impl GlobalState {
                     Durability::HIGH,
                 );
         }
+
+        if old_config.data().edition != self.config.data().edition {
+            self.analysis_host
+                .raw_database_mut()
+                .set_default_edition_with_durability(
+                    self.config.data().edition.to_edition(),
+                    Durability::HIGH,
+                );
+        }
     }
 }
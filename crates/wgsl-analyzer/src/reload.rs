@@ -176,13 +176,21 @@ impl GlobalState {
         let _p = tracing::info_span!("GlobalState::switch_workspaces").entered();
         tracing::info!(%cause, "will switch workspaces");
 
-        let glob_pattern = format!("{}/**/*.wgsl", self.config.root_path());
+        let root_path = self.config.root_path();
+        // `wgslfmt.toml` files are watched to invalidate the cached formatting options.
+        let glob_patterns = [
+            format!("{root_path}/**/*.{{wgsl,wesl}}"),
+            format!("{root_path}/**/{{wgslfmt,.wgslfmt}}.toml"),
+        ];
 
         let registration_options = lsp_types::DidChangeWatchedFilesRegistrationOptions {
-            watchers: vec![lsp_types::FileSystemWatcher {
-                glob_pattern: lsp_types::GlobPattern::String(glob_pattern),
-                kind: None,
-            }],
+            watchers: glob_patterns
+                .into_iter()
+                .map(|glob_pattern| lsp_types::FileSystemWatcher {
+                    glob_pattern: lsp_types::GlobPattern::String(glob_pattern),
+                    kind: None,
+                })
+                .collect(),
         };
         let registration = lsp_types::Registration {
             id: "workspace/didChangeWatchedFiles".to_owned(),
@@ -0,0 +1,136 @@
+//! Loading [`FormattingOptions`] from `wgslfmt.toml` files.
+//!
+//! Both `wgslfmt` and the language server use the file closest to the formatted
+//! file, so that formatting in an editor and in CI agree.
+
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde_derive::Deserialize;
+
+use crate::{AttributePlacement, BraceStyle, FormattingOptions, Policy};
+
+/// The names of configuration files, in order of precedence within a directory.
+pub const CONFIG_FILE_NAMES: [&str; 2] = ["wgslfmt.toml", ".wgslfmt.toml"];
+
+/// The contents of a configuration file. Options which are not set keep their defaults.
+///
+/// ```toml
+/// max_width = 100
+/// hard_tabs = false
+/// tab_spaces = 4
+/// trailing_commas = "insert"
+/// attribute_placement = "own_line"
+/// modernize_attributes = true
+/// blank_lines_upper_bound = 1
+/// blank_lines_lower_bound = 1
+/// brace_style = "same_line"
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub max_width: Option<usize>,
+    /// Indents with tabs instead of spaces.
+    pub hard_tabs: Option<bool>,
+    /// The number of spaces per indentation level, unless `hard_tabs` is set.
+    pub tab_spaces: Option<usize>,
+    pub trailing_commas: Option<Policy>,
+    pub attribute_placement: Option<AttributePlacement>,
+    pub modernize_attributes: Option<bool>,
+    pub blank_lines_upper_bound: Option<usize>,
+    pub blank_lines_lower_bound: Option<usize>,
+    pub brace_style: Option<BraceStyle>,
+}
+
+impl Config {
+    pub fn from_toml(text: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(text)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        let text =
+            fs::read_to_string(path).map_err(|error| ConfigError::Io(path.to_path_buf(), error))?;
+        Self::from_toml(&text).map_err(|error| ConfigError::Parse(path.to_path_buf(), error))
+    }
+
+    /// Overrides the options which are set in this configuration.
+    pub fn apply(
+        &self,
+        options: &mut FormattingOptions,
+    ) {
+        if let Some(max_width) = self.max_width {
+            options.max_width = max_width;
+        }
+        match (self.hard_tabs, self.tab_spaces) {
+            (Some(true), _) => options.indent_symbol = "\t".to_owned(),
+            (_, Some(spaces)) => options.indent_symbol = " ".repeat(spaces),
+            (Some(false), None) => options.indent_symbol = " ".repeat(4),
+            (None, None) => {},
+        }
+        if let Some(trailing_commas) = self.trailing_commas {
+            options.trailing_commas = trailing_commas;
+        }
+        if let Some(attribute_placement) = self.attribute_placement {
+            options.attribute_placement = attribute_placement;
+        }
+        if let Some(modernize_attributes) = self.modernize_attributes {
+            options.modernize_attributes = modernize_attributes;
+        }
+        if let Some(upper_bound) = self.blank_lines_upper_bound {
+            options.blank_lines_upper_bound = upper_bound;
+        }
+        if let Some(lower_bound) = self.blank_lines_lower_bound {
+            options.blank_lines_lower_bound = lower_bound;
+        }
+        if let Some(brace_style) = self.brace_style {
+            options.brace_style = brace_style;
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(PathBuf, io::Error),
+    Parse(PathBuf, toml::de::Error),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            Self::Io(path, error) => write!(f, "failed to read {}: {error}", path.display()),
+            Self::Parse(path, error) => {
+                write!(f, "invalid configuration in {}: {error}", path.display())
+            },
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// Finds the configuration file for `path` in its directory or the closest ancestor.
+pub fn find_config_file(path: &Path) -> Option<PathBuf> {
+    let path = std::path::absolute(path).ok()?;
+    let directory = if path.is_dir() {
+        path.as_path()
+    } else {
+        path.parent()?
+    };
+    directory
+        .ancestors()
+        .flat_map(|directory| CONFIG_FILE_NAMES.map(|name| directory.join(name)))
+        .find(|candidate| candidate.is_file())
+}
+
+/// The default options, overridden by the configuration file for `path`, if there is one.
+pub fn options_for_file(path: &Path) -> Result<FormattingOptions, ConfigError> {
+    let mut options = FormattingOptions::default();
+    if let Some(config_path) = find_config_file(path) {
+        Config::load(&config_path)?.apply(&mut options);
+    }
+    Ok(options)
+}
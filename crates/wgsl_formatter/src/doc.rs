@@ -0,0 +1,201 @@
+//! A Wadler-style document IR and its width-aware printer.
+//!
+//! A [`Doc`] describes text together with the places where it may be broken
+//! into multiple lines. A [`Doc::Group`] is printed on a single line if it fits
+//! within the maximum width, and otherwise every [`Doc::Line`] directly inside
+//! it becomes a newline followed by the current indentation.
+
+#[derive(Debug, Clone)]
+pub(crate) enum Doc {
+    Text(String),
+    Line(LineKind),
+    /// `n` newlines followed by the current indentation, regardless of the mode.
+    HardLines(usize),
+    Concat(Vec<Doc>),
+    Group(Box<Doc>),
+    Nest(Box<Doc>),
+    /// Only printed if the enclosing group is broken.
+    IfBreak(Box<Doc>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum LineKind {
+    /// A space when flat.
+    Space,
+    /// Nothing when flat.
+    Soft,
+}
+
+impl Doc {
+    pub(crate) fn text(text: impl Into<String>) -> Self {
+        Self::Text(text.into())
+    }
+
+    pub(crate) const fn line() -> Self {
+        Self::Line(LineKind::Space)
+    }
+
+    pub(crate) const fn softline() -> Self {
+        Self::Line(LineKind::Soft)
+    }
+
+    pub(crate) fn group(doc: Self) -> Self {
+        Self::Group(Box::new(doc))
+    }
+
+    pub(crate) fn nest(doc: Self) -> Self {
+        Self::Nest(Box::new(doc))
+    }
+
+    pub(crate) fn if_break(doc: Self) -> Self {
+        Self::IfBreak(Box::new(doc))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Flat,
+    Break,
+}
+
+pub(crate) struct PrintOptions<'a> {
+    pub max_width: usize,
+    /// The indentation of the line the document starts on.
+    pub base_indent: &'a str,
+    /// The column the document starts at.
+    pub start_column: usize,
+    pub indent_symbol: &'a str,
+}
+
+/// Prints `doc`, breaking the outermost groups first until every line fits.
+pub(crate) fn print(
+    doc: &Doc,
+    options: &PrintOptions<'_>,
+) -> String {
+    let mut out = String::new();
+    let mut column = options.start_column;
+    let mut stack: Vec<(usize, Mode, &Doc)> = vec![(0, Mode::Break, doc)];
+
+    while let Some((level, mode, doc)) = stack.pop() {
+        match doc {
+            Doc::Text(text) => {
+                out.push_str(text);
+                column = column_after(column, text);
+            },
+            Doc::Line(kind) => match mode {
+                Mode::Flat => {
+                    if *kind == LineKind::Space {
+                        out.push(' ');
+                        column += 1;
+                    }
+                },
+                Mode::Break => {
+                    column = newline(&mut out, 1, level, options);
+                },
+            },
+            Doc::HardLines(count) => {
+                column = newline(&mut out, *count, level, options);
+            },
+            Doc::Concat(docs) => {
+                stack.extend(docs.iter().rev().map(|doc| (level, mode, doc)));
+            },
+            Doc::Group(inner) => {
+                let mode = if mode == Mode::Flat
+                    || fits(
+                        options.max_width.saturating_sub(column),
+                        (level, Mode::Flat, inner),
+                        &stack,
+                    ) {
+                    Mode::Flat
+                } else {
+                    Mode::Break
+                };
+                stack.push((level, mode, inner));
+            },
+            Doc::Nest(inner) => stack.push((level + 1, mode, inner)),
+            Doc::IfBreak(inner) => {
+                if mode == Mode::Break {
+                    stack.push((level, mode, inner));
+                }
+            },
+        }
+    }
+
+    out
+}
+
+fn newline(
+    out: &mut String,
+    count: usize,
+    level: usize,
+    options: &PrintOptions<'_>,
+) -> usize {
+    for _ in 0..count {
+        out.push('\n');
+    }
+    out.push_str(options.base_indent);
+    for _ in 0..level {
+        out.push_str(options.indent_symbol);
+    }
+    width(options.base_indent) + level * width(options.indent_symbol)
+}
+
+/// Checks whether `next` fits in flat mode into the `remaining` width, followed by
+/// the rest of the current line.
+fn fits(
+    remaining: usize,
+    next: (usize, Mode, &Doc),
+    rest: &[(usize, Mode, &Doc)],
+) -> bool {
+    let mut remaining = remaining as isize;
+    let mut rest = rest.iter().rev();
+    let mut stack = vec![next];
+
+    loop {
+        let Some((level, mode, doc)) = stack.pop().or_else(|| rest.next().copied()) else {
+            return true;
+        };
+        match doc {
+            Doc::Text(text) => match text.split_once('\n') {
+                Some((first_line, _)) => return remaining >= width(first_line) as isize,
+                None => remaining -= width(text) as isize,
+            },
+            Doc::Line(kind) => match mode {
+                Mode::Flat => {
+                    if *kind == LineKind::Space {
+                        remaining -= 1;
+                    }
+                },
+                Mode::Break => return remaining >= 0,
+            },
+            Doc::HardLines(_) => return mode == Mode::Break && remaining >= 0,
+            Doc::Concat(docs) => stack.extend(docs.iter().rev().map(|doc| (level, mode, doc))),
+            Doc::Group(inner) | Doc::Nest(inner) => stack.push((level, mode, inner)),
+            Doc::IfBreak(inner) => {
+                if mode == Mode::Break {
+                    stack.push((level, mode, inner));
+                }
+            },
+        }
+        if remaining < 0 {
+            return false;
+        }
+    }
+}
+
+fn column_after(
+    column: usize,
+    text: &str,
+) -> usize {
+    match text.rsplit_once('\n') {
+        Some((_, last_line)) => width(last_line),
+        None => column + width(text),
+    }
+}
+
+/// The display width of `text`, counting tabs as four columns.
+pub(crate) fn width(text: &str) -> usize {
+    text.chars()
+        .map(|character| if character == '\t' { 4 } else { 1 })
+        .sum()
+}
@@ -1,23 +1,93 @@
+mod config;
+mod doc;
+mod preprocessor;
+mod pretty;
 #[cfg(test)]
 mod tests;
 
 use rowan::{GreenNode, GreenToken, NodeOrToken, WalkEvent};
-use syntax::{AstNode, HasGenerics, SyntaxElement, SyntaxKind, SyntaxNode, SyntaxToken, ast};
+use serde_derive::Deserialize;
+use syntax::{
+    AstNode, HasAttributes, HasGenerics, ParseEntryPoint, SyntaxElement, SyntaxKind, SyntaxNode,
+    SyntaxToken, ast,
+};
+
+pub use syntax::{Edition, ParseError};
+
+pub use crate::config::{
+    CONFIG_FILE_NAMES, Config, ConfigError, find_config_file, options_for_file,
+};
 
 pub fn format_str(
     input: &str,
     options: &FormattingOptions,
 ) -> String {
-    let parse = parser::parse_file(input);
-    let node = parse.syntax().clone_for_update();
-    format_recursive(node.clone(), options);
-    node.to_string()
+    format_str_with_edition(input, Edition::DEFAULT, options)
+}
+
+pub fn format_str_with_edition(
+    input: &str,
+    edition: Edition,
+    options: &FormattingOptions,
+) -> String {
+    format_parsed(input, edition, options).unwrap_or_else(|(formatted, _)| formatted)
+}
+
+/// Like [`format_str_with_edition`], but fails with the syntax errors of `input`
+/// instead of formatting around them.
+pub fn try_format_str(
+    input: &str,
+    edition: Edition,
+    options: &FormattingOptions,
+) -> Result<String, Vec<ParseError>> {
+    format_parsed(input, edition, options).map_err(|(_, errors)| errors)
+}
+
+/// On errors, returns the best-effort formatting together with the errors.
+fn format_parsed(
+    input: &str,
+    edition: Edition,
+    options: &FormattingOptions,
+) -> Result<String, (String, Vec<ParseError>)> {
+    let parse = |input: &str| {
+        let parse = parser::parse_entrypoint_with_edition(input, ParseEntryPoint::File, edition);
+        (parse.syntax(), !parse.errors().is_empty())
+    };
+    let (green_node, errors) =
+        parser::parse_entrypoint_with_edition(input, ParseEntryPoint::File, edition).into_parts();
+    if errors.is_empty() {
+        let node = SyntaxNode::new_root(green_node).clone_for_update();
+        format_recursive(node.clone(), options);
+        return Ok(node.to_string());
+    }
+
+    // Branches of an `#ifdef` which only parse on their own are formatted one by one.
+    match preprocessor::format_branches(input, &parse, options) {
+        Some((formatted, true)) => Ok(formatted),
+        Some((formatted, false)) => Err((formatted, errors)),
+        None => {
+            let node = SyntaxNode::new_root(green_node).clone_for_update();
+            format_recursive(node.clone(), options);
+            Err((node.to_string(), errors))
+        },
+    }
 }
 
 #[derive(Debug)]
 pub struct FormattingOptions {
     pub trailing_commas: Policy,
     pub indent_symbol: String,
+    /// Lines longer than this are broken up where possible.
+    pub max_width: usize,
+    /// Where the attributes of module-scope declarations are placed.
+    pub attribute_placement: AttributePlacement,
+    /// Rewrites legacy `[[stage(vertex)]]` attribute lists to `@vertex`.
+    pub modernize_attributes: bool,
+    /// Longer runs of blank lines are shortened to this many.
+    pub blank_lines_upper_bound: usize,
+    /// The minimum number of blank lines between module-scope declarations.
+    pub blank_lines_lower_bound: usize,
+    pub brace_style: BraceStyle,
 }
 
 impl Default for FormattingOptions {
@@ -25,24 +95,67 @@ impl Default for FormattingOptions {
         Self {
             trailing_commas: Policy::Ignore,
             indent_symbol: "    ".to_string(),
+            max_width: 100,
+            attribute_placement: AttributePlacement::Preserve,
+            modernize_attributes: false,
+            blank_lines_upper_bound: 1,
+            blank_lines_lower_bound: 0,
+            brace_style: BraceStyle::SameLine,
         }
     }
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Policy {
     Ignore,
     Remove,
     Insert,
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AttributePlacement {
+    /// Keeps attributes where they are.
+    Preserve,
+    /// `@compute @workgroup_size(64) fn main() {}`
+    SameLine,
+    /// Puts the attributes on the line above the declaration.
+    OwnLine,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BraceStyle {
+    /// `fn main() {`
+    SameLine,
+    /// Puts opening braces on their own line.
+    NextLine,
+}
+
 pub fn format_recursive(
     syntax: SyntaxNode,
     options: &FormattingOptions,
 ) {
+    if options.modernize_attributes {
+        // This replaces nodes, so it cannot happen during the traversal below.
+        let attribute_lists: Vec<ast::AttributeList> = syntax
+            .descendants()
+            .filter_map(ast::AttributeList::cast)
+            .collect();
+        for attribute_list in attribute_lists {
+            modernize_attribute_list(&attribute_list);
+        }
+    }
+
     let preorder = syntax.preorder();
 
-    let mut indentation: usize = 0;
+    // Formatting may start below the root, for example for range formatting.
+    let mut indentation = syntax
+        .ancestors()
+        .skip(1)
+        .filter(|node| is_indent_kind(node.clone()))
+        .count();
 
     for event in preorder {
         match event {
@@ -59,6 +172,82 @@ pub fn format_recursive(
             },
         };
     }
+
+    limit_blank_lines(&syntax, options);
+    preprocessor::indent_directives(&syntax, options);
+    pretty::break_long_lines(&syntax, options);
+}
+
+/// Applies [`FormattingOptions::blank_lines_upper_bound`] everywhere, and
+/// [`FormattingOptions::blank_lines_lower_bound`] between module-scope declarations.
+fn limit_blank_lines(
+    syntax: &SyntaxNode,
+    options: &FormattingOptions,
+) {
+    let max_newlines = options.blank_lines_upper_bound + 1;
+    let min_newlines = (options.blank_lines_lower_bound + 1).min(max_newlines);
+
+    let whitespace: Vec<SyntaxToken> = syntax
+        .descendants_with_tokens()
+        .filter_map(NodeOrToken::into_token)
+        .filter(|token| token.kind().is_whitespace())
+        .collect();
+    for token in whitespace {
+        let n_newlines = token.text().matches('\n').count();
+        if n_newlines > max_newlines {
+            let (_, indentation) = token.text().rsplit_once('\n').unwrap();
+            replace_token_with(
+                token.clone(),
+                create_whitespace(&format!("{}{indentation}", "\n".repeat(max_newlines))),
+            );
+        }
+    }
+
+    if min_newlines <= 1 {
+        return;
+    }
+    let Some(source_file) = ast::SourceFile::cast(syntax.clone()) else {
+        return;
+    };
+    let items: Vec<ast::Item> = source_file.items().collect();
+    for pair in items.windows(2) {
+        let [previous, item] = pair else {
+            continue;
+        };
+        // Consecutive imports form a single block.
+        if matches!(
+            (previous, item),
+            (ast::Item::Import(_), ast::Item::Import(_))
+        ) {
+            continue;
+        }
+        // The first line break after the previous item, which keeps trailing comments on its line.
+        let Some(separator) = previous
+            .syntax()
+            .last_token()
+            .and_then(|token| last_non_trivia_token(&token))
+            .and_then(|token| {
+                std::iter::successors(token.next_token(), SyntaxToken::next_token)
+                    .take_while(|token| token.kind().is_trivia())
+                    .find(|token| is_whitespace_with_newline(token.clone()))
+            })
+        else {
+            continue;
+        };
+        let n_newlines = separator.text().matches('\n').count();
+        if n_newlines < min_newlines {
+            let (_, indentation) = separator.text().rsplit_once('\n').unwrap();
+            replace_token_with(
+                separator.clone(),
+                create_whitespace(&format!("{}{indentation}", "\n".repeat(min_newlines))),
+            );
+        }
+    }
+}
+
+fn last_non_trivia_token(token: &SyntaxToken) -> Option<SyntaxToken> {
+    std::iter::successors(Some(token.clone()), SyntaxToken::prev_token)
+        .find(|token| !token.kind().is_trivia())
 }
 
 fn is_indent_kind(node: SyntaxNode) -> bool {
@@ -110,6 +299,10 @@ fn format_syntax_node(
         }
     }
 
+    if let Some(item) = ast::Item::cast(syntax.clone()) {
+        format_item_attributes(&item, options);
+    }
+
     match syntax.kind() {
         // fn name ( parameter : type, parameter : type ) -> return_ty {}
         // fn name(
@@ -122,7 +315,7 @@ fn format_syntax_node(
             trim_whitespace_before_to_newline(function.fn_token()?);
 
             set_whitespace_single_after(function.fn_token()?);
-            set_whitespace_single_before(function.body()?.left_brace_token()?);
+            set_whitespace_before_brace(function.body()?.left_brace_token()?, indentation, options);
 
             let param_list = function.parameter_list()?;
 
@@ -131,19 +324,19 @@ fn format_syntax_node(
             let has_newline =
                 is_whitespace_with_newline(param_list.left_parenthesis_token()?.next_token()?);
 
+            let (line_indent, _) = pretty::line_position(&param_list.left_parenthesis_token()?);
             format_param_list(
                 param_list.parameters(),
                 param_list.parameters().count(),
                 has_newline,
-                1,
+                &format!("{line_indent}{}", options.indent_symbol),
                 options.trailing_commas,
-                &options.indent_symbol,
             );
 
             if has_newline {
                 set_whitespace_before(
                     param_list.right_parenthesis_token()?,
-                    create_whitespace("\n"),
+                    create_whitespace(&format!("\n{line_indent}")),
                 );
             } else {
                 remove_if_whitespace(param_list.right_parenthesis_token()?.prev_token()?); // spellchecker:disable-line
@@ -165,23 +358,42 @@ fn format_syntax_node(
 
             let name = r#struct.name()?.ident_token()?;
             whitespace_to_single_around(name);
+
+            set_whitespace_before_brace(
+                r#struct.body()?.syntax().first_token()?,
+                indentation,
+                options,
+            );
         },
         SyntaxKind::IfStatement => {
             let if_statement = ast::IfStatement::cast(syntax)?;
 
             set_whitespace_single_after(if_statement.if_token()?);
 
-            set_whitespace_single_before(if_statement.block()?.left_brace_token()?);
-
-            if let Some(else_block) = if_statement.else_block() {
-                whitespace_to_single_around(else_block.else_token()?);
-            }
+            set_whitespace_before_brace(
+                if_statement.block()?.left_brace_token()?,
+                indentation,
+                options,
+            );
 
             for else_if_block in if_statement.else_if_blocks() {
-                whitespace_to_single_around(else_if_block.else_token()?);
+                set_whitespace_before_else(else_if_block.else_token()?, indentation, options);
                 whitespace_to_single_around(else_if_block.if_token()?);
 
-                set_whitespace_single_before(else_if_block.block()?.left_brace_token()?);
+                set_whitespace_before_brace(
+                    else_if_block.block()?.left_brace_token()?,
+                    indentation,
+                    options,
+                );
+            }
+
+            if let Some(else_block) = if_statement.else_block() {
+                set_whitespace_before_else(else_block.else_token()?, indentation, options);
+                set_whitespace_before_brace(
+                    else_block.block()?.left_brace_token()?,
+                    indentation,
+                    options,
+                );
             }
         },
         SyntaxKind::WhileStatement => {
@@ -189,14 +401,22 @@ fn format_syntax_node(
 
             set_whitespace_single_after(while_statement.while_token()?);
 
-            set_whitespace_single_before(while_statement.block()?.left_brace_token()?);
+            set_whitespace_before_brace(
+                while_statement.block()?.left_brace_token()?,
+                indentation,
+                options,
+            );
         },
         SyntaxKind::ForStatement => {
             let for_statement = ast::ForStatement::cast(syntax)?;
 
             set_whitespace_single_after(for_statement.for_token()?);
 
-            set_whitespace_single_before(for_statement.block()?.left_brace_token()?);
+            set_whitespace_before_brace(
+                for_statement.block()?.left_brace_token()?,
+                indentation,
+                options,
+            );
 
             remove_if_whitespace(
                 for_statement
@@ -209,6 +429,22 @@ fn format_syntax_node(
             set_whitespace_single_before(for_statement.continuing_part()?.syntax().first_token()?);
             remove_if_whitespace(for_statement.continuing_part()?.syntax().last_token()?);
         },
+        SyntaxKind::LoopStatement => {
+            let loop_statement = ast::LoopStatement::cast(syntax)?;
+            set_whitespace_before_brace(
+                loop_statement.block()?.left_brace_token()?,
+                indentation,
+                options,
+            );
+        },
+        SyntaxKind::SwitchStatement => {
+            let switch_statement = ast::SwitchStatement::cast(syntax)?;
+            set_whitespace_before_brace(
+                switch_statement.block()?.syntax().first_token()?,
+                indentation,
+                options,
+            );
+        },
         SyntaxKind::CompoundStatement => {
             let statement = ast::CompoundStatement::cast(syntax)?;
             let has_newline =
@@ -231,7 +467,7 @@ fn format_syntax_node(
                 remove_if_whitespace(expression.syntax().last_token()?);
             }
 
-            format_parameters(type_initialiser.arguments()?, indentation, options)?;
+            format_parameters(type_initialiser.arguments()?, options)?;
         },
         SyntaxKind::FunctionCall => {
             let function_call = ast::FunctionCall::cast(syntax)?;
@@ -242,18 +478,28 @@ fn format_syntax_node(
 
             let param_list = function_call.parameters()?;
 
-            format_parameters(param_list, indentation, options)?;
+            format_parameters(param_list, options)?;
         },
         SyntaxKind::InfixExpression => {
             let expression = ast::InfixExpression::cast(syntax)?;
 
             match expression.op()? {
                 NodeOrToken::Node(node) => {
-                    set_whitespace_single_before(node.first_token()?);
+                    if !node
+                        .first_token()?
+                        .prev_token()
+                        .is_some_and(is_whitespace_with_newline)
+                    {
+                        set_whitespace_single_before(node.first_token()?);
+                    }
                     set_whitespace_single_before(node.last_token()?.next_token()?);
                 },
                 NodeOrToken::Token(token) => {
-                    whitespace_to_single_around(token);
+                    // A line broken before the operator stays broken.
+                    if !token.prev_token().is_some_and(is_whitespace_with_newline) {
+                        set_whitespace_single_before(token.clone());
+                    }
+                    set_whitespace_single_after(token);
                 },
             }
         },
@@ -315,9 +561,15 @@ fn format_syntax_node(
                 let generics = r#type.generic_arg_list()?;
                 let left_angle = generics.left_angle_token()?;
                 remove_if_whitespace(left_angle.prev_token()?); // spellchecker:disable-line
-                remove_if_whitespace(left_angle.next_token()?);
-                let right_angle = generics.left_angle_token()?;
-                remove_if_whitespace(right_angle.prev_token()?); // spellchecker:disable-line
+                // Generic arguments that were broken over multiple lines stay that way.
+                if !left_angle
+                    .next_token()
+                    .is_some_and(is_whitespace_with_newline)
+                {
+                    remove_if_whitespace(left_angle.next_token()?);
+                    let right_angle = generics.t_angle_token()?;
+                    remove_if_whitespace(right_angle.prev_token()?); // spellchecker:disable-line
+                }
             }
         },
     }
@@ -325,25 +577,202 @@ fn format_syntax_node(
     None
 }
 
+/// Places the attributes of a module-scope declaration according to
+/// [`FormattingOptions::attribute_placement`].
+fn format_item_attributes(
+    item: &ast::Item,
+    options: &FormattingOptions,
+) -> Option<()> {
+    let attribute_list = item.attribute_list()?;
+    let declaration = attribute_list
+        .syntax()
+        .next_sibling_or_token()?
+        .into_token()?;
+    let whitespace = declaration
+        .prev_token()
+        .filter(|token| token.kind().is_whitespace())?;
+    if whitespace
+        .prev_token()
+        .is_none_or(|token| token.kind() == SyntaxKind::Comment)
+    {
+        return None;
+    }
+
+    match options.attribute_placement {
+        AttributePlacement::Preserve => {},
+        AttributePlacement::SameLine => {
+            let has_comment = attribute_list
+                .syntax()
+                .descendants_with_tokens()
+                .any(|element| element.kind() == SyntaxKind::Comment);
+            if has_comment {
+                return None;
+            }
+            let operators: Vec<SyntaxToken> = attribute_list
+                .syntax()
+                .children_with_tokens()
+                .filter_map(NodeOrToken::into_token)
+                .filter(|token| token.kind() == SyntaxKind::AttributeOperator)
+                .skip(1)
+                .collect();
+            for operator in operators {
+                set_whitespace_single_before(operator);
+            }
+            set_whitespace_single_before(declaration);
+        },
+        AttributePlacement::OwnLine => {
+            let (line_indent, _) = pretty::line_position(&attribute_list.syntax().first_token()?);
+            set_whitespace_before(declaration, create_whitespace(&format!("\n{line_indent}")));
+        },
+    }
+    Some(())
+}
+
+/// `[[stage(vertex), workgroup_size(64)]]` -> `@vertex @workgroup_size(64)`
+fn modernize_attribute_list(attribute_list: &ast::AttributeList) -> Option<()> {
+    let syntax = attribute_list.syntax();
+    let left = syntax.first_token()?;
+    let right = syntax
+        .children_with_tokens()
+        .filter_map(NodeOrToken::into_token)
+        .find(|token| token.kind() == SyntaxKind::AttributeRight)?;
+    let is_well_formed = syntax
+        .descendants_with_tokens()
+        .take_while(|element| element.as_token() != Some(&right))
+        .all(|element| {
+            element.kind() != SyntaxKind::Error
+                && (!element.kind().is_trivia() || element.kind().is_whitespace())
+        });
+    if left.kind() != SyntaxKind::AttributeLeft || !is_well_formed {
+        return None;
+    }
+
+    let attributes: Vec<ast::Attribute> = attribute_list.attributes().collect();
+    let mut replacement = Vec::new();
+    for attribute in attributes {
+        if !replacement.is_empty() {
+            replacement.push(SyntaxElement::Token(single_whitespace()));
+        }
+        replacement.push(SyntaxElement::Token(create_syntax_token(
+            SyntaxKind::AttributeOperator,
+            "@",
+        )));
+        remove_if_whitespace(attribute.syntax().last_token()?);
+        attribute.syntax().detach();
+        replacement.push(SyntaxElement::Node(modernize_attribute(attribute)));
+    }
+    // `splice_children` skips elements when deleting more than one, so the
+    // punctuation is detached one by one.
+    let punctuation: Vec<SyntaxElement> = syntax
+        .children_with_tokens()
+        .take(right.index() + 1)
+        .collect();
+    for element in punctuation {
+        element.detach();
+    }
+    syntax.splice_children(0..0, replacement);
+    Some(())
+}
+
+/// `stage(vertex)` is spelled `vertex` nowadays; other attributes kept their names.
+fn modernize_attribute(attribute: ast::Attribute) -> SyntaxNode {
+    let stage = attribute
+        .ident_token()
+        .filter(|name| name.text() == "stage")
+        .and_then(|_| attribute.parameters())
+        .and_then(|parameters| {
+            let mut values = parameters
+                .syntax()
+                .descendants_with_tokens()
+                .filter_map(NodeOrToken::into_token)
+                .filter(|token| {
+                    !token.kind().is_trivia()
+                        && !matches!(
+                            token.kind(),
+                            SyntaxKind::ParenthesisLeft | SyntaxKind::ParenthesisRight
+                        )
+                });
+            match (values.next(), values.next()) {
+                (Some(value), None) => Some(value),
+                _ => None,
+            }
+        })
+        .filter(|value| value.kind() == SyntaxKind::Identifier);
+    match stage {
+        Some(stage) => SyntaxNode::new_root(GreenNode::new(
+            SyntaxKind::Attribute.into(),
+            std::iter::once(NodeOrToken::Token(GreenToken::new(
+                SyntaxKind::Identifier.into(),
+                stage.text(),
+            ))),
+        ))
+        .clone_for_update(),
+        None => attribute.syntax().clone(),
+    }
+}
+
+/// Places an opening brace according to [`FormattingOptions::brace_style`].
+fn set_whitespace_before_brace(
+    left_brace: SyntaxToken,
+    indentation: usize,
+    options: &FormattingOptions,
+) -> Option<()> {
+    if follows_line_comment(&left_brace) {
+        return None;
+    }
+    match options.brace_style {
+        BraceStyle::SameLine => set_whitespace_single_before(left_brace),
+        BraceStyle::NextLine => set_whitespace_before(
+            left_brace,
+            create_whitespace(&format!("\n{}", options.indent_symbol.repeat(indentation))),
+        ),
+    }
+}
+
+/// `} else` or, with [`BraceStyle::NextLine`], `else` on its own line.
+fn set_whitespace_before_else(
+    else_token: SyntaxToken,
+    indentation: usize,
+    options: &FormattingOptions,
+) -> Option<()> {
+    set_whitespace_single_after(else_token.clone());
+    if follows_line_comment(&else_token) {
+        return None;
+    }
+    match options.brace_style {
+        BraceStyle::SameLine => set_whitespace_single_before(else_token),
+        BraceStyle::NextLine => set_whitespace_before(
+            else_token,
+            create_whitespace(&format!("\n{}", options.indent_symbol.repeat(indentation))),
+        ),
+    }
+}
+
+fn follows_line_comment(token: &SyntaxToken) -> bool {
+    std::iter::successors(token.prev_token(), SyntaxToken::prev_token)
+        .find(|token| !token.kind().is_whitespace())
+        .is_some_and(|token| token.kind() == SyntaxKind::Comment && token.text().starts_with("//"))
+}
+
+/// Formats call arguments, indenting them relative to the line the list starts on.
 fn format_parameters(
     param_list: ast::FunctionParameterList,
-    indentation: usize,
     options: &FormattingOptions,
 ) -> Option<()> {
     let has_newline =
         is_whitespace_with_newline(param_list.left_parenthesis_token()?.next_token()?);
+    let (line_indent, _) = pretty::line_position(&param_list.left_parenthesis_token()?);
     format_param_list(
         param_list.arguments(),
         param_list.arguments().count(),
         has_newline,
-        indentation + 1,
+        &format!("{line_indent}{}", options.indent_symbol),
         options.trailing_commas,
-        &options.indent_symbol,
     );
     if has_newline {
         set_whitespace_before(
             param_list.right_parenthesis_token()?,
-            create_whitespace(&format!("\n{}", options.indent_symbol.repeat(indentation))),
+            create_whitespace(&format!("\n{line_indent}")),
         );
     } else {
         remove_if_whitespace(param_list.right_parenthesis_token()?.prev_token()?); // spellchecker:disable-line
@@ -355,9 +784,8 @@ fn format_param_list<T: AstNode>(
     parameters: syntax::AstChildren<T>,
     count: usize,
     has_newline: bool,
-    n_indentations: usize,
+    indent: &str,
     trailing_comma_policy: Policy,
-    indent_symbol: &str,
 ) -> Option<()> {
     let mut first = true;
     for (i, parameter) in parameters.enumerate() {
@@ -370,7 +798,7 @@ fn format_param_list<T: AstNode>(
 
         let ws = match (first, previous_had_newline) {
             (true, false) => create_whitespace(""),
-            (_, true) => create_whitespace(&format!("\n{}", indent_symbol.repeat(n_indentations))),
+            (_, true) => create_whitespace(&format!("\n{indent}")),
             (false, false) => create_whitespace(" "),
         };
 
@@ -398,6 +826,8 @@ fn format_param_list<T: AstNode>(
                 },
             },
             (false, true) => {},
+            // Alternative parameters in the branches of an `#ifdef`.
+            (false, false) if is_followed_by_directive(parameter.syntax()) => {},
             (false, false) => {
                 insert_after_syntax(
                     parameter.syntax(),
@@ -412,10 +842,22 @@ fn format_param_list<T: AstNode>(
     Some(())
 }
 
+fn is_followed_by_directive(node: &SyntaxNode) -> bool {
+    let Some(last) = node
+        .last_token()
+        .and_then(|token| last_non_trivia_token(&token))
+    else {
+        return false;
+    };
+    std::iter::successors(last.next_token(), SyntaxToken::next_token)
+        .take_while(|token| token.kind().is_trivia())
+        .any(|token| preprocessor::is_directive(token.kind()))
+}
+
 // "\n  fn" -> "\nfn"
 fn trim_whitespace_before_to_newline(before: SyntaxToken) -> Option<()> {
     let maybe_whitespace = before.prev_token()?; // spellchecker:disable-line
-    if maybe_whitespace.kind().is_whitespace() {
+    if is_whitespace_with_newline(maybe_whitespace.clone()) {
         let index = maybe_whitespace.index();
 
         let text = maybe_whitespace.text().trim_end_matches(' ');
@@ -439,8 +881,17 @@ fn n_newlines_in_whitespace(maybe_whitespace: SyntaxToken) -> Option<usize> {
         .then(|| maybe_whitespace.text().matches('\n').count())
 }
 
+/// The line break around a directive, which must never be removed.
+fn ends_directive_line(whitespace: &SyntaxToken) -> bool {
+    whitespace.text().contains('\n')
+        && [whitespace.prev_token(), whitespace.next_token()]
+            .into_iter()
+            .flatten()
+            .any(|token| preprocessor::is_directive(token.kind()))
+}
+
 fn remove_if_whitespace(maybe_whitespace: SyntaxToken) {
-    if maybe_whitespace.kind().is_whitespace() {
+    if maybe_whitespace.kind().is_whitespace() && !ends_directive_line(&maybe_whitespace) {
         remove_token(maybe_whitespace);
     }
 }
@@ -508,6 +959,9 @@ fn set_whitespace_after(
 ) -> Option<()> {
     let maybe_whitespace = after.next_token()?;
     if maybe_whitespace.kind().is_whitespace() {
+        if ends_directive_line(&maybe_whitespace) && !to.text().contains('\n') {
+            return None;
+        }
         replace_token_with(maybe_whitespace, to);
     } else {
         insert_after(after, to);
@@ -522,6 +976,9 @@ fn set_whitespace_before(
 ) -> Option<()> {
     let maybe_whitespace = before.prev_token()?; // spellchecker:disable-line
     if maybe_whitespace.kind().is_whitespace() {
+        if ends_directive_line(&maybe_whitespace) && !to.text().contains('\n') {
+            return None;
+        }
         replace_token_with(maybe_whitespace, to);
     } else {
         insert_before(before, to);
@@ -0,0 +1,207 @@
+//! Formatting around `#ifdef`, `#else` and `#endif` directives.
+//!
+//! The parser treats directives as trivia, so the branches of a conditional
+//! usually form a single valid tree and are formatted like any other code. When
+//! they do not, for example with two alternative function headers, every branch
+//! is formatted in a variant of the file in which it is the selected branch.
+
+use rowan::NodeOrToken;
+use syntax::{SyntaxKind, SyntaxNode, SyntaxToken};
+
+use crate::{FormattingOptions, create_whitespace, is_whitespace_with_newline, replace_token_with};
+
+pub(crate) fn is_directive(kind: SyntaxKind) -> bool {
+    matches!(
+        kind,
+        SyntaxKind::UnofficialPreprocessorIfDef
+            | SyntaxKind::UnofficialPreprocessIf
+            | SyntaxKind::UnofficialPreprocessorElse
+            | SyntaxKind::UnofficialPreprocessorEndif
+    )
+}
+
+fn directives(syntax: &SyntaxNode) -> Vec<SyntaxToken> {
+    syntax
+        .descendants_with_tokens()
+        .filter_map(NodeOrToken::into_token)
+        .filter(|token| is_directive(token.kind()))
+        .collect()
+}
+
+/// Indents every `#ifdef` like the code following it, and the matching
+/// `#else` and `#endif` like their `#ifdef`.
+pub(crate) fn indent_directives(
+    syntax: &SyntaxNode,
+    options: &FormattingOptions,
+) {
+    let mut open: Vec<String> = Vec::new();
+    for directive in directives(syntax) {
+        let indentation = match directive.kind() {
+            SyntaxKind::UnofficialPreprocessorElse => open
+                .last()
+                .cloned()
+                .unwrap_or_else(|| indentation_of_following_code(&directive, options)),
+            SyntaxKind::UnofficialPreprocessorEndif => open
+                .pop()
+                .unwrap_or_else(|| indentation_of_following_code(&directive, options)),
+            _ => {
+                let indentation = indentation_of_following_code(&directive, options);
+                open.push(indentation.clone());
+                indentation
+            },
+        };
+        // Directives which do not start a line are left alone.
+        let Some(whitespace) = directive
+            .prev_token()
+            .filter(|token| is_whitespace_with_newline(token.clone()))
+        else {
+            continue;
+        };
+        let n_newlines = whitespace.text().matches('\n').count();
+        replace_token_with(
+            whitespace,
+            create_whitespace(&format!("{}{indentation}", "\n".repeat(n_newlines))),
+        );
+    }
+}
+
+fn indentation_of_following_code(
+    directive: &SyntaxToken,
+    options: &FormattingOptions,
+) -> String {
+    let Some(code) = std::iter::successors(directive.next_token(), SyntaxToken::next_token)
+        .find(|token| !token.kind().is_trivia())
+    else {
+        return String::new();
+    };
+    let (line_indent, _) = crate::pretty::line_position(&code);
+    // The directive is inside the block which this brace closes.
+    if code.kind() == SyntaxKind::BraceRight {
+        format!("{line_indent}{}", options.indent_symbol)
+    } else {
+        line_indent
+    }
+}
+
+/// Formats a file whose directives split it into regions which only parse on
+/// their own. `parse` returns the tree of a text and whether it has errors.
+///
+/// Returns `None` if the directives are unbalanced. Regions which do not parse
+/// in any variant are kept as they are, and the flag is `false` if there were any.
+pub(crate) fn format_branches(
+    input: &str,
+    parse: &dyn Fn(&str) -> (SyntaxNode, bool),
+    options: &FormattingOptions,
+) -> Option<(String, bool)> {
+    let (syntax, _) = parse(input);
+    let directives = directives(&syntax);
+    if directives.is_empty() {
+        return None;
+    }
+    let directive_texts: Vec<String> = directives
+        .iter()
+        .map(|directive| directive.text().to_string())
+        .collect();
+    let regions = split_at_directives(input, &directives);
+    let paths = branch_paths(&directives)?;
+    let n_conditionals = paths
+        .iter()
+        .flatten()
+        .map(|&(conditional, _)| conditional + 1)
+        .max()
+        .unwrap_or(0);
+
+    let mut formatted: Vec<Option<String>> = vec![None; regions.len()];
+    for (index, path) in paths.iter().enumerate() {
+        if formatted[index].is_some() {
+            continue;
+        }
+        // Every conditional takes its first branch, except for those containing the region.
+        let mut selection = vec![0; n_conditionals];
+        for &(conditional, branch) in path {
+            selection[conditional] = branch;
+        }
+        let is_selected = |path: &Vec<(usize, usize)>| {
+            path.iter()
+                .all(|&(conditional, branch)| selection[conditional] == branch)
+        };
+
+        let mut variant = String::new();
+        for (region_index, region) in regions.iter().enumerate() {
+            // The newline keeps adjacent directives on separate lines.
+            variant.push_str(if is_selected(&paths[region_index]) {
+                region
+            } else {
+                "\n"
+            });
+            if let Some(directive) = directive_texts.get(region_index) {
+                variant.push_str(directive);
+            }
+        }
+
+        let (variant_syntax, has_errors) = parse(&variant);
+        if has_errors {
+            continue;
+        }
+        let variant_syntax = variant_syntax.clone_for_update();
+        crate::format_recursive(variant_syntax.clone(), options);
+        let variant_directives = self::directives(&variant_syntax);
+        if variant_directives.len() != directives.len() {
+            continue;
+        }
+        let variant_regions = split_at_directives(&variant_syntax.to_string(), &variant_directives);
+        for (region_index, region) in variant_regions.into_iter().enumerate() {
+            if formatted[region_index].is_none() && is_selected(&paths[region_index]) {
+                formatted[region_index] = Some(region);
+            }
+        }
+    }
+
+    let is_complete = formatted.iter().all(Option::is_some);
+    let mut output = String::new();
+    for (index, region) in regions.iter().enumerate() {
+        output.push_str(formatted[index].as_deref().unwrap_or(region));
+        if let Some(directive) = directive_texts.get(index) {
+            output.push_str(directive);
+        }
+    }
+    Some((output, is_complete))
+}
+
+/// The text before, between and after the directives.
+fn split_at_directives(
+    text: &str,
+    directives: &[SyntaxToken],
+) -> Vec<String> {
+    let mut regions = Vec::with_capacity(directives.len() + 1);
+    let mut start = 0;
+    for directive in directives {
+        let range = directive.text_range();
+        regions.push(text[start..usize::from(range.start())].to_owned());
+        start = usize::from(range.end());
+    }
+    regions.push(text[start..].to_owned());
+    regions
+}
+
+/// For every region, the conditionals it is nested in and the index of the branch
+/// it belongs to. Conditionals are numbered by the position of their `#ifdef`.
+fn branch_paths(directives: &[SyntaxToken]) -> Option<Vec<Vec<(usize, usize)>>> {
+    let mut paths = vec![Vec::new()];
+    let mut open: Vec<(usize, usize)> = Vec::new();
+    let mut n_conditionals = 0;
+    for directive in directives {
+        match directive.kind() {
+            SyntaxKind::UnofficialPreprocessorElse => open.last_mut()?.1 += 1,
+            SyntaxKind::UnofficialPreprocessorEndif => {
+                open.pop()?;
+            },
+            _ => {
+                open.push((n_conditionals, 0));
+                n_conditionals += 1;
+            },
+        }
+        paths.push(open.clone());
+    }
+    open.is_empty().then_some(paths)
+}
@@ -0,0 +1,483 @@
+//! Breaks lines which are longer than [`FormattingOptions::max_width`].
+//!
+//! Every statement or item with an overflowing line is lowered into a [`Doc`],
+//! printed at the maximum width, and the printed layout is then written back
+//! into the whitespace between its tokens.
+
+use rowan::{NodeOrToken, TextSize};
+use syntax::{AstNode, SyntaxElement, SyntaxKind, SyntaxNode, SyntaxToken, ast};
+
+use crate::{
+    AttributePlacement, FormattingOptions, Policy, create_whitespace,
+    doc::{self, Doc, PrintOptions},
+    remove_token, set_whitespace_before,
+};
+
+pub(crate) fn break_long_lines(
+    syntax: &SyntaxNode,
+    options: &FormattingOptions,
+) {
+    for root in overflowing_roots(syntax, options) {
+        reflow(&root, options);
+    }
+}
+
+fn overflowing_roots(
+    syntax: &SyntaxNode,
+    options: &FormattingOptions,
+) -> Vec<SyntaxNode> {
+    let text = syntax.to_string();
+    let mut roots: Vec<SyntaxNode> = Vec::new();
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        let content = line.trim_end_matches(['\n', '\r']);
+        let line_start = offset;
+        offset += line.len();
+        if doc::width(content) <= options.max_width {
+            continue;
+        }
+        let indent = content.len() - content.trim_start().len();
+        let position = syntax.text_range().start() + TextSize::from((line_start + indent) as u32);
+        let Some(token) = syntax.token_at_offset(position).right_biased() else {
+            continue;
+        };
+        let Some(root) = token
+            .parent_ancestors()
+            .take_while(|node| syntax.text_range().contains_range(node.text_range()))
+            .find(|node| is_root_kind(node.kind()))
+        else {
+            continue;
+        };
+        if !roots.contains(&root) {
+            roots.push(root);
+        }
+    }
+    roots
+}
+
+/// Nodes which are reflowed as a whole.
+fn is_root_kind(kind: SyntaxKind) -> bool {
+    matches!(
+        kind,
+        SyntaxKind::Function
+            | SyntaxKind::StructDeclaration
+            | SyntaxKind::StructDeclarationField
+            | SyntaxKind::GlobalVariableDeclaration
+            | SyntaxKind::GlobalConstantDeclaration
+            | SyntaxKind::OverrideDeclaration
+            | SyntaxKind::TypeAliasDeclaration
+            | SyntaxKind::VariableStatement
+            | SyntaxKind::ExpressionStatement
+            | SyntaxKind::ReturnStatement
+            | SyntaxKind::AssignmentStatement
+            | SyntaxKind::CompoundAssignmentStatement
+            | SyntaxKind::IfStatement
+            | SyntaxKind::WhileStatement
+            | SyntaxKind::ForStatement
+            | SyntaxKind::SwitchStatement
+            | SyntaxKind::SwitchBodyCase
+    )
+}
+
+/// Nodes whose contents are printed as they are, because they are reflowed as separate roots.
+fn is_verbatim_kind(kind: SyntaxKind) -> bool {
+    matches!(
+        kind,
+        SyntaxKind::CompoundStatement | SyntaxKind::SwitchBlock | SyntaxKind::StructDeclBody
+    )
+}
+
+fn reflow(
+    root: &SyntaxNode,
+    options: &FormattingOptions,
+) -> Option<()> {
+    let has_unsupported_element = root.descendants_with_tokens().any(|element| {
+        element.kind() == SyntaxKind::Error
+            || (element.kind().is_trivia()
+                && !matches!(element.kind(), SyntaxKind::Whitespace | SyntaxKind::Comment))
+    });
+    if has_unsupported_element {
+        return None;
+    }
+
+    let first_token = root.first_token()?;
+    let (base_indent, start_column) = line_position(&first_token);
+    let doc = Lowerer::lower_root(root, options);
+    let printed = doc::print(
+        &doc,
+        &PrintOptions {
+            max_width: options.max_width,
+            base_indent: &base_indent,
+            start_column,
+            indent_symbol: &options.indent_symbol,
+        },
+    );
+
+    apply_layout(root, &printed)
+}
+
+/// The indentation of the line containing `token`, and the column `token` starts at.
+pub(crate) fn line_position(token: &SyntaxToken) -> (String, usize) {
+    let mut preceding = String::new();
+    let mut current = token.prev_token();
+    while let Some(token) = current {
+        if let Some((_, last_line)) = token.text().rsplit_once('\n') {
+            preceding.insert_str(0, last_line);
+            break;
+        }
+        preceding.insert_str(0, token.text());
+        current = token.prev_token();
+    }
+    let indent_length = preceding.len() - preceding.trim_start().len();
+    let column = doc::width(&preceding);
+    (preceding[..indent_length].to_owned(), column)
+}
+
+/// Writes the whitespace of `printed` back into the tokens of `root`.
+///
+/// `printed` must contain the non-whitespace tokens of `root` in order, except
+/// for trailing commas, which may be dropped.
+fn apply_layout(
+    root: &SyntaxNode,
+    printed: &str,
+) -> Option<()> {
+    enum Edit {
+        SetWhitespaceBefore(SyntaxToken, String),
+        Remove(SyntaxToken),
+    }
+
+    let mut edits = Vec::new();
+    let mut cursor = 0;
+    let mut previous_gap = String::new();
+    let mut first = true;
+
+    for token in root
+        .descendants_with_tokens()
+        .filter_map(NodeOrToken::into_token)
+    {
+        if token.kind().is_whitespace() {
+            previous_gap.push_str(token.text());
+            continue;
+        }
+        let rest = &printed[cursor..];
+        let trimmed = rest.trim_start_matches([' ', '\t', '\n', '\r']);
+        let gap = &rest[..rest.len() - trimmed.len()];
+        if !trimmed.starts_with(token.text()) {
+            if token.kind() == SyntaxKind::Comma {
+                edits.push(Edit::Remove(token));
+                continue;
+            }
+            return None;
+        }
+        if !first && gap != previous_gap {
+            edits.push(Edit::SetWhitespaceBefore(token.clone(), gap.to_owned()));
+        }
+        cursor = printed.len() - trimmed.len() + token.text().len();
+        previous_gap.clear();
+        first = false;
+    }
+
+    for edit in edits {
+        match edit {
+            Edit::SetWhitespaceBefore(token, gap) if gap.is_empty() => {
+                if let Some(whitespace) = token
+                    .prev_token()
+                    .filter(|token| token.kind().is_whitespace())
+                {
+                    remove_token(whitespace);
+                }
+            },
+            Edit::SetWhitespaceBefore(token, gap) => {
+                set_whitespace_before(token, create_whitespace(&gap));
+            },
+            Edit::Remove(token) => {
+                if let Some(whitespace) = token
+                    .prev_token()
+                    .filter(|token| token.kind().is_whitespace())
+                {
+                    remove_token(whitespace);
+                }
+                remove_token(token);
+            },
+        }
+    }
+
+    Some(())
+}
+
+/// Lowers a syntax tree into a [`Doc`].
+///
+/// Whitespace tokens are never lowered directly, because trailing whitespace is
+/// attached to the innermost preceding node. Instead, every token is preceded by
+/// the original whitespace in front of it, unless a construct has placed its own
+/// separator there.
+#[derive(Default)]
+struct Lowerer {
+    docs: Vec<Doc>,
+    is_first_token: bool,
+    has_separator: bool,
+    is_after_line_comment: bool,
+    remove_trailing_commas: bool,
+    item_attributes_on_own_line: bool,
+}
+
+impl Lowerer {
+    fn lower_root(
+        root: &SyntaxNode,
+        options: &FormattingOptions,
+    ) -> Doc {
+        let mut lowerer = Self {
+            is_first_token: true,
+            remove_trailing_commas: matches!(options.trailing_commas, Policy::Remove),
+            item_attributes_on_own_line: options.attribute_placement == AttributePlacement::OwnLine,
+            ..Self::default()
+        };
+        lowerer.node(root);
+        Doc::Concat(lowerer.docs)
+    }
+
+    /// Lowers `f` into a separate document.
+    fn nested(
+        &mut self,
+        f: impl FnOnce(&mut Self),
+    ) -> Doc {
+        let outer = std::mem::take(&mut self.docs);
+        f(self);
+        Doc::Concat(std::mem::replace(&mut self.docs, outer))
+    }
+
+    fn separator(
+        &mut self,
+        doc: Doc,
+    ) {
+        if self.is_after_line_comment {
+            // A line comment can never be followed by anything else on its line.
+            self.docs.push(Doc::HardLines(1));
+        } else {
+            self.docs.push(doc);
+        }
+        self.has_separator = true;
+    }
+
+    fn token(
+        &mut self,
+        token: &SyntaxToken,
+    ) {
+        if token.kind().is_whitespace() {
+            return;
+        }
+        self.gap_before(token);
+        self.docs.push(Doc::text(token.text()));
+        self.is_after_line_comment =
+            token.kind() == SyntaxKind::Comment && token.text().starts_with("//");
+    }
+
+    fn gap_before(
+        &mut self,
+        token: &SyntaxToken,
+    ) {
+        if std::mem::take(&mut self.is_first_token) || std::mem::take(&mut self.has_separator) {
+            return;
+        }
+        let mut gap = String::new();
+        let mut current = token.prev_token();
+        while let Some(whitespace) = current.filter(|token| token.kind().is_whitespace()) {
+            gap.insert_str(0, whitespace.text());
+            current = whitespace.prev_token();
+        }
+        if !gap.is_empty() {
+            self.docs.push(Doc::Text(gap));
+        }
+    }
+
+    fn node(
+        &mut self,
+        node: &SyntaxNode,
+    ) {
+        match node.kind() {
+            kind if is_verbatim_kind(kind) => self.verbatim(node),
+            SyntaxKind::InfixExpression if !has_comment_children(node) => self.infix(node),
+            SyntaxKind::FunctionParameterList
+            | SyntaxKind::ParameterList
+            | SyntaxKind::GenericArgumentList
+            | SyntaxKind::AttributeParameters
+                if !has_comment_children(node) =>
+            {
+                self.delimited_list(node);
+            },
+            SyntaxKind::AttributeList => self.attribute_list(node),
+            _ => self.children(node),
+        }
+    }
+
+    fn element(
+        &mut self,
+        element: &SyntaxElement,
+    ) {
+        match element {
+            NodeOrToken::Node(node) => self.node(node),
+            NodeOrToken::Token(token) => self.token(token),
+        }
+    }
+
+    fn children(
+        &mut self,
+        node: &SyntaxNode,
+    ) {
+        for child in node.children_with_tokens() {
+            self.element(&child);
+        }
+    }
+
+    fn verbatim(
+        &mut self,
+        node: &SyntaxNode,
+    ) {
+        let mut tokens = node
+            .descendants_with_tokens()
+            .filter_map(NodeOrToken::into_token)
+            .filter(|token| !token.kind().is_whitespace());
+        let Some(first) = tokens.next() else {
+            return;
+        };
+        let last = tokens.last().unwrap_or_else(|| first.clone());
+        self.gap_before(&first);
+        let range = first.text_range().cover(last.text_range()) - node.text_range().start();
+        self.docs.push(Doc::text(&node.to_string()[range]));
+        self.is_after_line_comment = false;
+    }
+
+    /// `@group(0) @binding(0) var` or, if too long, every attribute on its own line.
+    fn attribute_list(
+        &mut self,
+        node: &SyntaxNode,
+    ) {
+        let on_own_line = self.item_attributes_on_own_line
+            && node
+                .parent()
+                .is_some_and(|parent| ast::Item::can_cast(parent.kind()));
+        let attributes = self.nested(|this| {
+            for child in node.children_with_tokens() {
+                if child.kind() == SyntaxKind::AttributeOperator && child.index() != 0 {
+                    this.separator(Doc::line());
+                }
+                this.element(&child);
+            }
+            if !on_own_line {
+                this.separator(Doc::line());
+            }
+        });
+        self.docs.push(Doc::group(attributes));
+        if on_own_line {
+            self.separator(Doc::HardLines(1));
+        }
+    }
+
+    /// `a + b + c` or, if too long, one operand per line with the operator in front.
+    fn infix(
+        &mut self,
+        node: &SyntaxNode,
+    ) {
+        let mut operations = Vec::new();
+        let mut current = node.clone();
+        loop {
+            let Some(expression) = ast::InfixExpression::cast(current.clone()) else {
+                break;
+            };
+            let (Some(left), Some(op), Some(right)) = (
+                expression.left_side(),
+                expression.op(),
+                expression.right_side(),
+            ) else {
+                return self.children(node);
+            };
+            let is_same_operator =
+                operations
+                    .last()
+                    .is_none_or(|(previous, _): &(SyntaxElement, _)| {
+                        previous.to_string() == op.to_string()
+                    });
+            if !is_same_operator || has_comment_children(&current) {
+                break;
+            }
+            operations.push((op, right.syntax().clone()));
+            current = left.syntax().clone();
+        }
+        let first = current;
+
+        let group = self.nested(|this| {
+            this.node(&first);
+            let rest = this.nested(|this| {
+                for (op, right) in operations.iter().rev() {
+                    this.separator(Doc::line());
+                    this.element(op);
+                    this.separator(Doc::text(" "));
+                    this.node(right);
+                }
+            });
+            this.docs.push(Doc::nest(rest));
+        });
+        self.docs.push(Doc::group(group));
+    }
+
+    /// `(a, b)` or, if too long, one element per line.
+    fn delimited_list(
+        &mut self,
+        node: &SyntaxNode,
+    ) {
+        let children: Vec<SyntaxElement> = node
+            .children_with_tokens()
+            .filter(|child| !child.kind().is_whitespace())
+            .collect();
+        let (Some(NodeOrToken::Token(open)), Some(NodeOrToken::Token(close))) =
+            (children.first(), children.last())
+        else {
+            return self.children(node);
+        };
+        if children.len() < 2
+            || !matches!(
+                close.kind(),
+                SyntaxKind::ParenthesisRight | SyntaxKind::GreaterThan
+            )
+        {
+            return self.children(node);
+        }
+        let elements = &children[1..children.len() - 1];
+        if elements.is_empty() {
+            return self.children(node);
+        }
+        let last_node = elements
+            .iter()
+            .rposition(|element| element.as_node().is_some());
+
+        let group = self.nested(|this| {
+            this.token(open);
+            let inner = this.nested(|this| {
+                this.separator(Doc::softline());
+                for (index, element) in elements.iter().enumerate() {
+                    let is_trailing_comma = element.kind() == SyntaxKind::Comma
+                        && last_node.is_none_or(|last| index > last);
+                    if is_trailing_comma {
+                        if !this.remove_trailing_commas {
+                            this.docs.push(Doc::if_break(Doc::text(",")));
+                        }
+                        continue;
+                    }
+                    this.element(element);
+                    if element.kind() == SyntaxKind::Comma {
+                        this.separator(Doc::line());
+                    }
+                }
+            });
+            this.docs.push(Doc::nest(inner));
+            this.separator(Doc::softline());
+            this.token(close);
+        });
+        self.docs.push(Doc::group(group));
+    }
+}
+
+fn has_comment_children(node: &SyntaxNode) -> bool {
+    node.children_with_tokens()
+        .any(|child| child.kind() == SyntaxKind::Comment)
+}
@@ -2,7 +2,9 @@ use std::panic;
 
 use expect_test::{Expect, expect};
 
-use crate::{FormattingOptions, format_recursive};
+use crate::{
+    AttributePlacement, BraceStyle, Config, Edition, FormattingOptions, Policy, format_recursive,
+};
 
 fn check(
     before: &str,
@@ -211,9 +213,17 @@ fn format_struct() {
 fn format_bevy_function() {
     check(
         "fn directional_light(light: DirectionalLight, roughness: f32, NdotV: f32, normal: vec3<f32>, view: vec3<f32>, R: vec3<f32>, F0: vec3<f32>, diffuseColor: vec3<f32>) -> vec3<f32> {}",
-        expect![[
-            "fn directional_light(light: DirectionalLight, roughness: f32, NdotV: f32, normal: vec3<f32>, view: vec3<f32>, R: vec3<f32>, F0: vec3<f32>, diffuseColor: vec3<f32>) -> vec3<f32> {}"
-        ]],
+        expect![[r#"
+            fn directional_light(
+                light: DirectionalLight,
+                roughness: f32,
+                NdotV: f32,
+                normal: vec3<f32>,
+                view: vec3<f32>,
+                R: vec3<f32>,
+                F0: vec3<f32>,
+                diffuseColor: vec3<f32>
+            ) -> vec3<f32> {}"#]],
     )
 }
 
@@ -541,3 +551,522 @@ fn main() {
 			}"#]],
     );
 }
+
+fn check_width(
+    before: &str,
+    max_width: usize,
+    after: Expect,
+) {
+    let options = FormattingOptions {
+        max_width,
+        ..Default::default()
+    };
+    check_with_options(before, after, &options)
+}
+
+#[test]
+fn format_width_call_arguments() {
+    check_width(
+        "fn main() {
+    let x = some_function(first_argument, second_argument, third_argument);
+}",
+        40,
+        expect![[r#"
+            fn main() {
+                let x = some_function(
+                    first_argument,
+                    second_argument,
+                    third_argument
+                );
+            }"#]],
+    );
+}
+
+#[test]
+fn format_width_nested_calls() {
+    check_width(
+        "fn main() {
+    let x = outer(inner(first_argument, second_argument), vec3<f32>(1.0, 2.0, 3.0));
+}",
+        50,
+        expect![[r#"
+            fn main() {
+                let x = outer(
+                    inner(first_argument, second_argument),
+                    vec3<f32>(1.0, 2.0, 3.0)
+                );
+            }"#]],
+    );
+}
+
+#[test]
+fn format_width_binary_expression() {
+    check_width(
+        "fn main() {
+    let x = first_operand + second_operand + third_operand * fourth_operand;
+}",
+        40,
+        expect![[r#"
+            fn main() {
+                let x = first_operand
+                    + second_operand
+                    + third_operand * fourth_operand;
+            }"#]],
+    );
+}
+
+#[test]
+fn format_width_attributes() {
+    check_width(
+        "@group(0) @binding(0) var<storage, read> very_long_buffer_name: array<vec4<f32>, 64>;",
+        50,
+        expect![[r#"
+            @group(0)
+            @binding(0)
+            var<storage, read> very_long_buffer_name: array<
+                vec4<f32>,
+                64
+            >;"#]],
+    );
+}
+
+#[test]
+fn format_width_array_generics() {
+    check_width(
+        "const lookup_table_values: array<vec4<f32>, SOME_VERY_LONG_CONSTANT_NAME> = lookup();",
+        40,
+        expect![[r#"
+            const lookup_table_values: array<
+                vec4<f32>,
+                SOME_VERY_LONG_CONSTANT_NAME
+            > = lookup();"#]],
+    );
+}
+
+#[test]
+fn format_width_trailing_comma() {
+    check_width(
+        "fn main() {
+    let x = f(
+        a,
+        b,
+    );
+    let y = some_function(first_argument, second_argument, third_argument,);
+}",
+        40,
+        expect![[r#"
+            fn main() {
+                let x = f(
+                    a,
+                    b,
+                );
+                let y = some_function(
+                    first_argument,
+                    second_argument,
+                    third_argument
+                );
+            }"#]],
+    );
+}
+
+#[test]
+fn format_width_fits() {
+    check_width(
+        "fn main() {
+    let x = f(a, b) + g(c);
+}",
+        40,
+        expect![[r#"
+            fn main() {
+                let x = f(a, b) + g(c);
+            }"#]],
+    );
+}
+
+#[test]
+fn format_width_line_comment() {
+    check_width(
+        "fn main() {
+    let x = first_operand // first
+        + second_operand + third_operand;
+}",
+        30,
+        expect![[r#"
+            fn main() {
+                let x = first_operand // first
+                    + second_operand
+                    + third_operand;
+            }"#]],
+    );
+}
+
+#[test]
+fn format_attributes_preserve() {
+    check(
+        "@vertex fn main() {}
+@group(0) @binding(0)
+var<uniform> u: f32;",
+        expect![[r#"
+            @vertex fn main() {}
+            @group(0) @binding(0)
+            var<uniform> u: f32;"#]],
+    );
+}
+
+#[test]
+fn format_attributes_same_line() {
+    let options = FormattingOptions {
+        attribute_placement: AttributePlacement::SameLine,
+        ..Default::default()
+    };
+    check_with_options(
+        "@compute
+@workgroup_size(64)
+fn main() {}
+@group(0) // the bind group
+var t: texture_2d<f32>;",
+        expect![[r#"
+            @compute @workgroup_size(64) fn main() {}
+            @group(0) // the bind group
+            var t: texture_2d<f32>;"#]],
+        &options,
+    );
+}
+
+#[test]
+fn format_attributes_own_line() {
+    let options = FormattingOptions {
+        attribute_placement: AttributePlacement::OwnLine,
+        ..Default::default()
+    };
+    check_with_options(
+        "@group(0) @binding(0) var<uniform> u: f32;
+@vertex fn main(@location(0) position: vec3<f32>) {}",
+        expect![[r#"
+            @group(0) @binding(0)
+            var<uniform> u: f32;
+            @vertex
+            fn main(@location(0) position: vec3<f32>) {}"#]],
+        &options,
+    );
+}
+
+#[test]
+fn format_modernize_attributes() {
+    let options = FormattingOptions {
+        modernize_attributes: true,
+        ..Default::default()
+    };
+    check_with_options(
+        "[[stage(compute), workgroup_size(64, 1) ]]
+fn main([[builtin(global_invocation_id)]] id: vec3<u32>) {}",
+        expect![[r#"
+            @compute @workgroup_size(64, 1)
+            fn main(@builtin(global_invocation_id) id: vec3<u32>) {}"#]],
+        &options,
+    );
+}
+
+#[test]
+fn format_blank_lines_upper_bound() {
+    check(
+        "fn a() {}
+
+
+
+fn b() {
+    let x = 1;
+
+
+    let y = 2;
+}",
+        expect![[r#"
+            fn a() {}
+
+            fn b() {
+                let x = 1;
+
+                let y = 2;
+            }"#]],
+    );
+}
+
+#[test]
+fn format_blank_lines_lower_bound() {
+    let options = FormattingOptions {
+        blank_lines_lower_bound: 1,
+        ..Default::default()
+    };
+    check_with_options(
+        "fn a() {}
+fn b() {} // trailing
+// leading
+fn c() {}
+const X = 1;",
+        expect![[r#"
+            fn a() {}
+
+            fn b() {} // trailing
+
+            // leading
+            fn c() {}
+
+            const X = 1;"#]],
+        &options,
+    );
+}
+
+#[test]
+fn format_brace_next_line() {
+    let options = FormattingOptions {
+        brace_style: BraceStyle::NextLine,
+        ..Default::default()
+    };
+    check_with_options(
+        "struct S {
+    a: f32,
+}
+fn main() {
+    if a {
+        x = 1;
+    } else if b {
+        x = 2;
+    } else {
+        loop {
+            break;
+        }
+    }
+}",
+        expect![[r#"
+            struct S
+            {
+                a: f32,
+            }
+            fn main()
+            {
+                if a
+                {
+                    x = 1;
+                }
+                else if b
+                {
+                    x = 2;
+                }
+                else
+                {
+                    loop
+                    {
+                        break;
+                    }
+                }
+            }"#]],
+        &options,
+    );
+}
+
+#[test]
+fn format_width_remove_trailing_comma() {
+    let options = FormattingOptions {
+        max_width: 40,
+        trailing_commas: Policy::Remove,
+        ..Default::default()
+    };
+    check_with_options(
+        "fn main() {
+    let x = some_function(first_argument, second_argument, third_argument,);
+}",
+        expect![[r#"
+            fn main() {
+                let x = some_function(
+                    first_argument,
+                    second_argument,
+                    third_argument
+                );
+            }"#]],
+        &options,
+    );
+}
+
+#[test]
+fn config_from_toml() {
+    let config = Config::from_toml(
+        r#"
+max_width = 80
+hard_tabs = true
+trailing_commas = "insert"
+attribute_placement = "own_line"
+brace_style = "next_line"
+"#,
+    )
+    .unwrap();
+    let mut options = FormattingOptions::default();
+    config.apply(&mut options);
+    assert_eq!(options.max_width, 80);
+    assert_eq!(options.indent_symbol, "\t");
+    assert!(matches!(options.trailing_commas, Policy::Insert));
+    assert_eq!(options.attribute_placement, AttributePlacement::OwnLine);
+    assert_eq!(options.brace_style, BraceStyle::NextLine);
+    assert!(!options.modernize_attributes);
+
+    assert!(Config::from_toml("max_widht = 80").is_err());
+}
+
+#[test]
+fn format_preprocessor_directives() {
+    check(
+        "fn main() {
+#ifdef A
+        let x = 1;
+#else
+  let x = 2;
+        #endif
+    return;
+}
+    #ifdef B
+fn b() {}
+#endif",
+        expect![[r#"
+            fn main() {
+                #ifdef A
+                let x = 1;
+                #else
+                let x = 2;
+                #endif
+                return;
+            }
+            #ifdef B
+            fn b() {}
+            #endif"#]],
+    );
+}
+
+#[test]
+fn format_preprocessor_directive_before_brace() {
+    check(
+        "fn main() {
+    if true {
+        let x = 1;
+#ifdef A
+#endif
+    }
+}",
+        expect![[r#"
+            fn main() {
+                if true {
+                    let x = 1;
+                    #ifdef A
+                    #endif
+                }
+            }"#]],
+    );
+}
+
+#[test]
+fn format_preprocessor_keeps_directive_lines() {
+    check(
+        "fn main() {
+    let x = a
+#ifdef B
+    + b
+#endif
+    ;
+}",
+        expect![[r#"
+            fn main() {
+                let x = a
+                #ifdef B
+                + b
+                #endif
+                ;
+            }"#]],
+    );
+}
+
+#[test]
+fn format_preprocessor_branches_separately() {
+    let before = "#ifdef SKINNED
+fn vertex(  vertex: Vertex,skin: Skin) -> VertexOutput   {
+#else
+fn vertex(vertex:Vertex)->VertexOutput{
+#endif
+var out : VertexOutput;
+    return out;
+}";
+    let after = crate::format_str(before, &FormattingOptions::default());
+    expect![[r#"
+        #ifdef SKINNED
+        fn vertex(vertex: Vertex, skin: Skin) -> VertexOutput {
+        #else
+        fn vertex(vertex: Vertex) -> VertexOutput {
+        #endif
+            var out: VertexOutput;
+            return out;
+        }"#]]
+    .assert_eq(&after);
+    assert_eq!(
+        crate::format_str(&after, &FormattingOptions::default()),
+        after
+    );
+}
+
+#[test]
+fn format_preprocessor_unparsable_branch_is_kept() {
+    let before = "#ifdef A
+fn a(  ) {
+#else
+fn a( ) {{
+#endif
+    return;
+}";
+    let after = crate::format_str(before, &FormattingOptions::default());
+    expect![[r#"
+        #ifdef A
+        fn a() {
+        #else
+        fn a( ) {{
+        #endif
+            return;
+        }"#]]
+    .assert_eq(&after);
+}
+
+#[test]
+fn format_preprocessor_alternative_arguments() {
+    let before = "fn main() {
+    let v = vec2(1.0,
+#ifdef C
+    2.0
+#else
+  3.0
+#endif
+    );
+}";
+    let after = crate::format_str(before, &FormattingOptions::default());
+    expect![[r#"
+        fn main() {
+            let v = vec2(1.0,
+                #ifdef C
+                2.0
+                #else
+                3.0
+                #endif
+            );
+        }"#]]
+    .assert_eq(&after);
+}
+
+#[test]
+fn try_format_reports_parse_errors() {
+    let options = FormattingOptions::default();
+    let errors = crate::try_format_str("fn main() { let x = ; }", Edition::DEFAULT, &options)
+        .unwrap_err();
+    assert_eq!(errors.len(), 1);
+
+    // Branches which parse on their own are not errors.
+    let formatted = crate::try_format_str(
+        "#ifdef A\nfn a(x: f32) {\n#else\nfn a() {\n#endif\n}\n",
+        Edition::DEFAULT,
+        &options,
+    );
+    assert!(formatted.is_ok());
+}
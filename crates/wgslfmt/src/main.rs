@@ -1,21 +1,72 @@
-use std::{io::Read, path::PathBuf};
+mod report;
+#[cfg(test)]
+mod tests;
+
+use std::{
+    collections::hash_map::Entry,
+    io::Read,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
 
 use anyhow::Context;
+use ignore::{WalkBuilder, overrides::OverrideBuilder};
 use lexopt::prelude::*;
-use wgsl_formatter::FormattingOptions;
+use rayon::prelude::*;
+use rustc_hash::FxHashMap;
+use wgsl_formatter::{Config, Edition, FormattingOptions, ParseError};
+
+use crate::report::{Mismatch, OutputFormat};
 
-const HELP_STR: &str = r#"wgslfmt [options] <file>...
+const HELP_STR: &str = r#"wgslfmt [options] [<file or directory>...]
+
+Formats the given files, and the `.wgsl` and `.wesl` files in the given
+directories and their subdirectories, skipping files ignored by `.gitignore`.
+Reads from standard input if no path is given or the path is `-`.
 
 Options:
-    --check     Run in 'check' mode. Exists with 0 if input is formatted correctly.
+    --check     Run in 'check' mode. Exits with 0 if input is formatted correctly.
                 Exits with 1 and prints a diff if formatting is required.
+    --output-format <human|json|checkstyle|github>
+                How `--check` reports unformatted files. Defaults to `human`.
+    --emit <files|stdout>
+                Where to write the formatted code. Defaults to `files`, or to
+                `stdout` when reading from standard input.
+    --exclude <glob>
+                Skip files and directories matching the glob while traversing
+                directories. Can be given multiple times.
     --tabs      Use tabs for indentation (instead of spaces)
+    --config <path>
+                Use this configuration file instead of the `wgslfmt.toml` or
+                `.wgslfmt.toml` found in the directory of each file or its ancestors.
+
+Exit codes:
+    0   Success.
+    1   With `--check`, some files are not formatted.
+    2   Some files could not be parsed. They are left unchanged.
+    3   Any other error, such as invalid arguments or unreadable files.
 "#;
 
+const EXIT_UNFORMATTED: u8 = 1;
+const EXIT_PARSE_ERROR: u8 = 2;
+const EXIT_ERROR: u8 = 3;
+
+const EXTENSIONS: [&str; 2] = ["wgsl", "wesl"];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Emit {
+    Files,
+    Stdout,
+}
+
 struct Arguments {
     check: bool,
     tab_indent: bool,
-    files: Vec<PathBuf>,
+    config: Option<PathBuf>,
+    emit: Option<Emit>,
+    output_format: OutputFormat,
+    exclude: Vec<String>,
+    paths: Vec<PathBuf>,
 }
 
 fn parse_arguments() -> Result<Arguments, lexopt::Error> {
@@ -23,7 +74,11 @@ fn parse_arguments() -> Result<Arguments, lexopt::Error> {
     let mut arguments = Arguments {
         check: false,
         tab_indent: false,
-        files: Vec::new(),
+        config: None,
+        emit: None,
+        output_format: OutputFormat::Human,
+        exclude: Vec::new(),
+        paths: Vec::new(),
     };
 
     while let Some(arg) = parser.next()? {
@@ -34,51 +89,280 @@ fn parse_arguments() -> Result<Arguments, lexopt::Error> {
             },
             Long("check") => arguments.check = true,
             Long("tabs") => arguments.tab_indent = true,
-            Value(file) => arguments.files.push(PathBuf::from(file)),
+            Long("config") => arguments.config = Some(PathBuf::from(parser.value()?)),
+            Long("output-format") => arguments.output_format = parser.value()?.parse()?,
+            Long("emit") => {
+                arguments.emit = Some(match parser.value()?.string()?.as_str() {
+                    "files" => Emit::Files,
+                    "stdout" => Emit::Stdout,
+                    other => {
+                        return Err(format!(
+                            "unknown emit mode `{other}`, expected `files` or `stdout`"
+                        )
+                        .into());
+                    },
+                });
+            },
+            Long("exclude") => arguments.exclude.push(parser.value()?.string()?),
+            Value(path) => arguments.paths.push(PathBuf::from(path)),
             _ => return Err(arg.unexpected()),
         }
     }
     Ok(arguments)
 }
 
-fn main() -> Result<(), anyhow::Error> {
-    let mut arguments = parse_arguments()?;
+fn main() -> ExitCode {
+    match run() {
+        Ok(code) => code,
+        Err(error) => {
+            eprintln!("error: {error:#}");
+            ExitCode::from(EXIT_ERROR)
+        },
+    }
+}
 
-    if arguments.files.is_empty() {
-        arguments.files.push(PathBuf::from("-"))
+fn run() -> Result<ExitCode, anyhow::Error> {
+    // The sources of lexopt's errors repeat their messages.
+    let arguments = parse_arguments().map_err(|error| anyhow::anyhow!("{error}"))?;
+    if arguments.check && arguments.emit.is_some() {
+        anyhow::bail!("`--check` and `--emit` cannot be combined");
     }
 
-    for file in arguments.files {
-        let is_stdin = file.as_os_str() == "-";
-        let input = if is_stdin {
-            read_stdin()?
-        } else {
-            std::fs::read_to_string(&file)?
+    let mut options = OptionsResolver {
+        explicit_config: arguments.config.as_deref().map(Config::load).transpose()?,
+        configs: FxHashMap::default(),
+        tab_indent: arguments.tab_indent,
+    };
+
+    if reads_stdin(&arguments.paths)? {
+        if arguments.emit == Some(Emit::Files) {
+            anyhow::bail!("`--emit files` cannot be used with standard input");
+        }
+        let input = read_stdin()?;
+        // Standard input is formatted with the configuration of the working directory.
+        let options = options.for_path(&std::env::current_dir()?)?;
+        let file = FormattedFile {
+            path: PathBuf::from("<stdin>"),
+            result: wgsl_formatter::try_format_str(&input, Edition::DEFAULT, &options),
+            input,
         };
+        return Ok(report(&[file], &arguments, Emit::Stdout));
+    }
 
-        let mut formatting_options = FormattingOptions::default();
-        if arguments.tab_indent {
-            formatting_options.indent_symbol = "\t".to_string();
+    let paths = collect_files(&arguments.paths, &arguments.exclude)?;
+    let files_with_options = paths
+        .into_iter()
+        .map(|path| {
+            let options = options.for_path(&path)?;
+            Ok((path, options))
+        })
+        .collect::<Result<Vec<_>, anyhow::Error>>()?;
+
+    let files = files_with_options
+        .into_par_iter()
+        .map(|(path, options)| {
+            let input = std::fs::read_to_string(&path)
+                .with_context(|| format!("failed to read {}", path.display()))?;
+            let result = wgsl_formatter::try_format_str(&input, edition_of(&path), &options);
+            Ok(FormattedFile {
+                path,
+                input,
+                result,
+            })
+        })
+        .collect::<Result<Vec<_>, anyhow::Error>>()?;
+
+    let code = report(&files, &arguments, arguments.emit.unwrap_or(Emit::Files));
+    if !arguments.check && arguments.emit != Some(Emit::Stdout) {
+        for file in &files {
+            let Ok(output) = &file.result else {
+                continue;
+            };
+            if *output != file.input {
+                std::fs::write(&file.path, output)
+                    .with_context(|| format!("failed to write to {}", file.path.display()))?;
+            }
+        }
+    }
+    Ok(code)
+}
+
+/// Whether the input is standard input, which is read when no path or just `-` is given.
+fn reads_stdin(paths: &[PathBuf]) -> Result<bool, anyhow::Error> {
+    match paths {
+        [] => Ok(true),
+        [path] => Ok(path.as_os_str() == "-"),
+        paths => {
+            if paths.iter().any(|path| path.as_os_str() == "-") {
+                anyhow::bail!("`-` (standard input) cannot be combined with other paths");
+            }
+            Ok(false)
+        },
+    }
+}
+
+struct FormattedFile {
+    path: PathBuf,
+    input: String,
+    result: Result<String, Vec<ParseError>>,
+}
+
+/// Prints the parse errors and, depending on the mode, the differences or the
+/// formatted code, and returns the exit code.
+fn report(
+    files: &[FormattedFile],
+    arguments: &Arguments,
+    emit: Emit,
+) -> ExitCode {
+    let mut has_parse_errors = false;
+    for file in files {
+        if let Err(errors) = &file.result {
+            has_parse_errors = true;
+            for error in errors {
+                let (line, column) = line_column(&file.input, usize::from(error.range.start()));
+                eprintln!(
+                    "error: failed to parse {}:{line}:{column}: {}",
+                    file.path.display(),
+                    error.message()
+                );
+            }
+        }
+    }
+
+    let mut has_mismatches = false;
+    if arguments.check {
+        let mismatches: Vec<Mismatch<'_>> = files
+            .iter()
+            .filter_map(|file| {
+                let output = file.result.as_ref().ok()?;
+                (*output != file.input).then(|| Mismatch {
+                    path: &file.path,
+                    original: &file.input,
+                    expected: output,
+                    hunks: report::hunks(&file.input, output),
+                })
+            })
+            .collect();
+        has_mismatches = !mismatches.is_empty();
+        report::print(arguments.output_format, &mismatches);
+    } else if emit == Emit::Stdout {
+        for file in files {
+            let output = file.result.as_deref().unwrap_or(&file.input);
+            if files.len() > 1 {
+                println!("{}:\n", file.path.display());
+            }
+            print!("{output}");
         }
-        let output = wgsl_formatter::format_str(&input, &formatting_options);
+    }
 
-        if arguments.check {
-            let same = output == input;
-            if !same {
-                let diff = prettydiff::diff_lines(&input, &output);
+    if has_parse_errors {
+        ExitCode::from(EXIT_PARSE_ERROR)
+    } else if has_mismatches {
+        ExitCode::from(EXIT_UNFORMATTED)
+    } else {
+        ExitCode::SUCCESS
+    }
+}
 
-                println!("Diff in {}\n{}:", file.display(), diff);
-                std::process::exit(1);
+/// Expands directories into the shader files inside them, in a stable order.
+fn collect_files(
+    paths: &[PathBuf],
+    exclude: &[String],
+) -> Result<Vec<PathBuf>, anyhow::Error> {
+    let mut files = Vec::new();
+    for path in paths {
+        if !path.is_dir() {
+            files.push(path.clone());
+            continue;
+        }
+        let mut overrides = OverrideBuilder::new(path);
+        for glob in exclude {
+            overrides
+                .add(&format!("!{glob}"))
+                .with_context(|| format!("invalid glob `{glob}`"))?;
+        }
+        let walk = WalkBuilder::new(path)
+            .overrides(overrides.build()?)
+            // `.gitignore` files apply even outside of a git repository.
+            .require_git(false)
+            .sort_by_file_path(Path::cmp)
+            .build();
+        for entry in walk {
+            let entry = entry?;
+            let is_shader = entry.path().extension().is_some_and(|extension| {
+                EXTENSIONS
+                    .iter()
+                    .any(|shader_extension| extension == *shader_extension)
+            });
+            if is_shader
+                && entry
+                    .file_type()
+                    .is_some_and(|file_type| file_type.is_file())
+            {
+                files.push(entry.into_path());
             }
-        } else if is_stdin {
-            print!("{}", output);
-        } else {
-            std::fs::write(&file, output)
-                .with_context(|| format!("failed to write to {}", file.display()))?;
         }
     }
+    Ok(files)
+}
+
+fn edition_of(path: &Path) -> Edition {
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("wesl") => Edition::Wesl0_0_1,
+        Some("wgsl") => Edition::Wgsl,
+        _ => Edition::DEFAULT,
+    }
+}
+
+/// Finds and caches the configuration of every formatted file.
+struct OptionsResolver {
+    explicit_config: Option<Config>,
+    configs: FxHashMap<PathBuf, Config>,
+    tab_indent: bool,
+}
+
+impl OptionsResolver {
+    fn for_path(
+        &mut self,
+        path: &Path,
+    ) -> Result<FormattingOptions, anyhow::Error> {
+        let mut formatting_options = FormattingOptions::default();
+        let config = match &self.explicit_config {
+            Some(config) => Some(config),
+            None => match wgsl_formatter::find_config_file(path) {
+                Some(config_path) => Some(match self.configs.entry(config_path) {
+                    Entry::Occupied(entry) => &*entry.into_mut(),
+                    Entry::Vacant(entry) => {
+                        let config = Config::load(entry.key())?;
+                        &*entry.insert(config)
+                    },
+                }),
+                None => None,
+            },
+        };
+        if let Some(config) = config {
+            config.apply(&mut formatting_options);
+        }
+        if self.tab_indent {
+            formatting_options.indent_symbol = "\t".to_string();
+        }
+        Ok(formatting_options)
+    }
+}
 
-    Ok(())
+/// One-based line and column of `offset`.
+fn line_column(
+    text: &str,
+    offset: usize,
+) -> (usize, usize) {
+    let before = &text[..offset.min(text.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rsplit('\n')
+        .next()
+        .map_or(0, |line| line.chars().count())
+        + 1;
+    (line, column)
 }
 
 fn read_stdin() -> Result<String, std::io::Error> {
@@ -0,0 +1,229 @@
+//! The output of `--check`: the differences between a file and its formatted
+//! version, printed for humans or in a machine-readable format.
+
+use std::{fmt::Write as _, path::Path};
+
+use dissimilar::Chunk;
+use rustc_hash::FxHashMap;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum OutputFormat {
+    Human,
+    Json,
+    Checkstyle,
+    GitHub,
+}
+
+impl std::str::FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text {
+            "human" => Ok(Self::Human),
+            "json" => Ok(Self::Json),
+            "checkstyle" => Ok(Self::Checkstyle),
+            "github" => Ok(Self::GitHub),
+            _ => Err(format!(
+                "unknown output format `{text}`, expected `human`, `json`, `checkstyle` or `github`"
+            )),
+        }
+    }
+}
+
+/// A run of lines which differ. Lines are numbered from 1, and the end lines are
+/// inclusive, so an insertion ends one line before it begins.
+pub(crate) struct Hunk {
+    pub original_begin_line: usize,
+    pub original_end_line: usize,
+    pub expected_begin_line: usize,
+    pub expected_end_line: usize,
+    pub original: String,
+    pub expected: String,
+}
+
+pub(crate) struct Mismatch<'a> {
+    pub path: &'a Path,
+    pub original: &'a str,
+    pub expected: &'a str,
+    pub hunks: Vec<Hunk>,
+}
+
+/// Diffs `original` and `expected` line by line.
+pub(crate) fn hunks(
+    original: &str,
+    expected: &str,
+) -> Vec<Hunk> {
+    // `dissimilar` diffs characters, so every distinct line is mapped to one.
+    let mut interned: FxHashMap<&str, char> = FxHashMap::default();
+    let original_encoded = encode_lines(original, &mut interned);
+    let expected_encoded = encode_lines(expected, &mut interned);
+    let original_lines: Vec<&str> = original.split_inclusive('\n').collect();
+    let expected_lines: Vec<&str> = expected.split_inclusive('\n').collect();
+
+    let mut hunks: Vec<Hunk> = Vec::new();
+    let (mut original_line, mut expected_line) = (0, 0);
+    let mut current: Option<(usize, usize)> = None;
+    let mut flush = |current: &mut Option<(usize, usize)>, original_line, expected_line| {
+        if let Some((original_begin, expected_begin)) = current.take() {
+            hunks.push(Hunk {
+                original_begin_line: original_begin + 1,
+                original_end_line: original_line,
+                expected_begin_line: expected_begin + 1,
+                expected_end_line: expected_line,
+                original: original_lines[original_begin..original_line].concat(),
+                expected: expected_lines[expected_begin..expected_line].concat(),
+            });
+        }
+    };
+    for chunk in dissimilar::diff(&original_encoded, &expected_encoded) {
+        match chunk {
+            Chunk::Equal(lines) => {
+                flush(&mut current, original_line, expected_line);
+                original_line += lines.chars().count();
+                expected_line += lines.chars().count();
+            },
+            Chunk::Delete(lines) => {
+                current.get_or_insert((original_line, expected_line));
+                original_line += lines.chars().count();
+            },
+            Chunk::Insert(lines) => {
+                current.get_or_insert((original_line, expected_line));
+                expected_line += lines.chars().count();
+            },
+        }
+    }
+    flush(&mut current, original_line, expected_line);
+    hunks
+}
+
+fn encode_lines<'a>(
+    text: &'a str,
+    interned: &mut FxHashMap<&'a str, char>,
+) -> String {
+    text.split_inclusive('\n')
+        .map(|line| {
+            let next = line_char(interned.len());
+            *interned.entry(line).or_insert(next)
+        })
+        .collect()
+}
+
+/// Characters from the private use areas, which cannot clash with each other.
+fn line_char(index: usize) -> char {
+    const BMP_AREA: usize = 0xF8FF - 0xE000 + 1;
+    let code = if index < BMP_AREA {
+        0xE000 + index
+    } else {
+        0xF0000 + index - BMP_AREA
+    };
+    char::from_u32(code as u32).expect("fewer distinct lines than private use characters")
+}
+
+pub(crate) fn print(
+    format: OutputFormat,
+    mismatches: &[Mismatch<'_>],
+) {
+    match format {
+        OutputFormat::Human => {
+            for mismatch in mismatches {
+                let diff = prettydiff::diff_lines(mismatch.original, mismatch.expected);
+                println!("Diff in {}:\n{}", mismatch.path.display(), diff);
+            }
+        },
+        OutputFormat::Json => println!("{}", json(mismatches)),
+        OutputFormat::Checkstyle => print!("{}", checkstyle(mismatches)),
+        OutputFormat::GitHub => print!("{}", github(mismatches)),
+    }
+}
+
+/// Follows the layout of `rustfmt --check --message-format json`.
+pub(crate) fn json(mismatches: &[Mismatch<'_>]) -> serde_json::Value {
+    mismatches
+        .iter()
+        .map(|mismatch| {
+            serde_json::json!({
+                "name": mismatch.path.display().to_string(),
+                "mismatches": mismatch
+                    .hunks
+                    .iter()
+                    .map(|hunk| serde_json::json!({
+                        "original_begin_line": hunk.original_begin_line,
+                        "original_end_line": hunk.original_end_line,
+                        "expected_begin_line": hunk.expected_begin_line,
+                        "expected_end_line": hunk.expected_end_line,
+                        "original": hunk.original,
+                        "expected": hunk.expected,
+                    }))
+                    .collect::<Vec<_>>(),
+            })
+        })
+        .collect()
+}
+
+pub(crate) fn checkstyle(mismatches: &[Mismatch<'_>]) -> String {
+    let mut output = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    output.push_str("<checkstyle version=\"4.3\">\n");
+    for mismatch in mismatches {
+        let _ = writeln!(
+            output,
+            "<file name=\"{}\">",
+            xml_escape(&mismatch.path.display().to_string())
+        );
+        for hunk in &mismatch.hunks {
+            let _ = writeln!(
+                output,
+                "<error line=\"{}\" severity=\"warning\" message=\"{}\" />",
+                hunk.original_begin_line,
+                xml_escape(&message(hunk)),
+            );
+        }
+        output.push_str("</file>\n");
+    }
+    output.push_str("</checkstyle>\n");
+    output
+}
+
+/// GitHub Actions workflow commands, which annotate the lines of the hunks.
+pub(crate) fn github(mismatches: &[Mismatch<'_>]) -> String {
+    let mut output = String::new();
+    for mismatch in mismatches {
+        for hunk in &mismatch.hunks {
+            let _ = writeln!(
+                output,
+                "::error file={},line={},endLine={},title=wgslfmt::{}",
+                mismatch.path.display(),
+                hunk.original_begin_line,
+                hunk.original_end_line.max(hunk.original_begin_line),
+                github_escape(&message(hunk)),
+            );
+        }
+    }
+    output
+}
+
+fn message(hunk: &Hunk) -> String {
+    format!("Should be `{}`", hunk.expected.trim_end_matches('\n'))
+}
+
+fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for character in text.chars() {
+        match character {
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '&' => escaped.push_str("&amp;"),
+            '\n' => escaped.push_str("&#10;"),
+            _ => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+/// Workflow commands end at the first newline, so the message is percent-encoded.
+fn github_escape(text: &str) -> String {
+    text.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
@@ -0,0 +1,179 @@
+use std::path::{Path, PathBuf};
+
+use expect_test::{Expect, expect};
+
+use crate::{
+    collect_files, reads_stdin,
+    report::{self, Mismatch},
+};
+
+fn check_hunks(
+    original: &str,
+    expected: &str,
+    expect: Expect,
+) {
+    let actual: String = report::hunks(original, expected)
+        .iter()
+        .map(|hunk| {
+            format!(
+                "{}-{} -> {}-{}: {:?} -> {:?}\n",
+                hunk.original_begin_line,
+                hunk.original_end_line,
+                hunk.expected_begin_line,
+                hunk.expected_end_line,
+                hunk.original,
+                hunk.expected,
+            )
+        })
+        .collect();
+    expect.assert_eq(&actual);
+}
+
+#[test]
+fn hunks_group_adjacent_lines() {
+    check_hunks(
+        "a\nb\nc\nd\ne\nf\ng\n",
+        "a\nB\nC\nd\ne\nf\ng\nh\n",
+        expect![[r#"
+            2-3 -> 2-3: "b\nc\n" -> "B\nC\n"
+            8-7 -> 8-8: "" -> "h\n"
+        "#]],
+    );
+}
+
+#[test]
+fn hunks_of_identical_text() {
+    check_hunks("a\nb\n", "a\nb\n", expect![""]);
+}
+
+const FILES: [(&str, &str, &str); 2] = [
+    ("shaders/main.wgsl", "let a=b<c;\n", "let a = b < c;\n"),
+    (
+        "shaders/util.wgsl",
+        "fn f(){return;}\n",
+        "fn f() {\n    return;\n}\n",
+    ),
+];
+
+fn mismatches() -> Vec<Mismatch<'static>> {
+    FILES
+        .iter()
+        .map(|&(path, original, expected)| Mismatch {
+            path: Path::new(path),
+            original,
+            expected,
+            hunks: report::hunks(original, expected),
+        })
+        .collect()
+}
+
+#[test]
+fn json_output() {
+    assert_eq!(
+        report::json(&mismatches()),
+        serde_json::json!([
+            {
+                "name": "shaders/main.wgsl",
+                "mismatches": [{
+                    "original_begin_line": 1,
+                    "original_end_line": 1,
+                    "expected_begin_line": 1,
+                    "expected_end_line": 1,
+                    "original": "let a=b<c;\n",
+                    "expected": "let a = b < c;\n",
+                }],
+            },
+            {
+                "name": "shaders/util.wgsl",
+                "mismatches": [{
+                    "original_begin_line": 1,
+                    "original_end_line": 1,
+                    "expected_begin_line": 1,
+                    "expected_end_line": 3,
+                    "original": "fn f(){return;}\n",
+                    "expected": "fn f() {\n    return;\n}\n",
+                }],
+            },
+        ])
+    );
+}
+
+#[test]
+fn checkstyle_output() {
+    expect![[r#"
+        <?xml version="1.0" encoding="utf-8"?>
+        <checkstyle version="4.3">
+        <file name="shaders/main.wgsl">
+        <error line="1" severity="warning" message="Should be `let a = b &lt; c;`" />
+        </file>
+        <file name="shaders/util.wgsl">
+        <error line="1" severity="warning" message="Should be `fn f() {&#10;    return;&#10;}`" />
+        </file>
+        </checkstyle>
+    "#]]
+    .assert_eq(&report::checkstyle(&mismatches()));
+}
+
+#[test]
+fn github_output() {
+    expect![[r#"
+        ::error file=shaders/main.wgsl,line=1,endLine=1,title=wgslfmt::Should be `let a = b < c;`
+        ::error file=shaders/util.wgsl,line=1,endLine=1,title=wgslfmt::Should be `fn f() {%0A    return;%0A}`
+    "#]]
+    .assert_eq(&report::github(&mismatches()));
+}
+
+#[test]
+fn standard_input_is_not_combined_with_paths() {
+    let paths = |paths: &[&str]| paths.iter().map(PathBuf::from).collect::<Vec<_>>();
+    assert!(reads_stdin(&paths(&[])).unwrap());
+    assert!(reads_stdin(&paths(&["-"])).unwrap());
+    assert!(!reads_stdin(&paths(&["a.wgsl", "b.wgsl"])).unwrap());
+    assert!(reads_stdin(&paths(&["-", "a.wgsl"])).is_err());
+    assert!(reads_stdin(&paths(&["a.wgsl", "-"])).is_err());
+}
+
+#[test]
+fn collect_files_of_directories() {
+    let root = std::env::temp_dir().join(format!("wgslfmt-collect-files-{}", std::process::id()));
+    let files = [
+        ".gitignore",
+        "a.wesl",
+        "b.wgsl",
+        "notes.txt",
+        "ignored.wgsl",
+        "nested/c.wgsl",
+        "excluded/d.wgsl",
+    ];
+    for file in files {
+        let path = root.join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let text = if file == ".gitignore" {
+            "ignored.wgsl\n"
+        } else {
+            ""
+        };
+        std::fs::write(path, text).unwrap();
+    }
+
+    let collected = collect_files(
+        &[root.clone(), PathBuf::from("explicit.txt")],
+        &["excluded".to_owned()],
+    );
+    std::fs::remove_dir_all(&root).unwrap();
+
+    let collected: Vec<_> = collected
+        .unwrap()
+        .iter()
+        .map(|path| {
+            path.strip_prefix(&root)
+                .unwrap_or(path)
+                .display()
+                .to_string()
+        })
+        .collect();
+    assert_eq!(
+        collected,
+        ["a.wesl", "b.wgsl", "nested/c.wgsl", "explicit.txt"]
+    );
+}
@@ -197,9 +197,9 @@ export async function createClient(
 				const result: (vscode.CodeAction | vscode.Command)[] = [];
 				const groups = new Map<string, { index: number; items: vscode.CodeAction[] }>();
 				for (const item of values) {
-					// In our case we expect to get code edits only from diagnostics
+					// In our case we expect to get code edits only from diagnostics, and commands only
+					// from actions which open generated documents
 					if (lc.CodeAction.is(item)) {
-						assert(!item.command, "We don't expect to receive commands in CodeActions");
 						const action = await client.protocol2CodeConverter.asCodeAction(
 							item,
 							token,
@@ -687,6 +687,16 @@ export function runFlycheck(ctx: CtxInit): Cmd {
 	};
 }
 
+export function openRustMirror(_: CtxInit): Cmd {
+	return async (text: string) => {
+		const document = await vscode.workspace.openTextDocument({
+			language: "rust",
+			content: text,
+		});
+		await vscode.window.showTextDocument(document, vscode.ViewColumn.Beside);
+	};
+}
+
 export function resolveCodeAction(ctx: CtxInit): Cmd {
 	return async (parameters: lc.CodeAction) => {
 		const client = ctx.client;
@@ -29,13 +29,37 @@ export interface InlayHintsConfig {
 	enabled: boolean;
 	typeHints: boolean;
 	parameterHints: boolean;
+	structLayoutHints: boolean;
+	structLayoutHintKinds: ("offset" | "size" | "align" | "padding")[];
+	constValueHints: boolean;
+	conversionHints: boolean;
+	accessModeHints: boolean;
+	closingBraceHints: { enable: boolean; minLines: number };
 	typeVerbosity: "full" | "short" | "compact";
 }
 
+export interface SnippetDef {
+	prefix?: string | string[];
+	postfix?: string | string[];
+	body: string | string[];
+	description?: string;
+	scope?: "item" | "statement" | "expression";
+}
+
+export interface CompletionConfig {
+	autoimport: { enable: boolean };
+	callable: { snippets: "fill_arguments" | "add_parentheses" | "none" };
+	postfix: { enable: boolean };
+	snippets: { custom: Record<string, SnippetDef> };
+}
+
 export interface DiagnosticsConfig {
 	typeErrors: boolean;
 	nagaParsing: boolean;
 	nagaValidation: boolean;
+	severity: Record<string, "error" | "warning" | "info" | "hint" | "off">;
+	warningsAsInfo: string[];
+	warningsAsHint: string[];
 }
 
 export class Config {
@@ -377,6 +401,10 @@ export class Config {
 		return this.get<[string]>("preprocessor.shaderDefs");
 	}
 
+	get edition(): "wgsl" | "wesl" | undefined {
+		return this.get<"wgsl" | "wesl">("edition");
+	}
+
 	get trace(): TraceConfig | undefined {
 		return this.get("trace");
 	}
@@ -384,6 +412,10 @@ export class Config {
 	get inlayHints(): InlayHintsConfig | undefined {
 		return this.get("inlayHints");
 	}
+
+	get completion(): CompletionConfig | undefined {
+		return this.get("completion");
+	}
 }
 
 export function prepareVSCodeConfig<T>(response: T): T {
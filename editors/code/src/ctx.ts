@@ -16,7 +16,7 @@ import type { WgslAnalyzerExtensionApi } from "./main";
 
 import { promisify } from "util";
 import { readFile } from "fs";
-import { DiagnosticsConfig, InlayHintsConfig, TraceConfig } from "./config";
+import { CompletionConfig, DiagnosticsConfig, InlayHintsConfig, TraceConfig } from "./config";
 
 // We only support local folders, not eg. Live Share (`vlsl:` scheme), so do not activate if
 // only those are in use. We use "Empty" to represent these scenarios.
@@ -62,9 +62,11 @@ export type CtxInit = Ctx & {
 interface WGSLAnalyzerConfiguration {
 	customImports: Record<string, string>;
 	shaderDefs: [string];
+	edition: "wgsl" | "wesl";
 	trace: TraceConfig;
 	diagnostics: DiagnosticsConfig;
 	inlayHints: InlayHintsConfig;
+	completion: CompletionConfig;
 }
 
 async function lspOptions(config: Config): Promise<WGSLAnalyzerConfiguration> {
@@ -89,9 +91,11 @@ async function lspOptions(config: Config): Promise<WGSLAnalyzerConfiguration> {
 	return {
 		customImports,
 		shaderDefs: config.shaderDefs!,
+		edition: config.edition!,
 		diagnostics: config.diagnostics!,
 		trace: config.trace!,
 		inlayHints: config.inlayHints!,
+		completion: config.completion!,
 	};
 }
 
@@ -345,6 +349,10 @@ export class Ctx implements WgslAnalyzerExtensionApi {
 				vscode.workspace.openTextDocument(parameters.uri);
 				return;
 			}),
+			vscode.workspace.registerTextDocumentContentProvider(wa.BUILTIN_DOCUMENT_SCHEME, {
+				provideTextDocumentContent: (uri, token) =>
+					client.sendRequest(wa.builtinDocument, { uri: uri.toString() }, token),
+			}),
 		);
 		this.updateCommands();
 		if (this.config.showSyntaxTree) {
@@ -311,6 +311,16 @@ export const fullSource = new lc.RequestType<FullSourceParameters, string, void>
 	"wgsl-analyzer/fullSource",
 );
 
+/** The scheme of the read-only documents declaring the builtin functions. */
+export const BUILTIN_DOCUMENT_SCHEME = "wgsl-analyzer-builtin";
+
+export interface BuiltinDocumentParameters {
+	uri: string;
+}
+export const builtinDocument = new lc.RequestType<BuiltinDocumentParameters, string, void>(
+	"wgsl-analyzer/builtinDocument",
+);
+
 export const requestConfiguration = new lc.RequestType<void, unknown, void>(
 	"wgsl-analyzer/requestConfiguration",
 );
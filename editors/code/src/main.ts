@@ -168,6 +168,7 @@ function createCommands(): Record<string, CommandFactory> {
 		applyActionGroup: { enabled: commands.applyActionGroup },
 		applySnippetWorkspaceEdit: { enabled: commands.applySnippetWorkspaceEditCommand },
 		gotoLocation: { enabled: commands.gotoLocation },
+		openRustMirror: { enabled: commands.openRustMirror },
 		hoverRefCommandProxy: { enabled: commands.hoverRefCommandProxy },
 		resolveCodeAction: { enabled: commands.resolveCodeAction },
 		showReferences: { enabled: commands.showReferences },